@@ -23,6 +23,12 @@ impl Default for ColorInfo {
 }
 
 pub fn extract_icc_profile(path: &Path) -> Option<Vec<u8>> {
+    if crate::heif::is_heif(path) {
+        return crate::heif::icc_profile(path);
+    }
+    if crate::jxl::is_jxl(path) {
+        return crate::jxl::icc_profile(path);
+    }
     let reader = image::ImageReader::open(path)
         .ok()?
         .with_guessed_format()
@@ -79,10 +85,154 @@ pub fn rgba8_to_srgb_with_icc(rgba: &[u8], width: u32, height: u32, icc: Option<
     out
 }
 
+/// sRGB transfer function for one channel in 0.0–1.0. Mirrors
+/// `linear_to_srgb` in `image.wgsl`, so the software and Vulkan paths agree.
+pub fn linear_to_srgb(linear: f32) -> f32 {
+    let linear = linear.clamp(0.0, 1.0);
+    if linear <= 0.0031308 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// ACES filmic curve for one channel of scene-linear light. Mirrors
+/// `aces_tonemap` in `image.wgsl`.
+pub fn aces_tonemap(x: f32) -> f32 {
+    let (a, b, c, d, e) = (2.51, 0.03, 2.43, 0.59, 0.14);
+    ((x * (a * x + b)) / (x * (c * x + d) + e)).clamp(0.0, 1.0)
+}
+
+/// Scene-linear float RGBA, e.g. from OpenEXR or Radiance HDR, tone mapped
+/// and encoded to 8-bit sRGB the way the shader shows it.
+pub fn linear_f32_to_srgb_8(rgba: &[f32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(rgba.len());
+    for px in rgba.chunks_exact(4) {
+        for &v in &px[..3] {
+            out.push((linear_to_srgb(aces_tonemap(v.max(0.0))) * 255.0).round() as u8);
+        }
+        out.push((px[3].clamp(0.0, 1.0) * 255.0).round() as u8);
+    }
+    out
+}
+
+/// IEEE half-precision bits for `v`, rounding to nearest, for uploading
+/// float images as 16-bit float textures. Out-of-range values become
+/// infinity and NaN stays NaN.
+pub fn f32_to_f16_bits(v: f32) -> u16 {
+    let bits = v.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xFF) as i32;
+    let mantissa = bits & 0x007F_FFFF;
+    if exp == 0xFF {
+        let nan = if mantissa != 0 { 0x0200 } else { 0 };
+        return sign | 0x7C00 | nan;
+    }
+    let exp = exp - 127 + 15;
+    if exp >= 0x1F {
+        return sign | 0x7C00;
+    }
+    if exp <= 0 {
+        // Subnormal or zero: shift the implicit leading bit in.
+        if exp < -10 {
+            return sign;
+        }
+        let m = mantissa | 0x0080_0000;
+        let shift = (14 - exp) as u32;
+        let half = 1 << (shift - 1);
+        let rounded = (m + half - 1 + ((m >> shift) & 1)) >> shift;
+        return sign | rounded as u16;
+    }
+    let rounded = mantissa + 0x0FFF + ((mantissa >> 13) & 1);
+    // A carry out of the mantissa bumps the exponent, as it should.
+    let out = ((exp as u32) << 10) + (rounded >> 13);
+    if out >= 0x7C00 {
+        return sign | 0x7C00;
+    }
+    sign | out as u16
+}
+
+/// Scene dynamic range of float RGBA pixels in stops: the luminance ratio
+/// between the brightest and darkest lit pixels, ignoring the outer 0.1%
+/// at each end so a few hot or noisy pixels don't dominate. `None` when
+/// fewer than two distinct lit levels exist.
+pub fn dynamic_range_stops(rgba: &[f32]) -> Option<f32> {
+    // Every pixel of a 24-megapixel frame isn't needed for two percentiles.
+    let pixels = rgba.len() / 4;
+    let step = (pixels / 1_000_000).max(1);
+    let mut luma: Vec<f32> = rgba
+        .chunks_exact(4)
+        .step_by(step)
+        .map(|px| 0.2126 * px[0] + 0.7152 * px[1] + 0.0722 * px[2])
+        .filter(|l| l.is_finite() && *l > 0.0)
+        .collect();
+    if luma.len() < 2 {
+        return None;
+    }
+    let cut = luma.len() / 1000;
+    let last = luma.len() - 1;
+    let (_, &mut lo, _) = luma.select_nth_unstable_by(cut, f32::total_cmp);
+    let (_, &mut hi, _) = luma.select_nth_unstable_by(last - cut, f32::total_cmp);
+    (hi > lo).then(|| (hi / lo).log2())
+}
+
+/// Human-readable name of an ICC profile, e.g. "Display P3".
+pub fn profile_description(icc: &[u8]) -> Option<String> {
+    let profile = lcms2::Profile::new_icc(icc).ok()?;
+    let name = profile.info(lcms2::InfoType::Description, lcms2::Locale::none())?;
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn linear_to_srgb_matches_reference_values() {
+        // 18% gray in linear light is 118/255 once encoded.
+        assert_eq!((linear_to_srgb(0.18) * 255.0).round(), 118.0);
+        assert_eq!(linear_to_srgb(0.0), 0.0);
+        assert!((linear_to_srgb(1.0) - 1.0).abs() < 1e-6);
+        assert_eq!(linear_to_srgb(2.0), linear_to_srgb(1.0));
+    }
+
+    #[test]
+    fn half_floats_round_trip_common_values() {
+        assert_eq!(f32_to_f16_bits(0.0), 0x0000);
+        assert_eq!(f32_to_f16_bits(-0.0), 0x8000);
+        assert_eq!(f32_to_f16_bits(1.0), 0x3C00);
+        assert_eq!(f32_to_f16_bits(-2.0), 0xC000);
+        assert_eq!(f32_to_f16_bits(0.5), 0x3800);
+        assert_eq!(f32_to_f16_bits(65504.0), 0x7BFF);
+        assert_eq!(f32_to_f16_bits(1e6), 0x7C00);
+        assert_eq!(f32_to_f16_bits(f32::NAN) & 0x7C00, 0x7C00);
+        // Smallest subnormal half.
+        assert_eq!(f32_to_f16_bits(2f32.powi(-24)), 0x0001);
+        // 1 + 2^-11 is halfway to the next half; ties go to even.
+        assert_eq!(f32_to_f16_bits(1.0 + 2f32.powi(-11)), 0x3C00);
+    }
+
+    #[test]
+    fn dynamic_range_spans_lit_pixels_in_stops() {
+        let mut rgba = Vec::new();
+        for level in [0.0f32, 0.01, 0.1, 1.0, 10.24] {
+            rgba.extend([level, level, level, 1.0]);
+        }
+        let stops = dynamic_range_stops(&rgba).unwrap();
+        assert!((stops - 10.0).abs() < 1e-3, "{stops}");
+        assert_eq!(dynamic_range_stops(&[0.5, 0.5, 0.5, 1.0]), None);
+        // Tone mapping keeps highlights below white rather than clipping.
+        assert!(aces_tonemap(4.0) < 1.0 && aces_tonemap(4.0) > aces_tonemap(1.0));
+    }
+
+    #[test]
+    fn profile_description_names_embedded_profiles() {
+        let icc = lcms2::Profile::new_srgb().icc().unwrap();
+        assert!(profile_description(&icc).is_some_and(|name| name.contains("sRGB")));
+        assert_eq!(profile_description(&[1, 2, 3, 4]), None);
+    }
+
     #[test]
     fn default_color_info_is_sdr_without_icc() {
         let c = ColorInfo::default();
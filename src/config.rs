@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::sort::SortOrder;
+use crate::thumbcache::ThumbSize;
+use crate::viewport::vk::renderer::{AlphaBackground, ImageTransition};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default = "default_width")]
@@ -10,9 +14,94 @@ pub struct Config {
     #[serde(default)]
     pub window_maximized: bool,
     #[serde(default)]
+    pub window_fullscreen: bool,
+    #[serde(default)]
     pub info_panel_visible: bool,
     #[serde(default)]
     pub last_directory: Option<String>,
+    /// In fit-width mode, scrolling past the bottom of an image opens the
+    /// next one at its top.
+    #[serde(default)]
+    pub advance_on_scroll_end: bool,
+    /// Rotate and mirror images according to their EXIF orientation tag.
+    /// Turn off to see the stored pixels as-is.
+    #[serde(default = "default_true")]
+    pub auto_orient: bool,
+    /// Decoded images kept in GPU memory for instant back-and-forth
+    /// navigation.
+    #[serde(default = "default_image_cache_size")]
+    pub image_cache_size: usize,
+    /// How long an image must take to load before the progress indicator
+    /// appears, so quick loads don't flash it.
+    #[serde(default = "default_loading_indicator_delay")]
+    pub loading_indicator_delay_ms: u64,
+    #[serde(default)]
+    pub alpha_background: AlphaBackground,
+    #[serde(default = "default_slideshow_interval")]
+    pub slideshow_interval_secs: u32,
+    /// Show every image once in random order, then reshuffle.
+    #[serde(default)]
+    pub slideshow_shuffle: bool,
+    /// Animation when moving from one image to another.
+    #[serde(default)]
+    pub image_transition: ImageTransition,
+    /// Also collect images from subdirectories of the opened directory.
+    #[serde(default)]
+    pub recursive_scan: bool,
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    /// Last quality (1–100) chosen when saving a JPEG.
+    #[serde(default = "default_jpeg_quality")]
+    pub jpeg_quality: u8,
+    /// Images opened from the file chooser, drag and drop or the command
+    /// line, most recent first.
+    #[serde(default)]
+    pub recent_files: Vec<String>,
+    /// Restore each image's zoom and position when returning to it within
+    /// a session. Off fits every image as it opens.
+    #[serde(default = "default_true")]
+    pub remember_view: bool,
+    /// The scroll wheel changes image, like pages in a document reader,
+    /// instead of zooming. Ctrl+scroll zooms either way.
+    #[serde(default)]
+    pub scroll_navigates: bool,
+    /// Going past the last image returns to the first, and vice versa.
+    /// Off, navigation stops at either end.
+    #[serde(default = "default_true")]
+    pub wrap_navigation: bool,
+    /// Browsing onto a file that fails to decode moves on to the next one
+    /// in the same direction. Opening it directly still shows the error.
+    #[serde(default = "default_true")]
+    pub skip_unreadable: bool,
+    /// Multisample the image so its edges stay smooth when rotated or
+    /// smaller than the window. Needs the Vulkan renderer.
+    #[serde(default = "default_true")]
+    pub antialiasing: bool,
+    /// Dither the image to hide banding in smooth gradients. Off keeps the
+    /// displayed pixels exact.
+    #[serde(default)]
+    pub dither: bool,
+    /// Allow panning the image completely out of view; off, part of it
+    /// always stays on screen.
+    #[serde(default)]
+    pub free_pan: bool,
+    /// Escape closes the window when it isn't fullscreen.
+    #[serde(default)]
+    pub escape_closes: bool,
+    /// Size of the thumbnails in the strip.
+    #[serde(default)]
+    pub thumb_size: ThumbSize,
+}
+
+/// Entries kept in `Config::recent_files`.
+pub const MAX_RECENT_FILES: usize = 10;
+
+/// Move `path` to the front of `recent`, dropping the oldest entries past
+/// `MAX_RECENT_FILES`.
+pub fn remember_recent(recent: &mut Vec<String>, path: &str) {
+    recent.retain(|p| p != path);
+    recent.insert(0, path.to_string());
+    recent.truncate(MAX_RECENT_FILES);
 }
 
 fn default_width() -> i32 {
@@ -21,6 +110,21 @@ fn default_width() -> i32 {
 fn default_height() -> i32 {
     800
 }
+fn default_true() -> bool {
+    true
+}
+fn default_image_cache_size() -> usize {
+    8
+}
+fn default_loading_indicator_delay() -> u64 {
+    120
+}
+fn default_slideshow_interval() -> u32 {
+    5
+}
+fn default_jpeg_quality() -> u8 {
+    90
+}
 
 impl Default for Config {
     fn default() -> Self {
@@ -28,8 +132,30 @@ impl Default for Config {
             window_width: 1200,
             window_height: 800,
             window_maximized: false,
+            window_fullscreen: false,
             info_panel_visible: false,
             last_directory: None,
+            advance_on_scroll_end: false,
+            auto_orient: true,
+            image_cache_size: 8,
+            loading_indicator_delay_ms: 120,
+            alpha_background: AlphaBackground::default(),
+            slideshow_interval_secs: 5,
+            slideshow_shuffle: false,
+            image_transition: ImageTransition::default(),
+            recursive_scan: false,
+            sort_order: SortOrder::default(),
+            jpeg_quality: 90,
+            recent_files: Vec::new(),
+            remember_view: true,
+            scroll_navigates: false,
+            wrap_navigation: true,
+            skip_unreadable: true,
+            antialiasing: true,
+            dither: false,
+            free_pan: false,
+            escape_closes: false,
+            thumb_size: ThumbSize::default(),
         }
     }
 }
@@ -60,3 +186,21 @@ impl Config {
             .join("config.toml")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_files_are_deduplicated_and_capped() {
+        let mut recent = vec!["/a.jpg".to_string(), "/b.jpg".to_string()];
+        remember_recent(&mut recent, "/b.jpg");
+        assert_eq!(recent, ["/b.jpg", "/a.jpg"]);
+
+        for i in 0..MAX_RECENT_FILES {
+            remember_recent(&mut recent, &format!("/{i}.jpg"));
+        }
+        assert_eq!(recent.len(), MAX_RECENT_FILES);
+        assert_eq!(recent[0], format!("/{}.jpg", MAX_RECENT_FILES - 1));
+    }
+}
@@ -0,0 +1,59 @@
+//! `image::open` for every format the viewer lists, including the ones
+//! only an optional decoder feature can read.
+
+use image::{DynamicImage, RgbaImage};
+use std::path::Path;
+
+use crate::{heif, jxl};
+
+/// Decode `path`, routing formats the `image` crate lacks to their own
+/// decoders. RAW files are not handled here; see `crate::raw`.
+pub fn open(path: &Path) -> Option<DynamicImage> {
+    try_open(path).ok()
+}
+
+/// Like `open`, with a message saying why decoding failed, e.g. an
+/// unsupported format versus a truncated file.
+pub fn try_open(path: &Path) -> Result<DynamicImage, String> {
+    if heif::is_heif(path) {
+        heif::decode_heif(path).ok_or_else(|| "Could not decode this HEIF image".into())
+    } else if jxl::is_jxl(path) {
+        jxl::decode_jxl(path).ok_or_else(|| "Could not decode this JPEG XL image".into())
+    } else {
+        image::open(path).map_err(|e| e.to_string())
+    }
+}
+
+/// Whether `path` is in a format enabled by an optional decoder feature.
+pub fn is_extra_format(path: &Path) -> bool {
+    heif::is_heif(path) || jxl::is_jxl(path)
+}
+
+/// Whether `path` is an OpenEXR or Radiance HDR file, which hold
+/// scene-linear float pixels rather than display-ready ones.
+pub fn is_hdr(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("exr") || e.eq_ignore_ascii_case("hdr"))
+}
+
+/// Whether `img` holds float pixels, which are taken to be scene-linear.
+pub fn is_float(img: &DynamicImage) -> bool {
+    matches!(
+        img,
+        DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_)
+    )
+}
+
+/// `img` as 8-bit pixels for display. Float images are tone mapped and
+/// encoded to sRGB like the viewer shows them, instead of clipping
+/// everything past 1.0 and reading linear values as sRGB.
+pub fn to_display_rgba8(img: DynamicImage) -> RgbaImage {
+    if !is_float(&img) {
+        return img.to_rgba8();
+    }
+    let linear = img.to_rgba32f();
+    let (w, h) = linear.dimensions();
+    let rgba = crate::color::linear_f32_to_srgb_8(linear.as_raw());
+    RgbaImage::from_raw(w, h, rgba).unwrap_or_else(|| RgbaImage::new(w, h))
+}
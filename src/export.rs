@@ -0,0 +1,430 @@
+use glam::Vec2;
+use image::DynamicImage;
+use std::path::Path;
+
+use crate::error::{IrisError, IrisResult};
+use crate::viewport::camera::ImageTransform;
+
+/// A region of the source image, in source pixels. For straightened
+/// exports it is a region of the straightened image instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl CropRect {
+    /// The pixels spanned by two opposite corners in source pixel space,
+    /// clipped to a `width`×`height` image. `None` if nothing is left.
+    pub fn from_corners(a: Vec2, b: Vec2, width: u32, height: u32) -> Option<Self> {
+        let bounds = Vec2::new(width as f32, height as f32);
+        let min = a.min(b).clamp(Vec2::ZERO, bounds).round();
+        let max = a.max(b).clamp(Vec2::ZERO, bounds).round();
+        let size = max - min;
+        (size.x >= 1.0 && size.y >= 1.0).then(|| Self {
+            x: min.x as u32,
+            y: min.y as u32,
+            width: size.x as u32,
+            height: size.y as u32,
+        })
+    }
+}
+
+/// Rotate clockwise, then mirror, matching what the viewport displays.
+pub fn apply_transform(img: DynamicImage, transform: ImageTransform) -> DynamicImage {
+    let img = match transform.rotation_degrees.rem_euclid(360) {
+        90 => img.rotate90(),
+        180 => img.rotate180(),
+        270 => img.rotate270(),
+        _ => img,
+    };
+    let img = if transform.flip_h { img.fliph() } else { img };
+    if transform.flip_v { img.flipv() } else { img }
+}
+
+/// Turn `img` clockwise by `degrees` onto a transparent canvas that holds
+/// all of it, sampling bilinearly, as the viewport's straighten tool shows.
+pub fn straighten(img: &DynamicImage, degrees: f32) -> DynamicImage {
+    let src = img.to_rgba8();
+    let (w, h) = (src.width() as f32, src.height() as f32);
+    let (s, c) = degrees.to_radians().sin_cos();
+    let out_w = (w * c.abs() + h * s.abs()).round().max(1.0);
+    let out_h = (w * s.abs() + h * c.abs()).round().max(1.0);
+    let out = image::RgbaImage::from_fn(out_w as u32, out_h as u32, |x, y| {
+        // Back from the canvas into the source, about both centers.
+        let dx = x as f32 + 0.5 - out_w / 2.0;
+        let dy = y as f32 + 0.5 - out_h / 2.0;
+        let sx = c * dx + s * dy + w / 2.0 - 0.5;
+        let sy = -s * dx + c * dy + h / 2.0 - 0.5;
+        sample_bilinear(&src, sx, sy)
+    });
+    DynamicImage::ImageRgba8(out)
+}
+
+/// Pixels outside `img` are transparent, so edges fade out over one pixel
+/// instead of being cut off with jaggies.
+fn sample_bilinear(img: &image::RgbaImage, x: f32, y: f32) -> image::Rgba<u8> {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let texel = |x: f32, y: f32| {
+        if x < 0.0 || y < 0.0 || x >= img.width() as f32 || y >= img.height() as f32 {
+            return [0.0; 4];
+        }
+        img.get_pixel(x as u32, y as u32).0.map(f32::from)
+    };
+    let (a, b) = (texel(x0, y0), texel(x0 + 1.0, y0));
+    let (c, d) = (texel(x0, y0 + 1.0), texel(x0 + 1.0, y0 + 1.0));
+    let weights = [
+        (1.0 - fx) * (1.0 - fy),
+        fx * (1.0 - fy),
+        (1.0 - fx) * fy,
+        fx * fy,
+    ];
+    // Weighted by alpha, so transparent neighbors don't darken the edge.
+    let mut rgb = [0.0f32; 3];
+    let mut alpha = 0.0;
+    for (t, wt) in [a, b, c, d].into_iter().zip(weights) {
+        let wa = wt * t[3];
+        for (acc, v) in rgb.iter_mut().zip(t) {
+            *acc += v * wa;
+        }
+        alpha += wa;
+    }
+    if alpha <= 0.0 {
+        return image::Rgba([0; 4]);
+    }
+    let [r, g, b] = rgb.map(|v| (v / alpha).round().clamp(0.0, 255.0) as u8);
+    image::Rgba([r, g, b, alpha.round().clamp(0.0, 255.0) as u8])
+}
+
+/// Decode page `page` of `source` to sRGB, optionally crop it, orient it as displayed and
+/// write it to `dest` in the format implied by its extension. `quality`
+/// (1–100) applies to JPEG; the WebP encoder is lossless only.
+/// A non-zero `straighten_degrees` turns the oriented image as `straighten`
+/// does, and `crop` then selects from the straightened result.
+pub fn export(
+    source: &Path,
+    page: usize,
+    crop: Option<CropRect>,
+    transform: ImageTransform,
+    straighten_degrees: f32,
+    dest: &Path,
+    quality: u8,
+) -> IrisResult<()> {
+    let mut img = decode_srgb(source, page)?;
+    if straighten_degrees != 0.0 {
+        img = straighten(&apply_transform(img, transform), straighten_degrees);
+        if let Some(c) = crop {
+            img = img.crop_imm(c.x, c.y, c.width, c.height);
+        }
+        return save(&img, dest, quality);
+    }
+    if let Some(c) = crop {
+        img = img.crop_imm(c.x, c.y, c.width, c.height);
+    }
+    let img = apply_transform(img, transform);
+    save(&img, dest, quality)
+}
+
+/// Decode page `page` of `source` to sRGB at full resolution, oriented as
+/// displayed.
+pub fn decode_oriented(
+    source: &Path,
+    page: usize,
+    transform: ImageTransform,
+) -> IrisResult<image::RgbaImage> {
+    Ok(apply_transform(decode_srgb(source, page)?, transform).to_rgba8())
+}
+
+fn decode_srgb(path: &Path, page: usize) -> IrisResult<DynamicImage> {
+    if page > 0 {
+        let img = crate::pages::decode_page(path, page).ok_or_else(|| {
+            IrisError::Other(format!(
+                "Could not decode page {} of {}",
+                page + 1,
+                path.display()
+            ))
+        })?;
+        return to_srgb(img.to_rgba8(), path);
+    }
+    if crate::raw::is_raw(path) {
+        // Crop coordinates refer to what the viewer shows, so decode the
+        // same way it does.
+        if let Some(preview) = crate::raw::decode_display_preview(path) {
+            return Ok(DynamicImage::ImageRgba8(preview));
+        }
+        let raw = crate::raw::decode_raw(path)
+            .ok_or_else(|| IrisError::Other(format!("Could not decode {}", path.display())))?;
+        let rgba = crate::raw::linear_16_to_srgb_8(&raw.data, raw.width, raw.height);
+        let img = image::RgbaImage::from_raw(raw.width, raw.height, rgba)
+            .ok_or_else(|| IrisError::Other("RAW decode returned a short buffer".into()))?;
+        return Ok(DynamicImage::ImageRgba8(img));
+    }
+    let img = crate::decode::open(path)
+        .ok_or_else(|| IrisError::Other(format!("Could not decode {}", path.display())))?;
+    to_srgb(crate::decode::to_display_rgba8(img), path)
+}
+
+/// `img` converted to sRGB through `path`'s embedded color profile.
+fn to_srgb(img: image::RgbaImage, path: &Path) -> IrisResult<DynamicImage> {
+    let (w, h) = img.dimensions();
+    let icc = crate::color::extract_icc_profile(path);
+    let srgb = crate::color::rgba8_to_srgb_with_icc(img.as_raw(), w, h, icc.as_deref());
+    let img = image::RgbaImage::from_raw(w, h, srgb)
+        .ok_or_else(|| IrisError::Other("Color conversion returned a short buffer".into()))?;
+    Ok(DynamicImage::ImageRgba8(img))
+}
+
+/// How `bake_transform` rewrote a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Baked {
+    /// JPEG blocks were rearranged without decoding.
+    Lossless,
+    Reencoded,
+}
+
+/// Rewrite `path` with `transform` applied to its pixels and any EXIF
+/// orientation reset to normal, so it opens as displayed everywhere.
+/// `transform` is what the viewer shows, EXIF orientation included. JPEGs
+/// are transformed losslessly when built with `turbojpeg` and their size
+/// is a whole number of blocks; otherwise they are re-encoded at `quality`
+/// with their EXIF kept. Other formats are re-encoded without metadata.
+pub fn bake_transform(path: &Path, transform: ImageTransform, quality: u8) -> IrisResult<Baked> {
+    let unsupported = || IrisError::Other(format!("Can't rotate {} in place", path.display()));
+    if crate::raw::is_raw(path) || crate::decode::is_extra_format(path) {
+        return Err(unsupported());
+    }
+    let format = image::ImageFormat::from_path(path).map_err(|_| unsupported())?;
+    // Decoding keeps only the first frame or page.
+    if format == image::ImageFormat::Gif || crate::pages::page_count(path) > 1 {
+        return Err(unsupported());
+    }
+    let read_err = |e: String| IrisError::Other(format!("Could not read {}: {e}", path.display()));
+    let original = std::fs::read(path).map_err(|e| read_err(e.to_string()))?;
+
+    #[cfg(feature = "turbojpeg")]
+    if let Some(out) = (format == image::ImageFormat::Jpeg)
+        .then(|| transform_jpeg_lossless(&original, transform))
+        .flatten()
+    {
+        write_replacing(path, &out)?;
+        return Ok(Baked::Lossless);
+    }
+
+    let img = image::load_from_memory_with_format(&original, format)
+        .map_err(|e| read_err(e.to_string()))?;
+    let img = apply_transform(img, transform);
+    let save_err =
+        |e: image::ImageError| IrisError::Other(format!("Could not save {}: {e}", path.display()));
+    let mut encoded = Vec::new();
+    if format == image::ImageFormat::Jpeg {
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut encoded, quality.clamp(1, 100))
+            .encode_image(&DynamicImage::ImageRgb8(img.to_rgb8()))
+            .map_err(save_err)?;
+        encoded = crate::orientation::with_exif_from(&original, encoded);
+        crate::orientation::reset_jpeg_orientation(&mut encoded);
+    } else {
+        img.write_to(&mut std::io::Cursor::new(&mut encoded), format)
+            .map_err(save_err)?;
+    }
+    write_replacing(path, &encoded)?;
+    Ok(Baked::Reencoded)
+}
+
+/// `transform` as a clockwise rotation followed by an optional horizontal
+/// mirror; a vertical mirror is a half turn plus a horizontal one. These
+/// are the eight lossless JPEG operations.
+#[cfg(any(feature = "turbojpeg", test))]
+fn rotation_then_mirror(transform: ImageTransform) -> (i32, bool) {
+    let half_turn = if transform.flip_v { 180 } else { 0 };
+    (
+        (transform.rotation_degrees + half_turn).rem_euclid(360),
+        transform.flip_h != transform.flip_v,
+    )
+}
+
+/// Rearrange `jpeg`'s DCT blocks, or `None` if its size isn't a whole
+/// number of blocks and the edges would be cut off.
+#[cfg(feature = "turbojpeg")]
+fn transform_jpeg_lossless(jpeg: &[u8], transform: ImageTransform) -> Option<Vec<u8>> {
+    use turbojpeg::TransformOp;
+    let op = match rotation_then_mirror(transform) {
+        (90, false) => TransformOp::Rot90,
+        (180, false) => TransformOp::Rot180,
+        (270, false) => TransformOp::Rot270,
+        (0, true) => TransformOp::Hflip,
+        (90, true) => TransformOp::Transpose,
+        (180, true) => TransformOp::Vflip,
+        (270, true) => TransformOp::Transverse,
+        _ => TransformOp::None,
+    };
+    let mut t = turbojpeg::Transform::op(op);
+    t.perfect = true;
+    let mut out = turbojpeg::transform(&t, jpeg).ok()?.to_vec();
+    // Markers are copied as-is, including the old orientation.
+    crate::orientation::reset_jpeg_orientation(&mut out);
+    Some(out)
+}
+
+/// Replace `path` with `bytes` through a temporary sibling, so a failed
+/// write can't leave it truncated.
+fn write_replacing(path: &Path, bytes: &[u8]) -> IrisResult<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".iris-tmp");
+    let tmp = std::path::PathBuf::from(tmp);
+    std::fs::write(&tmp, bytes)
+        .and_then(|()| std::fs::rename(&tmp, path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&tmp);
+            IrisError::Other(format!("Could not save {}: {e}", path.display()))
+        })
+}
+
+/// Whether `path` names a format with a quality setting.
+pub fn has_quality(path: &Path) -> bool {
+    matches!(
+        image::ImageFormat::from_path(path),
+        Ok(image::ImageFormat::Jpeg)
+    )
+}
+
+fn save(img: &DynamicImage, dest: &Path, quality: u8) -> IrisResult<()> {
+    let format = image::ImageFormat::from_path(dest)
+        .map_err(|_| IrisError::Other(format!("Unsupported file type: {}", dest.display())))?;
+    let save_err =
+        |e: image::ImageError| IrisError::Other(format!("Could not save {}: {e}", dest.display()));
+    if format != image::ImageFormat::Jpeg {
+        return img.save_with_format(dest, format).map_err(save_err);
+    }
+    let file = std::fs::File::create(dest).map_err(|e| save_err(e.into()))?;
+    let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(
+        std::io::BufWriter::new(file),
+        quality.clamp(1, 100),
+    );
+    // JPEG has no alpha channel.
+    encoder
+        .encode_image(&DynamicImage::ImageRgb8(img.to_rgb8()))
+        .map_err(save_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crop_rect_orders_and_clips_corners() {
+        let rect = CropRect::from_corners(Vec2::new(120.4, 80.0), Vec2::new(-10.0, 10.0), 100, 50);
+        assert_eq!(
+            rect,
+            Some(CropRect {
+                x: 0,
+                y: 10,
+                width: 100,
+                height: 40,
+            })
+        );
+        assert_eq!(
+            CropRect::from_corners(Vec2::new(5.0, 5.0), Vec2::new(5.2, 30.0), 100, 50),
+            None
+        );
+    }
+
+    #[test]
+    fn any_transform_is_a_rotation_then_horizontal_mirror() {
+        let mut img = image::RgbaImage::new(3, 2);
+        for (i, px) in img.pixels_mut().enumerate() {
+            *px = image::Rgba([i as u8, 0, 0, 255]);
+        }
+        let img = DynamicImage::ImageRgba8(img);
+        for rotation_degrees in [0, 90, 180, 270] {
+            for (flip_h, flip_v) in [(false, false), (true, false), (false, true), (true, true)] {
+                let t = ImageTransform {
+                    rotation_degrees,
+                    flip_h,
+                    flip_v,
+                };
+                let (rotation, mirror) = rotation_then_mirror(t);
+                let simple = ImageTransform {
+                    rotation_degrees: rotation,
+                    flip_h: mirror,
+                    flip_v: false,
+                };
+                assert_eq!(
+                    apply_transform(img.clone(), t),
+                    apply_transform(img.clone(), simple),
+                    "{t:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn straighten_grows_the_canvas_to_hold_the_turned_image() {
+        let img = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+            40,
+            20,
+            image::Rgba([200, 100, 50, 255]),
+        ));
+        let out = straighten(&img, 30.0).to_rgba8();
+        // 40·cos30 + 20·sin30 by 40·sin30 + 20·cos30.
+        assert_eq!(out.dimensions(), (45, 37));
+        // Corners are outside the turned image; the middle is inside.
+        assert_eq!(out.get_pixel(0, 0).0[3], 0);
+        assert_eq!(out.get_pixel(22, 18).0, [200, 100, 50, 255]);
+
+        let level = straighten(&img, 0.0).to_rgba8();
+        assert_eq!(level, img.to_rgba8());
+    }
+
+    #[test]
+    fn exports_the_selected_page() {
+        use tiff::encoder::{TiffEncoder, colortype};
+
+        let dir = std::env::temp_dir().join(format!("iris-export-page-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("pages.tif");
+        {
+            let file = std::fs::File::create(&source).unwrap();
+            let mut encoder = TiffEncoder::new(std::io::BufWriter::new(file)).unwrap();
+            encoder
+                .write_image::<colortype::RGB8>(2, 1, &[255, 0, 0, 255, 0, 0])
+                .unwrap();
+            encoder
+                .write_image::<colortype::Gray8>(1, 3, &[10, 20, 30])
+                .unwrap();
+        }
+        let dest = dir.join("page.png");
+        let result = export(&source, 1, None, ImageTransform::default(), 0.0, &dest, 90);
+        let exported = image::open(&dest).map(|img| img.to_rgba8());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        result.unwrap();
+        let exported = exported.unwrap();
+        assert_eq!(exported.dimensions(), (1, 3));
+        assert_eq!(exported.get_pixel(0, 2).0, [30, 30, 30, 255]);
+    }
+
+    #[test]
+    fn transform_rotates_before_mirroring() {
+        // 2×1 image: red on the left, blue on the right.
+        let mut img = image::RgbaImage::new(2, 1);
+        img.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+        img.put_pixel(1, 0, image::Rgba([0, 0, 255, 255]));
+
+        let out = apply_transform(
+            DynamicImage::ImageRgba8(img),
+            ImageTransform {
+                rotation_degrees: 90,
+                flip_h: false,
+                flip_v: true,
+            },
+        )
+        .to_rgba8();
+
+        // Rotating clockwise puts red on top; the vertical flip moves it down.
+        assert_eq!(out.dimensions(), (1, 2));
+        assert_eq!(out.get_pixel(0, 1).0, [255, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,87 @@
+//! HEIF/HEIC decoding through the system libheif, behind the `heif` Cargo
+//! feature. Without it, HEIF files are simply not offered as images.
+
+use image::DynamicImage;
+use std::path::Path;
+
+const HEIF_EXTENSIONS: &[&str] = &["heic", "heif", "hif"];
+
+/// Whether `path` names a HEIF container and this build can decode it.
+pub fn is_heif(path: &Path) -> bool {
+    cfg!(feature = "heif")
+        && path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| HEIF_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// Decode the primary image as 8-bit RGBA. The container's own rotation
+/// and mirror boxes are ignored: HEIC files also carry an EXIF orientation,
+/// and that is applied by the viewer like for any other photo.
+#[cfg(feature = "heif")]
+pub fn decode_heif(path: &Path) -> Option<DynamicImage> {
+    use libheif_rs::{ColorSpace, DecodingOptions, HeifContext, LibHeif, RgbChroma};
+
+    let ctx = HeifContext::read_from_file(path.to_str()?).ok()?;
+    let handle = ctx.primary_image_handle().ok()?;
+    let mut options = DecodingOptions::new()?;
+    options.set_ignore_transformations(true);
+    let decoded = LibHeif::new()
+        .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgba), Some(options))
+        .ok()?;
+
+    let plane = decoded.planes().interleaved?;
+    let (width, height) = (plane.width, plane.height);
+    // Rows may be padded; copy out just the pixels.
+    let row = width as usize * 4;
+    let mut rgba = Vec::with_capacity(row * height as usize);
+    for y in 0..height as usize {
+        rgba.extend_from_slice(plane.data.get(y * plane.stride..)?.get(..row)?);
+    }
+    let img = image::RgbaImage::from_raw(width, height, rgba)?;
+    Some(DynamicImage::ImageRgba8(img))
+}
+
+#[cfg(not(feature = "heif"))]
+pub fn decode_heif(_path: &Path) -> Option<DynamicImage> {
+    None
+}
+
+/// Bits per luma sample as stored in the file, before conversion to 8 bits.
+#[cfg(feature = "heif")]
+pub fn bit_depth(path: &Path) -> Option<u32> {
+    let ctx = libheif_rs::HeifContext::read_from_file(path.to_str()?).ok()?;
+    let handle = ctx.primary_image_handle().ok()?;
+    Some(u32::from(handle.luma_bits_per_pixel()))
+}
+
+#[cfg(not(feature = "heif"))]
+pub fn bit_depth(_path: &Path) -> Option<u32> {
+    None
+}
+
+/// Embedded ICC profile of the primary image, if it has one.
+#[cfg(feature = "heif")]
+pub fn icc_profile(path: &Path) -> Option<Vec<u8>> {
+    let ctx = libheif_rs::HeifContext::read_from_file(path.to_str()?).ok()?;
+    let handle = ctx.primary_image_handle().ok()?;
+    handle.color_profile_raw().map(|p| p.data)
+}
+
+#[cfg(not(feature = "heif"))]
+pub fn icc_profile(_path: &Path) -> Option<Vec<u8>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heif_extensions_follow_the_feature() {
+        for p in ["IMG_0001.HEIC", "a.heic", "a.heif", "a.hif"] {
+            assert_eq!(is_heif(Path::new(p)), cfg!(feature = "heif"), "{p}");
+        }
+        assert!(!is_heif(Path::new("a.jpg")));
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::Path;
+
+pub const BINS: usize = 256;
+
+/// Per-channel 8-bit histograms plus Rec. 709 luminance.
+#[derive(Clone)]
+pub struct Histogram {
+    pub red: [u32; BINS],
+    pub green: [u32; BINS],
+    pub blue: [u32; BINS],
+    pub luma: [u32; BINS],
+}
+
+/// Longest edge the image is reduced to before counting. The shape of the
+/// histogram survives downscaling; the cost of a 50 MP pass doesn't.
+const SAMPLE_EDGE: u32 = 1024;
+
+impl Histogram {
+    /// Count straight-alpha RGBA8 pixels. Fully transparent pixels carry no
+    /// visible color and are skipped.
+    pub fn from_rgba8(pixels: &[u8]) -> Self {
+        let mut h = Self {
+            red: [0; BINS],
+            green: [0; BINS],
+            blue: [0; BINS],
+            luma: [0; BINS],
+        };
+        for px in pixels.chunks_exact(4) {
+            if px[3] == 0 {
+                continue;
+            }
+            let (r, g, b) = (px[0] as usize, px[1] as usize, px[2] as usize);
+            h.red[r] += 1;
+            h.green[g] += 1;
+            h.blue[b] += 1;
+            // 0.2126 / 0.7152 / 0.0722 in 8-bit fixed point.
+            h.luma[(54 * r + 183 * g + 19 * b) >> 8] += 1;
+        }
+        h
+    }
+
+    /// Decode a reduced copy of the image and count it. RAW files use their
+    /// embedded preview, which is what the camera itself would show.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let rgba = if crate::raw::is_raw(path) {
+            let preview = crate::orientation::embedded_preview(path)?;
+            image::DynamicImage::ImageRgba8(preview)
+                .thumbnail(SAMPLE_EDGE, SAMPLE_EDGE)
+                .to_rgba8()
+        } else {
+            crate::decode::to_display_rgba8(
+                crate::decode::open(path)?.thumbnail(SAMPLE_EDGE, SAMPLE_EDGE),
+            )
+        };
+        Some(Self::from_rgba8(rgba.as_raw()))
+    }
+
+    /// Tallest bin across all channels, for normalizing the plot.
+    pub fn peak(&self) -> u32 {
+        [&self.red, &self.green, &self.blue, &self.luma]
+            .iter()
+            .flat_map(|c| c.iter())
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_channels_and_luma() {
+        let pixels = [
+            255, 0, 0, 255, // red
+            255, 255, 255, 255, // white
+            0, 0, 0, 255, // black
+        ];
+        let h = Histogram::from_rgba8(&pixels);
+        assert_eq!(h.red[255], 2);
+        assert_eq!(h.red[0], 1);
+        assert_eq!(h.green[0], 2);
+        assert_eq!(h.luma[255], 1);
+        assert_eq!(h.luma[0], 1);
+        assert_eq!(h.luma[53], 1);
+        assert_eq!(h.peak(), 2);
+    }
+
+    #[test]
+    fn transparent_pixels_are_ignored() {
+        let pixels = [10, 20, 30, 0, 10, 20, 30, 128];
+        let h = Histogram::from_rgba8(&pixels);
+        assert_eq!(h.red.iter().sum::<u32>(), 1);
+    }
+}
@@ -0,0 +1,71 @@
+//! JPEG XL decoding through the pure-Rust `jxl-oxide`, behind the `jxl`
+//! Cargo feature. Without it, `.jxl` files are not offered as images.
+
+use image::DynamicImage;
+use std::path::Path;
+
+/// Whether `path` names a JPEG XL file and this build can decode it.
+pub fn is_jxl(path: &Path) -> bool {
+    cfg!(feature = "jxl")
+        && path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("jxl"))
+}
+
+/// Decode lossy or lossless JPEG XL. Animations show their first frame.
+#[cfg(feature = "jxl")]
+pub fn decode_jxl(path: &Path) -> Option<DynamicImage> {
+    DynamicImage::from_decoder(decoder(path)?).ok()
+}
+
+#[cfg(not(feature = "jxl"))]
+pub fn decode_jxl(_path: &Path) -> Option<DynamicImage> {
+    None
+}
+
+/// ICC profile of the decoded pixels, synthesized by jxl-oxide for files
+/// that signal an enumerated colour space instead of embedding one.
+#[cfg(feature = "jxl")]
+pub fn icc_profile(path: &Path) -> Option<Vec<u8>> {
+    use image::ImageDecoder;
+    decoder(path)?.icc_profile().ok()?
+}
+
+#[cfg(not(feature = "jxl"))]
+pub fn icc_profile(_path: &Path) -> Option<Vec<u8>> {
+    None
+}
+
+/// Bits per sample as stored in the file, before conversion to 8 bits.
+#[cfg(feature = "jxl")]
+pub fn bit_depth(path: &Path) -> Option<u32> {
+    let image = jxl_oxide::JxlImage::builder().open(path).ok()?;
+    Some(image.image_header().metadata.bit_depth.bits_per_sample())
+}
+
+#[cfg(not(feature = "jxl"))]
+pub fn bit_depth(_path: &Path) -> Option<u32> {
+    None
+}
+
+#[cfg(feature = "jxl")]
+fn decoder(
+    path: &Path,
+) -> Option<jxl_oxide::integration::JxlDecoder<std::io::BufReader<std::fs::File>>> {
+    let file = std::fs::File::open(path).ok()?;
+    jxl_oxide::integration::JxlDecoder::new(std::io::BufReader::new(file)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jxl_extension_follows_the_feature() {
+        for p in ["a.jxl", "a.JXL"] {
+            assert_eq!(is_jxl(Path::new(p)), cfg!(feature = "jxl"), "{p}");
+        }
+        assert!(!is_jxl(Path::new("a.jpg")));
+    }
+}
@@ -2,7 +2,18 @@
 
 pub mod color;
 pub mod config;
+pub mod decode;
 pub mod error;
+pub mod export;
+pub mod heif;
+pub mod histogram;
+pub mod jxl;
+pub mod metadata;
+pub mod orientation;
+pub mod pages;
+pub mod quality;
+pub mod ratings;
 pub mod raw;
+pub mod sort;
 pub mod thumbcache;
 pub mod viewport;
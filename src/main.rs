@@ -7,41 +7,72 @@ use image::GenericImageView;
 use libadwaita as adw;
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 mod color;
 mod config;
+mod decode;
 mod error;
+mod export;
+mod heif;
+mod histogram;
+mod jxl;
+mod metadata;
+mod orientation;
+mod pages;
+mod print;
+mod quality;
+mod ratings;
 mod raw;
+mod sort;
 mod thumbcache;
 mod viewport;
 
 use config::Config;
+use export::CropRect;
+use histogram::Histogram;
+use metadata::CameraInfo;
+use ratings::Ratings;
+use sort::SortOrder;
+use thumbcache::ThumbSize;
+use viewport::camera::{ImageTransform, MAX_STRAIGHTEN_DEGREES};
+use viewport::vk::renderer::{
+    Adjustments, AlphaBackground, CompareLayout, EXPOSURE_RANGE_STOPS, GAMMA_RANGE, ImageTransition,
+};
 
 const APP_ID: &str = "dev.iris.viewer";
 
-fn read_exif_rotation(path: &Path) -> i32 {
-    let file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(_) => return 0,
-    };
-    let mut buf = std::io::BufReader::new(file);
-    let exif = match exif::Reader::new().read_from_container(&mut buf) {
-        Ok(r) => r,
-        Err(_) => return 0,
-    };
-    match exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY) {
-        Some(field) => match field.value.get_uint(0) {
-            Some(1) => 0,
-            Some(3) => 180,
-            Some(6) => 90,
-            Some(8) => 270,
-            _ => 0,
-        },
-        None => 0,
-    }
-}
+/// Folders with fewer images than this load fast enough that a progress
+/// indicator would only flash.
+const THUMB_PROGRESS_MIN_FILES: usize = 24;
+
+/// Thumbnail decodes running at once. Each holds a full-size image in
+/// memory, so a large folder must not start them all together.
+const THUMB_DECODE_CONCURRENCY: usize = 4;
+
+/// Thumbnails decoded ahead of the visible strip before it has been laid
+/// out, on each side of the current image.
+const THUMB_LOOKAHEAD: usize = 16;
+
+/// Edge of a gallery tile's picture. Matches the "normal" cached
+/// thumbnail, so tiles reuse what the strip already decoded.
+const GALLERY_TILE_PX: i32 = thumbcache::THUMB_SIZE as i32;
+
+/// Subdirectory levels followed when scanning recursively.
+const MAX_SCAN_DEPTH: usize = 8;
+
+/// Choices offered in the slideshow menu, in seconds.
+const SLIDESHOW_INTERVALS: [u32; 4] = [2, 5, 10, 30];
+
+/// How close to the top edge the pointer reveals the header in
+/// fullscreen, in pixels; it hides again this far below it.
+const HEADER_REVEAL_PX: f64 = 4.0;
+
+/// How long the directory must stay quiet before it is rescanned, so a burst
+/// of events (a download being written, a batch copy) costs one rescan.
+const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
 
 #[derive(Clone, Copy)]
 struct ViewState {
@@ -54,12 +85,44 @@ struct AppState {
     files: Vec<PathBuf>,
     current_index: usize,
     rotations: HashMap<PathBuf, i32>,
+    /// Per-path (horizontal, vertical) mirroring, applied after rotation.
+    flips: HashMap<PathBuf, (bool, bool)>,
+    /// Per-path fine rotation in degrees, clockwise, on top of `rotations`.
+    straighten: HashMap<PathBuf, f32>,
     view_states: HashMap<PathBuf, ViewState>,
+    /// Whether `view_states` is kept and restored on return.
+    remember_view: bool,
+    /// Whether the scroll wheel changes image instead of zooming.
+    scroll_navigates: bool,
+    /// Whether navigating past the last image returns to the first, and
+    /// back from the first to the last. Off, navigation stops at the ends.
+    wrap: bool,
+    /// Whether browsing past a file that fails to decode moves on to the
+    /// next one instead of stopping on the error.
+    skip_unreadable: bool,
+    /// Files that failed to decode since the last one that loaded, so a
+    /// folder of nothing but broken files isn't skipped through forever.
+    failed_in_a_row: usize,
+    /// Per-path brightness/contrast; dropped when the working set changes.
+    adjustments: HashMap<PathBuf, Adjustments>,
     info_visible: bool,
+    /// Include images from subdirectories of the opened directory.
+    recursive_scan: bool,
+    sort_order: SortOrder,
+    alpha_background: AlphaBackground,
     watched_directory: Option<PathBuf>,
     /// +1 when navigating forward, -1 backward, 0 neutral.
     /// Used to bias prefetch in the direction the user is scrubbing.
     last_nav_direction: i32,
+    /// Images a shuffled slideshow has yet to show this round, popped from
+    /// the end.
+    shuffle_queue: Vec<PathBuf>,
+    /// xorshift state for picking random images; never zero.
+    rng: u64,
+    ratings: Ratings,
+    /// Directory listings only include images rated at least this many
+    /// stars; 0 shows everything.
+    min_rating: u8,
 }
 
 impl AppState {
@@ -68,10 +131,28 @@ impl AppState {
             files: vec![],
             current_index: 0,
             rotations: HashMap::new(),
+            flips: HashMap::new(),
+            straighten: HashMap::new(),
             view_states: HashMap::new(),
+            remember_view: true,
+            scroll_navigates: false,
+            wrap: true,
+            skip_unreadable: true,
+            failed_in_a_row: 0,
+            adjustments: HashMap::new(),
             info_visible: false,
+            recursive_scan: false,
+            sort_order: SortOrder::default(),
+            alpha_background: AlphaBackground::default(),
             watched_directory: None,
             last_nav_direction: 0,
+            shuffle_queue: Vec::new(),
+            rng: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+                | 1,
+            ratings: Ratings::load(),
+            min_rating: 0,
         }
     }
 
@@ -85,6 +166,42 @@ impl AppState {
             .unwrap_or(0)
     }
 
+    fn current_flip(&self) -> (bool, bool) {
+        self.current_path()
+            .and_then(|p| self.flips.get(&p).copied())
+            .unwrap_or((false, false))
+    }
+
+    fn flip_horizontal(&mut self) {
+        if let Some(path) = self.current_path() {
+            let f = self.flips.entry(path).or_insert((false, false));
+            f.0 = !f.0;
+        }
+    }
+
+    fn flip_vertical(&mut self) {
+        if let Some(path) = self.current_path() {
+            let f = self.flips.entry(path).or_insert((false, false));
+            f.1 = !f.1;
+        }
+    }
+
+    /// Forget the current image's rotation, mirroring, straightening,
+    /// remembered zoom and adjustments, putting back `orientation` (its
+    /// EXIF orientation, or none) in place of the rotation and mirroring.
+    fn reset_current(&mut self, orientation: ImageTransform) {
+        let Some(path) = self.current_path() else {
+            return;
+        };
+        self.view_states.remove(&path);
+        self.adjustments.remove(&path);
+        self.straighten.remove(&path);
+        self.rotations
+            .insert(path.clone(), orientation.rotation_degrees);
+        self.flips
+            .insert(path, (orientation.flip_h, orientation.flip_v));
+    }
+
     fn rotate_cw(&mut self) {
         if let Some(path) = self.current_path() {
             let r = self.rotations.entry(path).or_insert(0);
@@ -99,31 +216,90 @@ impl AppState {
         }
     }
 
-    fn load_directory(&mut self, path: &PathBuf) {
+    /// Open `path` with the images beside it as the working set. A folder
+    /// that can't be listed is an error and leaves the working set empty.
+    fn load_directory(&mut self, path: &PathBuf) -> std::io::Result<()> {
         if let Some(parent) = path.parent() {
-            let mut files = Self::scan_images(parent);
-            files.sort();
+            if let Err(err) = std::fs::read_dir(parent) {
+                self.files.clear();
+                self.current_index = 0;
+                self.watched_directory = None;
+                return Err(err);
+            }
+            let mut files = self.scan_images(parent);
+            // The opened image stays even when the rating filter hides it.
+            if !files.contains(path) {
+                files.push(path.clone());
+            }
+            sort::sort_paths(&mut files, self.sort_order);
             self.current_index = files.iter().position(|f| f == path).unwrap_or(0);
             self.files = files;
             self.watched_directory = Some(parent.to_path_buf());
+            self.adjustments.clear();
             self.last_nav_direction = 0;
         }
+        Ok(())
+    }
+
+    /// Use an explicit set of files as the working set instead of a
+    /// directory listing. Nothing is watched for changes.
+    fn load_files(&mut self, paths: &[PathBuf]) {
+        let mut files: Vec<PathBuf> = paths
+            .iter()
+            .filter(|p| p.is_file() && Self::is_image(p))
+            .cloned()
+            .collect();
+        sort::sort_paths(&mut files, self.sort_order);
+        files.dedup();
+        self.current_index = 0;
+        self.files = files;
+        self.watched_directory = None;
+        self.adjustments.clear();
+        self.last_nav_direction = 0;
+    }
+
+    /// Drop the current file from the working set after it was deleted,
+    /// returning the image that takes its place, if any.
+    fn remove_current(&mut self) -> Option<PathBuf> {
+        if self.current_index >= self.files.len() {
+            return None;
+        }
+        let removed = self.files.remove(self.current_index);
+        self.rotations.remove(&removed);
+        self.flips.remove(&removed);
+        self.straighten.remove(&removed);
+        self.view_states.remove(&removed);
+        self.adjustments.remove(&removed);
+        self.current_index = self.current_index.min(self.files.len().saturating_sub(1));
+        self.last_nav_direction = 0;
+        self.current_path()
+    }
+
+    /// Re-sort the working set, keeping the current image selected.
+    fn sort_files(&mut self, order: SortOrder) {
+        self.sort_order = order;
+        let current = self.current_path();
+        sort::sort_paths(&mut self.files, order);
+        if let Some(current) = current {
+            self.current_index = self.files.iter().position(|f| *f == current).unwrap_or(0);
+        }
     }
 
     fn load_from_directory(&mut self, dir: &Path) {
-        let mut files = Self::scan_images(dir);
-        files.sort();
+        let mut files = self.scan_images(dir);
+        sort::sort_paths(&mut files, self.sort_order);
         self.current_index = 0;
         self.files = files;
         self.watched_directory = Some(dir.to_path_buf());
+        self.adjustments.clear();
         self.last_nav_direction = 0;
     }
 
     fn refresh_watched_directory(&mut self) -> Option<PathBuf> {
         let dir = self.watched_directory.clone()?;
         let old_current = self.current_path();
-        let mut files = Self::scan_images(&dir);
-        files.sort();
+        let mut files = self.scan_images(&dir);
+        sort::sort_paths(&mut files, self.sort_order);
 
         if files.is_empty() {
             self.files.clear();
@@ -148,36 +324,193 @@ impl AppState {
         Some(new_current)
     }
 
-    fn scan_images(dir: &Path) -> Vec<PathBuf> {
+    fn scan_images(&self, dir: &Path) -> Vec<PathBuf> {
+        let depth = if self.recursive_scan {
+            MAX_SCAN_DEPTH
+        } else {
+            0
+        };
+        let mut files = Vec::new();
+        Self::collect_images(dir, depth, &mut files);
+        files.retain(|f| self.ratings.get(f) >= self.min_rating);
+        files
+    }
+
+    /// Rescan the directory showing only images rated `min` stars or more.
+    /// Returns the image to show; None leaves the filter unchanged, when no
+    /// directory is open or no image would be left.
+    fn set_min_rating(&mut self, min: u8) -> Option<PathBuf> {
+        let dir = self.watched_directory.clone()?;
+        let previous = std::mem::replace(&mut self.min_rating, min);
+        if self.scan_images(&dir).is_empty() {
+            self.min_rating = previous;
+            return None;
+        }
+        self.refresh_watched_directory()
+    }
+
+    /// Rate the current image and save the ratings index.
+    fn rate_current(&mut self, stars: u8) -> std::io::Result<()> {
+        let Some(path) = self.current_path() else {
+            return Ok(());
+        };
+        self.ratings.set(&path, stars);
+        self.ratings.save()
+    }
+
+    /// Gather images in `dir`, descending up to `depth` levels into
+    /// subdirectories. Hidden and symlinked directories are skipped so a
+    /// library can't loop back on itself.
+    fn collect_images(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
         let Ok(entries) = std::fs::read_dir(dir) else {
-            return vec![];
+            return;
         };
-        entries
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| {
-                let is_standard = matches!(
-                    p.extension().and_then(|e| e.to_str()),
-                    Some("jpg" | "jpeg" | "png" | "gif" | "webp" | "avif" | "tiff" | "bmp")
-                );
-                is_standard || crate::raw::is_raw(p)
-            })
-            .collect()
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+            if is_dir {
+                let hidden = path
+                    .file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with('.'));
+                if depth > 0 && !hidden {
+                    Self::collect_images(&path, depth - 1, out);
+                }
+            } else if Self::is_image(&path) {
+                out.push(path);
+            }
+        }
+    }
+
+    fn is_image(path: &Path) -> bool {
+        let is_standard = matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("jpg" | "jpeg" | "png" | "gif" | "webp" | "avif" | "tiff" | "bmp" | "exr" | "hdr")
+        );
+        is_standard || crate::raw::is_raw(path) || crate::decode::is_extra_format(path)
     }
 
     fn next(&mut self) -> Option<PathBuf> {
-        if self.files.is_empty() {
+        self.step(1)
+    }
+
+    fn prev(&mut self) -> Option<PathBuf> {
+        self.step(-1)
+    }
+
+    /// Move `delta` images through the working set, wrapping around or,
+    /// without `wrap`, stopping at the ends. `None` if there was nowhere to
+    /// go.
+    fn step(&mut self, delta: i64) -> Option<PathBuf> {
+        let len = self.files.len() as i64;
+        if len == 0 || delta == 0 {
+            return None;
+        }
+        let target = self.current_index as i64 + delta;
+        let index = if self.wrap {
+            target.rem_euclid(len)
+        } else {
+            target.clamp(0, len - 1)
+        } as usize;
+        if index == self.current_index && !self.wrap {
             return None;
         }
-        self.current_index = (self.current_index + 1) % self.files.len();
+        self.current_index = index;
         self.current_path()
     }
 
-    fn prev(&mut self) -> Option<PathBuf> {
-        if self.files.is_empty() {
+    /// After `path` failed to decode, the image to try in its place: the
+    /// next one in the direction of travel, when browsing rather than a
+    /// jump led there. `None` to show the error, including once every image
+    /// has failed in a row.
+    fn skip_unreadable(&mut self, path: &Path) -> Option<PathBuf> {
+        let direction = self.last_nav_direction;
+        if !self.skip_unreadable || direction == 0 || self.current_path().as_deref() != Some(path) {
+            return None;
+        }
+        self.failed_in_a_row += 1;
+        let next = if self.failed_in_a_row < self.files.len() {
+            self.step(direction as i64)
+        } else {
+            None
+        };
+        if next.is_none() {
+            self.failed_in_a_row = 0;
+        }
+        next
+    }
+
+    /// A number in `0..n`, `n` > 0. Not uniform to the last bit, which
+    /// doesn't matter for picking images.
+    fn random_below(&mut self, n: usize) -> usize {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng % n as u64) as usize
+    }
+
+    /// Jump to a random image other than the current one.
+    fn random(&mut self) -> Option<PathBuf> {
+        let len = self.files.len();
+        if len < 2 {
             return None;
         }
-        self.current_index = (self.current_index + self.files.len() - 1) % self.files.len();
+        let mut index = self.random_below(len - 1);
+        if index >= self.current_index {
+            index += 1;
+        }
+        self.current_index = index;
+        self.last_nav_direction = 0;
+        self.current_path()
+    }
+
+    /// Begin a shuffled round: every image but the current one, in random
+    /// order.
+    fn start_shuffle(&mut self) {
+        let current = self.current_path();
+        let mut queue: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|p| Some(*p) != current.as_ref())
+            .cloned()
+            .collect();
+        for i in (1..queue.len()).rev() {
+            let j = self.random_below(i + 1);
+            queue.swap(i, j);
+        }
+        self.shuffle_queue = queue;
+    }
+
+    /// The next image of the shuffled round, skipping any removed since it
+    /// began. Once all are shown a new round starts, or, without `wrap`,
+    /// `None` ends the show.
+    fn next_shuffled(&mut self) -> Option<PathBuf> {
+        loop {
+            let Some(path) = self.shuffle_queue.pop() else {
+                if !self.wrap || self.files.len() < 2 {
+                    return None;
+                }
+                self.start_shuffle();
+                continue;
+            };
+            if let Some(index) = self.files.iter().position(|f| *f == path) {
+                self.current_index = index;
+                self.last_nav_direction = 0;
+                return Some(path);
+            }
+        }
+    }
+
+    /// Jump to the start of the working set; browsing continues forward.
+    fn first(&mut self) -> Option<PathBuf> {
+        self.current_index = 0;
+        self.last_nav_direction = 1;
+        self.current_path()
+    }
+
+    /// Jump to the end of the working set; browsing continues backward.
+    fn last(&mut self) -> Option<PathBuf> {
+        self.current_index = self.files.len().saturating_sub(1);
+        self.last_nav_direction = -1;
         self.current_path()
     }
 
@@ -185,6 +518,7 @@ impl AppState {
     /// When scrubbing forward, prefetch more forward images.
     /// When scrubbing backward, prefetch more backward images.
     /// When neutral (e.g. thumbnail click), prefetch symmetrically.
+    /// Nearest neighbours come first so they win when the cache is small.
     fn adjacent_paths(&self) -> Vec<PathBuf> {
         if self.files.is_empty() {
             return vec![];
@@ -198,149 +532,439 @@ impl AppState {
             _ => (5.min(max_neighbors), 5.min(max_neighbors)),
         };
 
+        // Without wrapping, nothing past either end can come next.
+        let (forward_count, backward_count) = if self.wrap {
+            (forward_count, backward_count)
+        } else {
+            (
+                forward_count.min(len - 1 - self.current_index),
+                backward_count.min(self.current_index),
+            )
+        };
+
         let mut paths = Vec::with_capacity(forward_count + backward_count);
-        for offset in 1..=forward_count {
-            paths.push(self.files[(self.current_index + offset) % len].clone());
-        }
-        for offset in 1..=backward_count {
-            paths.push(self.files[(self.current_index + len - offset) % len].clone());
+        for offset in 1..=forward_count.max(backward_count) {
+            if offset <= forward_count {
+                paths.push(self.files[(self.current_index + offset) % len].clone());
+            }
+            if offset <= backward_count {
+                paths.push(self.files[(self.current_index + len - offset) % len].clone());
+            }
         }
         paths
     }
 }
 
+/// Keep `state.files` in step with the watched directory. The watcher follows
+/// `watched_directory` as folders are opened, and lives as long as the main
+/// loop. If the image on screen disappears, its neighbour is loaded instead.
 fn start_directory_watcher(
     state: Rc<RefCell<AppState>>,
     populate_thumbnails: Rc<dyn Fn()>,
     load_image: Rc<dyn Fn(PathBuf)>,
-) -> notify::RecommendedWatcher {
-    use notify::{RecursiveMode, Watcher};
+) {
+    use notify::{EventKind, RecursiveMode, Watcher};
     use std::sync::mpsc;
+    use std::time::Instant;
 
     let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
 
-    let mut watcher = notify::recommended_watcher(move |res| {
+    let mut watcher = match notify::recommended_watcher(move |res| {
         let _ = tx.send(res);
-    })
-    .expect("Failed to create directory watcher");
+    }) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            eprintln!("[Iris] Directory watching unavailable: {err}");
+            return;
+        }
+    };
 
-    if let Some(dir) = state.borrow().watched_directory.clone() {
-        let _ = watcher.watch(&dir, RecursiveMode::NonRecursive);
-    }
+    // What the watcher is currently registered on.
+    let mut watching: Option<(PathBuf, bool)> = None;
+    let mut last_event: Option<Instant> = None;
+
+    glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+        let wanted = {
+            let s = state.borrow();
+            s.watched_directory
+                .clone()
+                .map(|dir| (dir, s.recursive_scan))
+        };
+        if wanted != watching {
+            if let Some((dir, _)) = watching.take() {
+                let _ = watcher.unwatch(&dir);
+            }
+            if let Some((dir, recursive)) = &wanted {
+                let mode = if *recursive {
+                    RecursiveMode::Recursive
+                } else {
+                    RecursiveMode::NonRecursive
+                };
+                if let Err(err) = watcher.watch(dir, mode) {
+                    eprintln!("[Iris] Cannot watch {}: {err}", dir.display());
+                }
+            }
+            watching = wanted;
+            // Events queued for the old directory no longer apply.
+            while rx.try_recv().is_ok() {}
+            last_event = None;
+        }
 
-    glib::timeout_add_local(std::time::Duration::from_millis(250), move || {
-        let mut changed = false;
         while let Ok(res) = rx.try_recv() {
             match res {
-                Ok(_event) => changed = true,
+                Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+                Ok(_) => last_event = Some(Instant::now()),
                 Err(err) => eprintln!("[Iris] Directory watch error: {err}"),
             }
         }
 
-        if changed {
-            let next = state.borrow_mut().refresh_watched_directory();
+        if last_event.is_some_and(|t| t.elapsed() >= WATCH_DEBOUNCE) {
+            last_event = None;
+            let (previous, next) = {
+                let mut s = state.borrow_mut();
+                let previous = s.current_path();
+                (previous, s.refresh_watched_directory())
+            };
             populate_thumbnails();
-            if let Some(path) = next {
+            // Only reload when the image on screen went away.
+            if let Some(path) = next.filter(|p| previous.as_ref() != Some(p)) {
                 load_image(path);
             }
         }
 
         glib::ControlFlow::Continue
     });
+}
 
-    watcher
+/// Height of the thumbnail strip's scroller: the cell plus the strip's
+/// margins and button padding.
+/// Tell the user the folder of `path` couldn't be listed.
+fn report_unreadable_directory(
+    toast_overlay: &adw::ToastOverlay,
+    path: &Path,
+    err: &std::io::Error,
+) {
+    let dir = path.parent().unwrap_or(path);
+    eprintln!("[Iris] Cannot read {}: {err}", dir.display());
+    toast_overlay.add_toast(adw::Toast::new(&format!(
+        "Could not open the folder: {err}"
+    )));
 }
 
-/// Load or generate a 128×128 RGBA8 thumbnail entirely off the GTK thread.
-fn load_or_generate_thumb(path: &Path) -> Option<Vec<u8>> {
-    let thumb_size = 128u32;
+fn thumb_strip_height(size: ThumbSize) -> i32 {
+    size.pixels() + 18
+}
 
-    let cache_dir = dirs::cache_dir()?.join("thumbnails").join("normal");
-    let uri = format!(
-        "file://{}",
-        path.canonicalize()
-            .unwrap_or_else(|_| path.to_path_buf())
-            .to_string_lossy()
-    );
-    let key = format!("{:x}", md5::compute(uri.as_bytes()));
-    let thumb_path = cache_dir.join(format!("{key}.png"));
+/// Scroll `thumb_scroll` so `btn` sits in the middle, as far as the strip's
+/// ends allow. Returns false if the button hasn't been allocated yet.
+fn center_thumb(thumb_scroll: &gtk4::ScrolledWindow, btn: &impl IsA<gtk4::Widget>) -> bool {
+    let btn_width = btn.width() as f64;
+    let Some(point) = btn.compute_point(thumb_scroll, &gtk4::graphene::Point::new(0.0, 0.0)) else {
+        return false;
+    };
+    if btn_width <= 0.0 {
+        return false;
+    }
+    // `point` is relative to the visible area, so add the current offset.
+    let hadj = thumb_scroll.hadjustment();
+    let scroll_width = thumb_scroll.width() as f64;
+    hadj.set_value(hadj.value() + point.x() as f64 + btn_width / 2.0 - scroll_width / 2.0);
+    true
+}
 
-    // Try disk cache
-    if let (Ok(src_meta), Ok(thumb_meta)) =
-        (std::fs::metadata(path), std::fs::metadata(&thumb_path))
-    {
-        if let (Ok(src_mtime), Ok(thumb_mtime)) = (src_meta.modified(), thumb_meta.modified()) {
-            if thumb_mtime >= src_mtime {
-                if let Ok(img) = image::open(&thumb_path) {
-                    let rgba = img.to_rgba8();
-                    let resized = image::imageops::resize(
-                        &rgba,
-                        thumb_size,
-                        thumb_size,
-                        image::imageops::FilterType::Triangle,
-                    );
-                    return Some(resized.into_raw());
-                }
+/// Whether the desktop allows animations; off when the user has asked for
+/// reduced motion.
+fn animations_enabled() -> bool {
+    gtk4::Settings::default().is_none_or(|s| s.is_gtk_enable_animations())
+}
+
+/// `ms`, or 0 under reduced motion.
+fn motion_duration(ms: u32) -> u32 {
+    if animations_enabled() { ms } else { 0 }
+}
+
+/// Show `stars` on a rating label, hiding it for unrated images.
+fn show_rating(label: &gtk4::Label, stars: u8) {
+    label.set_label(&ratings::stars_label(stars));
+    label.set_visible(stars > 0);
+}
+
+/// `time` as a local date and time in the user's locale, e.g. for file
+/// timestamps in the info panel.
+fn format_file_time(time: std::time::SystemTime) -> Option<String> {
+    let secs = time.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs();
+    let local = glib::DateTime::from_unix_local(secs as i64).ok()?;
+    local.format("%x %X").ok().map(String::from)
+}
+
+/// Star rating typed with a number key, on the main row or the keypad.
+fn rating_for_key(key: gtk4::gdk::Key) -> Option<u8> {
+    let digit = key.to_unicode()?.to_digit(10)?;
+    (digit <= ratings::MAX_STARS as u32).then_some(digit as u8)
+}
+
+/// A thumbnail waiting for a decode slot.
+struct ThumbJob {
+    index: usize,
+    path: PathBuf,
+    picture: gtk4::Picture,
+    stack: gtk4::Stack,
+    flavor: thumbcache::Flavor,
+    generation: u64,
+}
+
+/// Thumbnail decodes not yet started, plus the number in flight. Jobs are
+/// started nearest-to-the-current-image first, so the visible part of the
+/// strip fills in before distant entries.
+#[derive(Default)]
+struct ThumbQueue {
+    /// Off-screen thumbnails, by index; requested once scrolled near.
+    deferred: HashMap<usize, ThumbJob>,
+    pending: Vec<ThumbJob>,
+    in_flight: usize,
+}
+
+/// Thumbnail indices within a page of the visible part of the strip.
+/// Thumbnails are equally wide, so the index follows from the scroll
+/// offset alone.
+fn visible_thumb_range(hadj: &gtk4::Adjustment, count: usize, current: usize) -> Range<usize> {
+    let span = hadj.upper() - hadj.lower();
+    let page = hadj.page_size();
+    if span <= 0.0 || page <= 0.0 {
+        // Not laid out yet: start around the current image.
+        return current.saturating_sub(THUMB_LOOKAHEAD)..(current + THUMB_LOOKAHEAD + 1).min(count);
+    }
+    let per_thumb = span / count.max(1) as f64;
+    let offset = hadj.value() - hadj.lower();
+    let first = ((offset - page) / per_thumb).floor().max(0.0) as usize;
+    let last = ((offset + 2.0 * page) / per_thumb).ceil() as usize;
+    first.min(count)..last.min(count)
+}
+
+/// Start queued thumbnail decodes until `THUMB_DECODE_CONCURRENCY` are
+/// running. Each completion frees its slot and pumps the queue again.
+fn pump_thumb_queue(
+    queue: &Rc<RefCell<ThumbQueue>>,
+    state: &Rc<RefCell<AppState>>,
+    on_done: &Rc<dyn Fn(u64)>,
+) {
+    loop {
+        let job = {
+            let mut q = queue.borrow_mut();
+            if q.in_flight >= THUMB_DECODE_CONCURRENCY {
+                return;
+            }
+            let current = state.borrow().current_index;
+            let Some(next) =
+                (0..q.pending.len()).min_by_key(|&i| q.pending[i].index.abs_diff(current))
+            else {
+                return;
+            };
+            q.in_flight += 1;
+            q.pending.swap_remove(next)
+        };
+
+        let (tx, rx) = futures::channel::oneshot::channel();
+        let path = job.path.clone();
+        let flavor = job.flavor;
+        rayon::spawn(move || {
+            let _ = tx.send(thumbcache::load_or_generate_flavor(&path, flavor));
+        });
+
+        let queue = queue.clone();
+        let state = state.clone();
+        let on_done = on_done.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(Some(thumb)) = rx.await {
+                let (w, h) = thumb.dimensions();
+                let glib_bytes = glib::Bytes::from_owned(thumb.into_raw());
+                let texture = gtk4::gdk::MemoryTexture::new(
+                    w as i32,
+                    h as i32,
+                    gtk4::gdk::MemoryFormat::R8g8b8a8,
+                    &glib_bytes,
+                    (w * 4) as usize,
+                );
+                job.picture.set_paintable(Some(&texture));
             }
+            job.stack.set_visible_child_name("image");
+            queue.borrow_mut().in_flight -= 1;
+            on_done(job.generation);
+            pump_thumb_queue(&queue, &state, &on_done);
+        });
+    }
+}
+
+/// Compute the histogram for `path` off the GTK thread and redraw `area`,
+/// unless another image was opened in the meantime.
+fn spawn_histogram(
+    path: PathBuf,
+    state: Rc<RefCell<AppState>>,
+    histogram: Rc<RefCell<Option<Histogram>>>,
+    area: gtk4::DrawingArea,
+) {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    let path_hist = path.clone();
+    rayon::spawn(move || {
+        let _ = tx.send(Histogram::from_path(&path_hist));
+    });
+    glib::spawn_future_local(async move {
+        let Ok(result) = rx.await else { return };
+        if state.borrow().current_path().as_deref() != Some(path.as_path()) {
+            return;
+        }
+        *histogram.borrow_mut() = result;
+        area.queue_draw();
+    });
+}
+
+fn draw_histogram(cr: &gtk4::cairo::Context, width: i32, height: i32, hist: Option<&Histogram>) {
+    let Some(hist) = hist else { return };
+    let peak = hist.peak();
+    if peak == 0 {
+        return;
+    }
+    let (w, h) = (width as f64, height as f64);
+    let bin_w = w / histogram::BINS as f64;
+
+    let channels = [
+        (&hist.red, (0.95, 0.3, 0.3)),
+        (&hist.green, (0.3, 0.85, 0.4)),
+        (&hist.blue, (0.35, 0.5, 1.0)),
+        (&hist.luma, (0.85, 0.85, 0.85)),
+    ];
+    for (bins, (r, g, b)) in channels {
+        cr.move_to(0.0, h);
+        for (i, &count) in bins.iter().enumerate() {
+            // Square root keeps small bins visible next to a dominant peak.
+            let y = h - (count as f64 / peak as f64).sqrt() * h;
+            cr.line_to(i as f64 * bin_w, y);
+            cr.line_to((i + 1) as f64 * bin_w, y);
         }
+        cr.line_to(w, h);
+        cr.close_path();
+        cr.set_source_rgba(r, g, b, 0.35);
+        let _ = cr.fill();
     }
+}
+
+/// Set `path` as the desktop background through the XDG wallpaper portal,
+/// which previews the image and works across desktops and sandboxes. Falls
+/// back to GNOME's settings when no portal answers.
+fn set_wallpaper(path: &Path, on_error: impl FnOnce(String) + 'static) {
+    use gtk4::gio;
 
-    // Cache miss — generate
-    let thumb = if crate::raw::is_raw(path) {
-        let raw_img = crate::raw::decode_raw(path)?;
-        let rgba8 = crate::raw::linear_16_to_srgb_8(&raw_img.data, raw_img.width, raw_img.height);
-        let img = image::RgbaImage::from_raw(raw_img.width, raw_img.height, rgba8)?;
-        image::imageops::resize(
-            &img,
-            thumb_size,
-            thumb_size,
-            image::imageops::FilterType::Triangle,
-        )
-    } else {
-        let img = image::open(path).ok()?.to_rgba8();
-        let (w, h) = img.dimensions();
-        let icc = crate::color::extract_icc_profile(path);
-        let corrected = crate::color::rgba8_to_srgb_with_icc(img.as_raw(), w, h, icc.as_deref());
-        let corrected_img = image::RgbaImage::from_raw(w, h, corrected)?;
-        image::imageops::resize(
-            &corrected_img,
-            thumb_size,
-            thumb_size,
-            image::imageops::FilterType::Triangle,
-        )
+    let uri = gio::File::for_path(path).uri().to_string();
+    let connection = match gio::bus_get_sync(gio::BusType::Session, gio::Cancellable::NONE) {
+        Ok(c) => c,
+        Err(_) => {
+            if let Err(e) = set_gnome_wallpaper(&uri) {
+                on_error(e);
+            }
+            return;
+        }
     };
+    let options: HashMap<String, glib::Variant> = HashMap::from([
+        ("show-preview".to_string(), true.to_variant()),
+        ("set-on".to_string(), "both".to_variant()),
+    ]);
+    connection.call(
+        Some("org.freedesktop.portal.Desktop"),
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.Wallpaper",
+        "SetWallpaperURI",
+        Some(&("", uri.as_str(), options).to_variant()),
+        None,
+        gio::DBusCallFlags::NONE,
+        -1,
+        gio::Cancellable::NONE,
+        move |result| {
+            if result.is_err() {
+                if let Err(e) = set_gnome_wallpaper(&uri) {
+                    on_error(e);
+                }
+            }
+        },
+    );
+}
 
-    // Save to disk cache
-    let _ = std::fs::create_dir_all(&cache_dir);
-    let _ = thumb.save_with_format(&thumb_path, image::ImageFormat::Png);
+fn set_gnome_wallpaper(uri: &str) -> Result<(), String> {
+    use gtk4::gio;
 
-    Some(thumb.into_raw())
+    const SCHEMA: &str = "org.gnome.desktop.background";
+    // `Settings::new` aborts on a missing schema, so look it up first.
+    let Some(schema) = gio::SettingsSchemaSource::default().and_then(|s| s.lookup(SCHEMA, true))
+    else {
+        return Err("No wallpaper service found on this desktop".into());
+    };
+    let settings = gio::Settings::new(SCHEMA);
+    settings
+        .set_string("picture-uri", uri)
+        .map_err(|e| e.to_string())?;
+    // The dark style has its own key since GNOME 42.
+    if schema.has_key("picture-uri-dark") {
+        settings
+            .set_string("picture-uri-dark", uri)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
 }
 
 fn main() {
+    // `--export` renders one view of an image to a file, with no window.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(request) = viewport::headless::parse_args(&args) {
+        let result = request.and_then(|request| viewport::headless::export(&request));
+        if let Err(e) = result {
+            eprintln!("[Iris] {e}\n{}", viewport::headless::USAGE);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let app = adw::Application::builder()
         .application_id(APP_ID)
         .flags(gtk4::gio::ApplicationFlags::HANDLES_OPEN)
         .build();
 
-    app.connect_activate(|app| {
-        build_ui(app, None);
+    // Opens a path in the existing window; set once the UI is built.
+    let open_path: Rc<RefCell<Option<Rc<dyn Fn(PathBuf)>>>> = Rc::new(RefCell::new(None));
+
+    app.connect_activate({
+        let open_path = open_path.clone();
+        move |app| present_or_build(app, &open_path, None)
     });
 
-    app.connect_open(|app, files, _hint| {
-        let path = files.first().and_then(|f| f.path());
-        build_ui(app, path);
+    app.connect_open({
+        let open_path = open_path.clone();
+        move |app, files, _hint| {
+            let path = files.first().and_then(|f| f.path());
+            present_or_build(app, &open_path, path);
+        }
     });
 
     app.run();
 }
 
-fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
+/// A second `iris <path>` is forwarded to the running instance, which
+/// should switch to the new path rather than just raise its window.
+fn present_or_build(
+    app: &adw::Application,
+    open_path: &Rc<RefCell<Option<Rc<dyn Fn(PathBuf)>>>>,
+    path: Option<PathBuf>,
+) {
     if let Some(window) = app.active_window() {
         window.present();
+        let open = open_path.borrow().clone();
+        if let (Some(open), Some(path)) = (open, path) {
+            open(path);
+        }
         return;
     }
+    *open_path.borrow_mut() = Some(build_ui(app, path));
+}
 
+fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) -> Rc<dyn Fn(PathBuf)> {
     let cfg = Config::load();
 
     let window = adw::ApplicationWindow::builder()
@@ -353,6 +977,9 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
     if cfg.window_maximized {
         window.maximize();
     }
+    if cfg.window_fullscreen {
+        window.fullscreen();
+    }
 
     let css = gtk4::CssProvider::new();
     css.load_from_string(
@@ -364,6 +991,21 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
         .info-panel { padding: 16px; border-left: 1px solid alpha(@borders, 0.5); }
         .info-field-label { font-size: 11px; opacity: 0.5; margin-top: 10px; text-transform: uppercase; letter-spacing: 0.5px; }
         .info-field-value { font-weight: 600; }
+        .raw-badge { font-size: 10px; font-weight: 700; padding: 1px 6px; border-radius: 4px; background: alpha(@accent_bg_color, 0.25); }
+        .drop-zone:drop(active) { outline: 3px solid alpha(@accent_color, 0.8); outline-offset: -3px; }
+        .histogram { margin: 12px; border-radius: 8px; background: alpha(@window_bg_color, 0.8); }
+        .zoom-indicator { margin: 16px; padding: 4px 10px; border-radius: 6px; background: alpha(@window_bg_color, 0.85); font-size: 12px; font-weight: 600; transition: opacity 300ms ease; }
+        .zoom-indicator.faded { opacity: 0; }
+        .minimap { margin: 16px; border-radius: 6px; background: alpha(@window_bg_color, 0.85); box-shadow: 0 1px 4px alpha(black, 0.4); }
+        .pixel-readout { margin: 16px; padding: 4px 10px; border-radius: 6px; background: alpha(@window_bg_color, 0.85); font-family: monospace; font-size: 12px; }
+        .status-bar { padding: 2px 10px; font-size: 11px; border-top: 1px solid alpha(@borders, 0.5); }
+        .status-bar label { opacity: 0.7; }
+        .thumb-rating { margin: 4px; padding: 0 4px; border-radius: 4px; background: alpha(black, 0.6); color: #f6d32d; font-size: 10px; }
+        .load-progress { padding: 8px 12px; border-radius: 8px; background: alpha(@window_bg_color, 0.85); }
+        .gallery-tile { padding: 6px; }
+        .gallery-name { font-size: 11px; opacity: 0.8; }
+        .rating-overlay { margin: 16px; padding: 4px 10px; border-radius: 6px; background: alpha(@window_bg_color, 0.85); color: #e5a50a; font-size: 14px; }
+        .thumb-progress { margin: 6px 10px; padding: 4px 8px; border-radius: 6px; background: alpha(@window_bg_color, 0.85); font-size: 11px; }
     ",
     );
     gtk4::style_context_add_provider_for_display(
@@ -374,11 +1016,29 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
 
     let state = Rc::new(RefCell::new(AppState::new()));
     state.borrow_mut().info_visible = cfg.info_panel_visible;
+    state.borrow_mut().alpha_background = cfg.alpha_background;
+    state.borrow_mut().recursive_scan = cfg.recursive_scan;
+    state.borrow_mut().remember_view = cfg.remember_view;
+    state.borrow_mut().scroll_navigates = cfg.scroll_navigates;
+    state.borrow_mut().wrap = cfg.wrap_navigation;
+    state.borrow_mut().skip_unreadable = cfg.skip_unreadable;
+    state.borrow_mut().sort_order = cfg.sort_order;
 
     let toolbar_view = adw::ToolbarView::new();
     let header = adw::HeaderBar::new();
 
     let open_btn = gtk4::Button::builder().label("Open").build();
+    let zoom_entry = gtk4::Entry::builder()
+        .width_chars(6)
+        .max_width_chars(6)
+        .xalign(1.0)
+        .placeholder_text("Zoom")
+        .tooltip_text("Zoom, in percent of actual size")
+        .build();
+    let open_folder_btn = gtk4::Button::builder()
+        .icon_name("folder-open-symbolic")
+        .tooltip_text("Open Folder")
+        .build();
     let rotate_cw_btn = gtk4::Button::builder()
         .icon_name("object-rotate-right-symbolic")
         .tooltip_text("Rotate CW (R)")
@@ -387,6 +1047,26 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
         .icon_name("object-rotate-left-symbolic")
         .tooltip_text("Rotate CCW (Shift+R)")
         .build();
+    let flip_h_btn = gtk4::Button::builder()
+        .icon_name("object-flip-horizontal-symbolic")
+        .tooltip_text("Flip Horizontally (H)")
+        .build();
+    let flip_v_btn = gtk4::Button::builder()
+        .icon_name("object-flip-vertical-symbolic")
+        .tooltip_text("Flip Vertically (V)")
+        .build();
+    let reset_view_btn = gtk4::Button::builder()
+        .icon_name("view-restore-symbolic")
+        .tooltip_text("Reset View (Backspace)")
+        .build();
+    let histogram_btn = gtk4::ToggleButton::builder()
+        .icon_name("utilities-system-monitor-symbolic")
+        .tooltip_text("Histogram")
+        .build();
+    let crop_btn = gtk4::ToggleButton::builder()
+        .icon_name("edit-cut-symbolic")
+        .tooltip_text("Crop and Export Selection (X)")
+        .build();
     let info_btn = gtk4::Button::builder()
         .icon_name("dialog-information-symbolic")
         .tooltip_text("Image info (I)")
@@ -405,10 +1085,155 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
         .tooltip_text("Denoise (D)")
         .build();
 
+    // ── Exposure / brightness / contrast / straighten popover ─────────────
+    let adjust_scale = |label: &str| {
+        let scale = gtk4::Scale::with_range(Orientation::Horizontal, -100.0, 100.0, 1.0);
+        scale.set_value(0.0);
+        scale.set_draw_value(true);
+        scale.set_value_pos(gtk4::PositionType::Right);
+        scale.set_width_request(220);
+        scale.add_mark(0.0, gtk4::PositionType::Bottom, None);
+        let row = gtk4::Box::new(Orientation::Vertical, 2);
+        row.append(
+            &gtk4::Label::builder()
+                .label(label)
+                .xalign(0.0)
+                .css_classes(["info-field-label"])
+                .build(),
+        );
+        row.append(&scale);
+        (row, scale)
+    };
+    let (exposure_row, exposure_scale) = adjust_scale("Exposure");
+    exposure_scale.set_range(-EXPOSURE_RANGE_STOPS as f64, EXPOSURE_RANGE_STOPS as f64);
+    exposure_scale.set_increments(0.1, 1.0);
+    exposure_scale.set_digits(1);
+    let (gamma_row, gamma_scale) = adjust_scale("Gamma");
+    gamma_scale.set_range(GAMMA_RANGE.0 as f64, GAMMA_RANGE.1 as f64);
+    gamma_scale.set_increments(0.05, 0.25);
+    gamma_scale.set_digits(2);
+    gamma_scale.clear_marks();
+    gamma_scale.add_mark(1.0, gtk4::PositionType::Bottom, None);
+    gamma_scale.set_value(1.0);
+    let (brightness_row, brightness_scale) = adjust_scale("Brightness");
+    let (contrast_row, contrast_scale) = adjust_scale("Contrast");
+    let (straighten_row, straighten_scale) = adjust_scale("Straighten");
+    straighten_scale.set_range(
+        -MAX_STRAIGHTEN_DEGREES as f64,
+        MAX_STRAIGHTEN_DEGREES as f64,
+    );
+    straighten_scale.set_increments(0.1, 1.0);
+    straighten_scale.set_digits(1);
+    let grayscale_check = gtk4::CheckButton::with_label("Grayscale (G)");
+    let invert_check = gtk4::CheckButton::with_label("Invert Colors (N)");
+    let adjust_reset_btn = gtk4::Button::builder()
+        .label("Reset")
+        .halign(gtk4::Align::End)
+        .build();
+    let adjust_box = gtk4::Box::new(Orientation::Vertical, 6);
+    adjust_box.set_margin_top(6);
+    adjust_box.set_margin_bottom(6);
+    adjust_box.set_margin_start(6);
+    adjust_box.set_margin_end(6);
+    adjust_box.append(&exposure_row);
+    adjust_box.append(&gamma_row);
+    adjust_box.append(&brightness_row);
+    adjust_box.append(&contrast_row);
+    adjust_box.append(&grayscale_check);
+    adjust_box.append(&invert_check);
+    adjust_box.append(&straighten_row);
+    adjust_box.append(&adjust_reset_btn);
+    let adjust_btn = gtk4::MenuButton::builder()
+        .icon_name("preferences-color-symbolic")
+        .tooltip_text("Adjust Colors")
+        .popover(&gtk4::Popover::builder().child(&adjust_box).build())
+        .build();
+    // Set while the sliders are moved to show another image's values, so
+    // that doesn't register as an edit.
+    let adjust_syncing = Rc::new(Cell::new(false));
+
+    let slideshow_menu = gtk4::gio::Menu::new();
+    slideshow_menu.append(Some("Start Slideshow"), Some("win.slideshow"));
+    slideshow_menu.append(Some("Shuffle"), Some("win.slideshow-shuffle"));
+    slideshow_menu.append(Some("Random Image"), Some("win.random-image"));
+    let interval_menu = gtk4::gio::Menu::new();
+    for secs in SLIDESHOW_INTERVALS {
+        let item = gtk4::gio::MenuItem::new(Some(&format!("Every {secs} Seconds")), None);
+        item.set_action_and_target_value(Some("win.slideshow-interval"), Some(&secs.to_variant()));
+        interval_menu.append_item(&item);
+    }
+    slideshow_menu.append_section(Some("Interval"), &interval_menu);
+    let transition_menu = gtk4::gio::Menu::new();
+    for (i, transition) in ImageTransition::ALL.into_iter().enumerate() {
+        let item = gtk4::gio::MenuItem::new(Some(transition.label()), None);
+        item.set_action_and_target_value(
+            Some("win.image-transition"),
+            Some(&(i as u32).to_variant()),
+        );
+        transition_menu.append_item(&item);
+    }
+    slideshow_menu.append_section(Some("Transition"), &transition_menu);
+    let slideshow_btn = gtk4::MenuButton::builder()
+        .icon_name("media-playback-start-symbolic")
+        .tooltip_text("Slideshow (F5)")
+        .menu_model(&slideshow_menu)
+        .build();
+
+    let sort_menu = gtk4::gio::Menu::new();
+    for order in SortOrder::ALL {
+        let item = gtk4::gio::MenuItem::new(Some(order.label()), None);
+        item.set_action_and_target_value(Some("win.sort-order"), Some(&order.id().to_variant()));
+        sort_menu.append_item(&item);
+    }
+    let rating_menu = gtk4::gio::Menu::new();
+    for stars in 0..=ratings::MAX_STARS {
+        let label = match stars {
+            0 => "All Images".to_string(),
+            ratings::MAX_STARS => "★★★★★ Only".to_string(),
+            n => format!("{} and Up", ratings::stars_label(n)),
+        };
+        let item = gtk4::gio::MenuItem::new(Some(&label), None);
+        item.set_action_and_target_value(
+            Some("win.min-rating"),
+            Some(&u32::from(stars).to_variant()),
+        );
+        rating_menu.append_item(&item);
+    }
+    sort_menu.append_section(Some("Show"), &rating_menu);
+    let sort_btn = gtk4::MenuButton::builder()
+        .icon_name("view-sort-ascending-symbolic")
+        .tooltip_text("Sort and Filter")
+        .menu_model(&sort_menu)
+        .build();
+
+    let gallery_btn = gtk4::ToggleButton::builder()
+        .icon_name("view-grid-symbolic")
+        .tooltip_text("Gallery")
+        .build();
+
+    let recent_menu = gtk4::gio::Menu::new();
+    let recent_btn = gtk4::MenuButton::builder()
+        .icon_name("document-open-recent-symbolic")
+        .tooltip_text("Recent Files")
+        .menu_model(&recent_menu)
+        .build();
+
     header.pack_start(&open_btn);
+    header.pack_start(&open_folder_btn);
+    header.pack_start(&recent_btn);
+    header.pack_start(&slideshow_btn);
+    header.pack_start(&sort_btn);
+    header.pack_start(&gallery_btn);
+    header.pack_start(&zoom_entry);
     header.pack_end(&info_btn);
+    header.pack_end(&histogram_btn);
+    header.pack_end(&adjust_btn);
+    header.pack_end(&crop_btn);
     header.pack_end(&rotate_cw_btn);
     header.pack_end(&rotate_ccw_btn);
+    header.pack_end(&flip_v_btn);
+    header.pack_end(&flip_h_btn);
+    header.pack_end(&reset_view_btn);
     header.pack_end(&gtk4::Separator::new(Orientation::Vertical));
     header.pack_end(&denoise_btn);
     header.pack_end(&sharpen_btn);
@@ -428,7 +1253,7 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
     viewport_stack.set_vexpand(true);
     viewport_stack.set_hexpand(true);
     viewport_stack.set_transition_type(gtk4::StackTransitionType::Crossfade);
-    viewport_stack.set_transition_duration(150);
+    viewport_stack.set_transition_duration(motion_duration(150));
 
     let viewport = Rc::new(viewport::Viewport::new({
         let toast_overlay = toast_overlay.clone();
@@ -438,8 +1263,52 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
             toast_overlay.add_toast(toast);
         }
     }));
+    viewport.set_cache_capacity(cfg.image_cache_size);
+    viewport.set_load_progress_delay(std::time::Duration::from_millis(
+        cfg.loading_indicator_delay_ms,
+    ));
+    viewport.set_alpha_background(cfg.alpha_background);
+    viewport.set_image_transition(cfg.image_transition);
+    viewport.set_antialiasing(cfg.antialiasing);
+    viewport.set_dither(cfg.dither);
+    viewport.set_free_pan(cfg.free_pan);
     viewport_stack.add_named(&viewport.widget, Some("image"));
 
+    // The zoom entry follows the view unless the user is typing in it.
+    let sync_zoom_entry: Rc<dyn Fn()> = Rc::new({
+        let window = window.clone();
+        let viewport = viewport.clone();
+        let zoom_entry = zoom_entry.clone();
+        move || {
+            let editing = GtkWindowExt::focus(&window).is_some_and(|w| w.is_ancestor(&zoom_entry));
+            if editing {
+                return;
+            }
+            match viewport.zoom_percent() {
+                Some(percent) => zoom_entry.set_text(&format!("{}%", percent.round() as u32)),
+                None => zoom_entry.set_text(""),
+            }
+        }
+    });
+    viewport.connect_zoom_changed({
+        let sync_zoom_entry = sync_zoom_entry.clone();
+        move |_| sync_zoom_entry()
+    });
+    zoom_entry.connect_activate({
+        let window = window.clone();
+        let viewport = viewport.clone();
+        let sync_zoom_entry = sync_zoom_entry.clone();
+        move |entry| {
+            if let Some(percent) = viewport::camera::parse_zoom_percent(&entry.text()) {
+                viewport.set_zoom_percent(percent);
+            }
+            // Hand the keyboard back to the viewer, showing the level that
+            // was applied after clamping, or the old one for bad input.
+            GtkWindowExt::set_focus(&window, None::<&gtk4::Widget>);
+            sync_zoom_entry();
+        }
+    });
+
     let welcome_box = gtk4::Box::new(Orientation::Vertical, 12);
     welcome_box.set_halign(gtk4::Align::Center);
     welcome_box.set_valign(gtk4::Align::Center);
@@ -457,7 +1326,61 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
     viewport_stack.add_named(&welcome_box, Some("welcome"));
     viewport_stack.set_visible_child_name("welcome");
 
-    content_box.append(&*viewport_stack);
+    // Shown instead of the image when it can't be decoded; navigating away
+    // switches back to "image" as usual.
+    let error_box = gtk4::Box::new(Orientation::Vertical, 12);
+    error_box.set_halign(gtk4::Align::Center);
+    error_box.set_valign(gtk4::Align::Center);
+    error_box.set_margin_start(24);
+    error_box.set_margin_end(24);
+    let error_icon = gtk4::Image::from_icon_name("image-missing-symbolic");
+    error_icon.set_pixel_size(64);
+    error_icon.set_opacity(0.5);
+    let error_name = gtk4::Label::builder()
+        .css_classes(["title-4"])
+        .wrap(true)
+        .justify(gtk4::Justification::Center)
+        .build();
+    let error_reason = gtk4::Label::builder()
+        .css_classes(["dim-label"])
+        .wrap(true)
+        .selectable(true)
+        .justify(gtk4::Justification::Center)
+        .max_width_chars(60)
+        .build();
+    error_box.append(&error_icon);
+    error_box.append(&error_name);
+    error_box.append(&error_reason);
+    viewport_stack.add_named(&error_box, Some("error"));
+
+    // ── Histogram overlay ─────────────────────────────────────────────────
+    let histogram: Rc<RefCell<Option<Histogram>>> = Rc::new(RefCell::new(None));
+    let histogram_area = gtk4::DrawingArea::builder()
+        .content_width(256)
+        .content_height(100)
+        .halign(gtk4::Align::End)
+        .valign(gtk4::Align::End)
+        .css_classes(["histogram"])
+        .visible(false)
+        .build();
+    histogram_area.set_draw_func({
+        let histogram = histogram.clone();
+        move |_, cr, w, h| draw_histogram(cr, w, h, histogram.borrow().as_ref())
+    });
+
+    let rating_overlay = gtk4::Label::builder()
+        .halign(gtk4::Align::Start)
+        .valign(gtk4::Align::Start)
+        .css_classes(["rating-overlay"])
+        .can_target(false)
+        .visible(false)
+        .build();
+
+    let viewport_overlay = gtk4::Overlay::new();
+    viewport_overlay.set_child(Some(&*viewport_stack));
+    viewport_overlay.add_overlay(&histogram_area);
+    viewport_overlay.add_overlay(&rating_overlay);
+    content_box.append(&viewport_overlay);
 
     let info_sep = Rc::new(gtk4::Separator::new(Orientation::Vertical));
     info_sep.set_visible(cfg.info_panel_visible);
@@ -500,39 +1423,311 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
 
     let (row_name, info_name) = make_field("Filename");
     let (row_dims, info_dims) = make_field("Dimensions");
+    let (row_megapixels, info_megapixels) = make_field("Megapixels");
+    let (row_aspect, info_aspect) = make_field("Aspect ratio");
     let (row_size, info_size) = make_field("File size");
+    // File system dates; "Captured" below comes from EXIF, and differs from
+    // these for copied or edited files.
+    let (row_modified, info_modified) = make_field("File modified");
+    let (row_created, info_created) = make_field("File created");
+    row_created.set_visible(false);
+    let (row_profile, info_profile) = make_field("Color profile");
+    // Estimated for JPEG and WebP; "n/a" for formats without a quality
+    // setting.
+    let (row_quality, info_quality) = make_field("Quality");
     let (row_path, info_path_lbl) = make_field("Path");
+    // Depth and channels stored in the file, once the image has decoded.
+    let (row_format, info_format) = make_field("Pixel format");
+    row_format.set_visible(false);
+    let (row_alpha, info_alpha) = make_field("Alpha");
+    row_alpha.set_visible(false);
+    // Only known for formats that are decoded to fewer bits than they
+    // store: JPEG XL and HEIF.
+    let (row_depth, info_depth) = make_field("Source bit depth");
+    row_depth.set_visible(false);
+    // Only for float (HDR) images, once they have been decoded.
+    let (row_range, info_range) = make_field("Dynamic range");
+    row_range.set_visible(false);
+
+    // RAW files are shown from the camera's embedded JPEG when it is big
+    // enough; say so, since edits and white balance aren't the sensor data.
+    let raw_preview_badge = gtk4::Label::builder()
+        .label("RAW preview")
+        .halign(gtk4::Align::Start)
+        .tooltip_text("Showing the camera's embedded JPEG, not a full RAW decode")
+        .css_classes(["raw-badge"])
+        .visible(false)
+        .build();
+    row_dims.append(&raw_preview_badge);
 
     info_panel.append(&row_name);
     info_panel.append(&row_dims);
+    info_panel.append(&row_megapixels);
+    info_panel.append(&row_aspect);
     info_panel.append(&row_size);
+    info_panel.append(&row_modified);
+    info_panel.append(&row_created);
+    info_panel.append(&row_quality);
+    info_panel.append(&row_profile);
+    info_panel.append(&row_format);
+    info_panel.append(&row_alpha);
+    info_panel.append(&row_depth);
+    info_panel.append(&row_range);
     info_panel.append(&row_path);
 
-    let thumb_scroll = Rc::new(
-        gtk4::ScrolledWindow::builder()
-            .hscrollbar_policy(gtk4::PolicyType::Automatic)
-            .vscrollbar_policy(gtk4::PolicyType::Never)
-            .height_request(108)
-            .focusable(false)
-            .can_focus(false)
+    // Dimensions and the fields derived from them, once an image decodes.
+    let show_dimensions: Rc<dyn Fn(u32, u32)> = Rc::new({
+        let info_dims = info_dims.clone();
+        let info_megapixels = info_megapixels.clone();
+        let info_aspect = info_aspect.clone();
+        move |w, h| {
+            info_dims.set_label(&format!("{}×{} px", w, h));
+            info_megapixels.set_label(&metadata::format_megapixels(w, h));
+            info_aspect.set_label(&metadata::format_aspect_ratio(w, h));
+        }
+    });
+
+    // Camera fields stay hidden until the image's EXIF provides them.
+    let camera_fields: Rc<Vec<(gtk4::Box, Rc<gtk4::Label>)>> = Rc::new(
+        CameraInfo::default()
+            .rows()
+            .into_iter()
+            .map(|(label, _)| {
+                let (row, value) = make_field(label);
+                row.set_visible(false);
+                info_panel.append(&row);
+                (row, value)
+            })
+            .collect(),
+    );
+
+    let location_row = gtk4::Box::new(Orientation::Vertical, 2);
+    location_row.append(
+        &gtk4::Label::builder()
+            .label("Location")
+            .xalign(0.0)
+            .css_classes(["info-field-label"])
             .build(),
     );
+    let location_link = gtk4::LinkButton::builder()
+        .halign(gtk4::Align::Start)
+        .tooltip_text("Open in OpenStreetMap")
+        .build();
+    location_row.append(&location_link);
+    location_row.set_visible(false);
+    info_panel.append(&location_row);
 
-    let thumb_strip = Rc::new(gtk4::Box::new(Orientation::Horizontal, 6));
+    // Multi-page TIFFs get a page picker; single images hide it.
+    let page_row = gtk4::Box::new(Orientation::Vertical, 2);
+    page_row.append(
+        &gtk4::Label::builder()
+            .label("Page")
+            .xalign(0.0)
+            .css_classes(["info-field-label"])
+            .build(),
+    );
+    let page_picker = gtk4::Box::new(Orientation::Horizontal, 6);
+    let page_spin = gtk4::SpinButton::with_range(1.0, 1.0, 1.0);
+    page_spin.set_tooltip_text(Some("Page Up / Page Down"));
+    let page_total = gtk4::Label::builder()
+        .css_classes(["info-field-value"])
+        .build();
+    page_picker.append(&page_spin);
+    page_picker.append(&page_total);
+    page_row.append(&page_picker);
+    page_row.set_visible(false);
+    info_panel.append(&page_row);
+
+    let copy_metadata_btn = gtk4::Button::builder()
+        .label("Copy Metadata")
+        .tooltip_text("Copy the details above and every EXIF tag as text")
+        .build();
+    let save_metadata_btn = gtk4::Button::builder()
+        .label("Save as Text")
+        .tooltip_text("Write the metadata to a .txt file next to the image")
+        .build();
+    let metadata_actions = gtk4::Box::new(Orientation::Horizontal, 6);
+    metadata_actions.set_margin_top(6);
+    metadata_actions.append(&copy_metadata_btn);
+    metadata_actions.append(&save_metadata_btn);
+    info_panel.append(&metadata_actions);
+    // Page of the current file on screen, 0-based.
+    let current_page = Rc::new(Cell::new(0usize));
+
+    let thumb_size = Rc::new(Cell::new(cfg.thumb_size));
+    let thumb_scroll = Rc::new(
+        gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vscrollbar_policy(gtk4::PolicyType::Never)
+            .height_request(thumb_strip_height(cfg.thumb_size))
+            .focusable(false)
+            .can_focus(false)
+            .build(),
+    );
+
+    let thumb_strip = Rc::new(gtk4::Box::new(Orientation::Horizontal, 6));
     thumb_strip.set_margin_start(8);
     thumb_strip.set_margin_end(8);
     thumb_strip.set_margin_top(6);
     thumb_strip.set_margin_bottom(6);
     thumb_scroll.set_child(Some(&*thumb_strip));
 
-    root_box.append(&content_box);
-    root_box.append(&gtk4::Separator::new(Orientation::Horizontal));
-    root_box.append(&*thumb_scroll);
+    // Overall thumbnail progress: pulses until the first thumbnail lands,
+    // then fills as the rest complete. Never intercepts pointer events.
+    let thumb_progress = Rc::new(
+        gtk4::ProgressBar::builder()
+            .show_text(true)
+            .halign(gtk4::Align::End)
+            .valign(gtk4::Align::Start)
+            .can_target(false)
+            .visible(false)
+            .css_classes(["thumb-progress"])
+            .build(),
+    );
+
+    let thumb_overlay = gtk4::Overlay::new();
+    thumb_overlay.set_child(Some(&*thumb_scroll));
+    thumb_overlay.add_overlay(&*thumb_progress);
+
+    // ── Gallery: every image of the folder as a grid of tiles ─────────────
+    let gallery_grid = Rc::new(
+        gtk4::FlowBox::builder()
+            .homogeneous(true)
+            .selection_mode(gtk4::SelectionMode::None)
+            .activate_on_single_click(true)
+            .valign(gtk4::Align::Start)
+            .max_children_per_line(64)
+            .column_spacing(8)
+            .row_spacing(8)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .build(),
+    );
+    let gallery_scroll = gtk4::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vexpand(true)
+        .child(&*gallery_grid)
+        .build();
+
+    let main_stack = Rc::new(gtk4::Stack::new());
+    main_stack.set_transition_type(gtk4::StackTransitionType::Crossfade);
+    main_stack.set_transition_duration(motion_duration(150));
+    main_stack.add_named(&content_box, Some("viewer"));
+    main_stack.add_named(&gallery_scroll, Some("gallery"));
+
+    // ── Reduced motion: follow the desktop's animation setting live ───────
+    viewport.set_animations_enabled(animations_enabled());
+    if let Some(settings) = gtk4::Settings::default() {
+        let viewport = viewport.clone();
+        let viewport_stack = viewport_stack.clone();
+        let main_stack = main_stack.clone();
+        settings.connect_gtk_enable_animations_notify(move |_| {
+            viewport.set_animations_enabled(animations_enabled());
+            viewport_stack.set_transition_duration(motion_duration(150));
+            main_stack.set_transition_duration(motion_duration(150));
+        });
+    }
+
+    let strip_sep = gtk4::Separator::new(Orientation::Horizontal);
+    root_box.append(&*main_stack);
+    root_box.append(&strip_sep);
+    root_box.append(&thumb_overlay);
+
+    // ── Status bar: format and load time of the image on screen ───────────
+    let status_image = gtk4::Label::builder()
+        .halign(gtk4::Align::Start)
+        .hexpand(true)
+        .ellipsize(gtk4::pango::EllipsizeMode::End)
+        .build();
+    let status_gpu = gtk4::Label::builder().halign(gtk4::Align::End).build();
+    match (viewport.gpu_name(), viewport.gpu_summary()) {
+        (Some(name), Some(summary)) => {
+            status_gpu.set_label(&name);
+            status_gpu.set_tooltip_text(Some(&format!(
+                "{name}\n{summary}\nSet IRIS_GPU to discrete, integrated or part of a device name to pick another"
+            )));
+        }
+        _ => {
+            status_gpu.set_label("Software renderer");
+            status_gpu.set_tooltip_text(Some(
+                "Vulkan is unavailable or disabled with IRIS_RENDERER=software",
+            ));
+        }
+    }
+    let status_bar = gtk4::Box::new(Orientation::Horizontal, 12);
+    status_bar.add_css_class("status-bar");
+    status_bar.append(&status_image);
+    status_bar.append(&status_gpu);
+    root_box.append(&status_bar);
+    // Stats of the image on screen, for the metadata report.
+    let current_stats: Rc<RefCell<Option<viewport::LoadStats>>> = Rc::default();
+    viewport.connect_load_stats({
+        let status_image = status_image.clone();
+        let current_stats = current_stats.clone();
+        let row_range = row_range.clone();
+        let info_range = info_range.clone();
+        let row_format = row_format.clone();
+        let info_format = info_format.clone();
+        let row_alpha = row_alpha.clone();
+        let info_alpha = info_alpha.clone();
+        move |stats| {
+            status_image.set_label(&stats.to_string());
+            info_format.set_label(&stats.format);
+            info_alpha.set_label(if stats.has_alpha { "Yes" } else { "No" });
+            row_format.set_visible(true);
+            row_alpha.set_visible(true);
+            if let Some(stops) = stats.dynamic_range_stops {
+                info_range.set_label(&format!("{stops:.1} stops"));
+            }
+            row_range.set_visible(stats.dynamic_range_stops.is_some());
+            *current_stats.borrow_mut() = Some(stats.clone());
+        }
+    });
 
     toolbar_view.set_content(Some(&root_box));
     toast_overlay.set_child(Some(&toolbar_view));
     window.set_content(Some(&toast_overlay));
 
+    // ── Fullscreen: image only, header slides in at the top edge ─────────
+    let set_fullscreen_chrome = {
+        let toolbar_view = toolbar_view.clone();
+        let strip_sep = strip_sep.clone();
+        let thumb_overlay = thumb_overlay.clone();
+        let status_bar = status_bar.clone();
+        move |fullscreen: bool| {
+            // Over the image rather than beside it, so revealing the
+            // header doesn't reflow the view.
+            toolbar_view.set_extend_content_to_top_edge(fullscreen);
+            toolbar_view.set_reveal_top_bars(!fullscreen);
+            strip_sep.set_visible(!fullscreen);
+            thumb_overlay.set_visible(!fullscreen);
+            status_bar.set_visible(!fullscreen);
+        }
+    };
+    set_fullscreen_chrome(window.is_fullscreen());
+    window.connect_fullscreened_notify(move |w| set_fullscreen_chrome(w.is_fullscreen()));
+
+    let reveal_motion = gtk4::EventControllerMotion::new();
+    reveal_motion.connect_motion({
+        let window = window.clone();
+        let toolbar_view = toolbar_view.clone();
+        let header = header.clone();
+        move |_, _, y| {
+            if !window.is_fullscreen() {
+                return;
+            }
+            let revealed = toolbar_view.reveals_top_bars();
+            if !revealed && y <= HEADER_REVEAL_PX {
+                toolbar_view.set_reveal_top_bars(true);
+            } else if revealed && y > header.height() as f64 + HEADER_REVEAL_PX {
+                toolbar_view.set_reveal_top_bars(false);
+            }
+        }
+    });
+    window.add_controller(reveal_motion);
+
     // ── Navigation coalescing state ───────────────────────────────────────
     let nav_pending: Rc<Cell<i32>> = Rc::new(Cell::new(0));
     let nav_scheduled: Rc<Cell<bool>> = Rc::new(Cell::new(false));
@@ -541,71 +1736,196 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
     let prev_active_thumb: Rc<Cell<Option<usize>>> = Rc::new(Cell::new(None));
 
     let thumb_buttons: Rc<RefCell<Vec<gtk4::Button>>> = Rc::new(RefCell::new(vec![]));
+    // Star labels over each thumbnail, by index like `thumb_buttons`.
+    let thumb_ratings: Rc<RefCell<Vec<gtk4::Label>>> = Rc::new(RefCell::new(vec![]));
+
+    // ── Thumbnail progress tracking ───────────────────────────────────────
+    // The generation is bumped on every repopulate so completions from a
+    // previous folder never count towards the current one.
+    let thumb_generation: Rc<Cell<u64>> = Rc::new(Cell::new(0));
+    let thumb_total: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+    let thumb_done: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+    let thumb_queue: Rc<RefCell<ThumbQueue>> = Rc::default();
+
+    let on_thumb_done: Rc<dyn Fn(u64)> = Rc::new({
+        let thumb_progress = thumb_progress.clone();
+        let thumb_generation = thumb_generation.clone();
+        let thumb_total = thumb_total.clone();
+        let thumb_done = thumb_done.clone();
+        move |generation| {
+            if generation != thumb_generation.get() {
+                return;
+            }
+            let total = thumb_total.get();
+            let done = (thumb_done.get() + 1).min(total);
+            thumb_done.set(done);
+            if done >= total {
+                thumb_progress.set_visible(false);
+                return;
+            }
+            thumb_progress.set_fraction(done as f64 / total as f64);
+            thumb_progress.set_text(Some(format!("Loading {done}/{total} thumbnails").as_str()));
+        }
+    });
+
     let load_image_fn: Rc<RefCell<Option<Rc<dyn Fn(PathBuf)>>>> = Rc::new(RefCell::new(None));
 
+    // Center the active thumbnail in the strip. Buttons created by a fresh
+    // populate have no allocation yet; retry once after layout has run.
     let scroll_to_active_thumb = {
         let thumb_buttons = thumb_buttons.clone();
         let thumb_scroll = thumb_scroll.clone();
         let state = state.clone();
         Rc::new(move || {
             let idx = state.borrow().current_index;
-            let btns = thumb_buttons.borrow();
-            if let Some(btn) = btns.get(idx) {
-                let hadj = thumb_scroll.hadjustment();
-                if let Some(point) =
-                    btn.compute_point(&*thumb_scroll, &gtk4::graphene::Point::new(0.0, 0.0))
-                {
-                    let x = point.x() as f64;
-                    let btn_width = btn.width() as f64;
-                    let scroll_width = thumb_scroll.width() as f64;
-                    let current = hadj.value();
-                    if x < 0.0 || x + btn_width > scroll_width {
-                        let target = current + x - (scroll_width / 2.0) + (btn_width / 2.0);
-                        hadj.set_value(target.max(0.0));
-                    }
-                }
+            let Some(btn) = thumb_buttons.borrow().get(idx).cloned() else {
+                return;
+            };
+            if !center_thumb(&thumb_scroll, &btn) {
+                let thumb_scroll = thumb_scroll.clone();
+                glib::idle_add_local_once(move || {
+                    center_thumb(&thumb_scroll, &btn);
+                });
             }
         })
     };
 
+    // Only thumbnails near the visible part of the strip are decoded; the
+    // rest keep their spinner until scrolled close.
+    let request_visible_thumbs: Rc<dyn Fn()> = Rc::new({
+        let thumb_scroll = thumb_scroll.clone();
+        let thumb_queue = thumb_queue.clone();
+        let thumb_buttons = thumb_buttons.clone();
+        let thumb_total = thumb_total.clone();
+        let state = state.clone();
+        let on_thumb_done = on_thumb_done.clone();
+        move || {
+            let requested = {
+                let mut q = thumb_queue.borrow_mut();
+                if q.deferred.is_empty() {
+                    return;
+                }
+                let range = visible_thumb_range(
+                    &thumb_scroll.hadjustment(),
+                    thumb_buttons.borrow().len(),
+                    state.borrow().current_index,
+                );
+                let jobs: Vec<ThumbJob> = range.filter_map(|i| q.deferred.remove(&i)).collect();
+                let n = jobs.len();
+                q.pending.extend(jobs);
+                n
+            };
+            if requested > 0 {
+                thumb_total.set(thumb_total.get() + requested);
+                pump_thumb_queue(&thumb_queue, &state, &on_thumb_done);
+            }
+        }
+    });
+    {
+        let hadj = thumb_scroll.hadjustment();
+        let request = request_visible_thumbs.clone();
+        hadj.connect_value_changed(move |_| request());
+        // Fires when the strip is (re)allocated or the window resized.
+        let request = request_visible_thumbs.clone();
+        hadj.connect_changed(move |_| request());
+    }
+
     let populate_thumbnails: Rc<dyn Fn()> = Rc::new({
         let thumb_strip = thumb_strip.clone();
         let thumb_buttons = thumb_buttons.clone();
+        let thumb_ratings = thumb_ratings.clone();
         let state = state.clone();
         let load_fn_ref = load_image_fn.clone();
         let prev_active = prev_active_thumb.clone();
+        let thumb_progress = thumb_progress.clone();
+        let thumb_generation = thumb_generation.clone();
+        let thumb_total = thumb_total.clone();
+        let thumb_done = thumb_done.clone();
+        let thumb_queue = thumb_queue.clone();
+        let request_visible_thumbs = request_visible_thumbs.clone();
+        let viewport = viewport.clone();
+        let toast_overlay = toast_overlay.clone();
+        let thumb_size = thumb_size.clone();
 
         move || {
             while let Some(child) = thumb_strip.first_child() {
                 thumb_strip.remove(&child);
             }
             thumb_buttons.borrow_mut().clear();
+            thumb_ratings.borrow_mut().clear();
+            // Decodes already running finish into their detached pictures.
+            {
+                let mut q = thumb_queue.borrow_mut();
+                q.deferred.clear();
+                q.pending.clear();
+            }
 
             let files = state.borrow().files.clone();
             let current_index = state.borrow().current_index;
+            let size = thumb_size.get();
+            let edge = size.pixels();
+
+            let generation = thumb_generation.get().wrapping_add(1);
+            thumb_generation.set(generation);
+            // Counts grow as thumbnails are requested by scrolling.
+            thumb_total.set(0);
+            thumb_done.set(0);
+
+            if files.len() >= THUMB_PROGRESS_MIN_FILES {
+                thumb_progress.set_text(Some("Loading thumbnails…"));
+                thumb_progress.pulse();
+                thumb_progress.set_visible(true);
+
+                let thumb_progress = thumb_progress.clone();
+                let thumb_generation = thumb_generation.clone();
+                let thumb_done = thumb_done.clone();
+                glib::timeout_add_local(std::time::Duration::from_millis(120), move || {
+                    if thumb_generation.get() != generation || !thumb_progress.is_visible() {
+                        return glib::ControlFlow::Break;
+                    }
+                    if thumb_done.get() > 0 {
+                        // Determinate from here on; completions drive the fraction.
+                        return glib::ControlFlow::Break;
+                    }
+                    thumb_progress.pulse();
+                    glib::ControlFlow::Continue
+                });
+            } else {
+                thumb_progress.set_visible(false);
+            }
 
             for (i, path) in files.iter().enumerate() {
                 let thumb_spinner = gtk4::Spinner::new();
-                thumb_spinner.set_size_request(90, 90);
+                thumb_spinner.set_size_request(edge, edge);
                 thumb_spinner.start();
 
                 let thumb_stack = gtk4::Stack::new();
-                thumb_stack.set_size_request(90, 90);
+                thumb_stack.set_size_request(edge, edge);
                 thumb_stack.set_transition_type(gtk4::StackTransitionType::Crossfade);
-                thumb_stack.set_transition_duration(200);
+                thumb_stack.set_transition_duration(motion_duration(200));
                 thumb_stack.add_named(&thumb_spinner, Some("loading"));
 
                 let thumb_pic = gtk4::Picture::builder()
                     .can_shrink(true)
                     .content_fit(gtk4::ContentFit::Cover)
-                    .width_request(90)
-                    .height_request(90)
+                    .width_request(edge)
+                    .height_request(edge)
                     .build();
                 thumb_stack.add_named(&thumb_pic, Some("image"));
                 thumb_stack.set_visible_child_name("loading");
 
+                let rating_label = gtk4::Label::builder()
+                    .halign(gtk4::Align::End)
+                    .valign(gtk4::Align::End)
+                    .css_classes(["thumb-rating"])
+                    .build();
+                show_rating(&rating_label, state.borrow().ratings.get(path));
+                let thumb_cell = gtk4::Overlay::new();
+                thumb_cell.set_child(Some(&thumb_stack));
+                thumb_cell.add_overlay(&rating_label);
+
                 let btn = gtk4::Button::builder()
-                    .child(&thumb_stack)
+                    .child(&thumb_cell)
                     .css_classes(["flat", "thumb-btn"])
                     .focusable(false)
                     .can_focus(false)
@@ -618,7 +1938,36 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
                 let state_click = state.clone();
                 let load_fn_click = load_fn_ref.clone();
                 let path_click = path.clone();
-                btn.connect_clicked(move |_| {
+                let viewport_click = viewport.clone();
+                let toast_click = toast_overlay.clone();
+                btn.connect_clicked(move |btn| {
+                    // Ctrl+click picks an image to compare against side by
+                    // side, Alt+click one to toggle to with Tab.
+                    let modifiers = btn
+                        .display()
+                        .default_seat()
+                        .and_then(|seat| seat.keyboard())
+                        .map(|kb| kb.modifier_state())
+                        .unwrap_or_else(gtk4::gdk::ModifierType::empty);
+                    let layout = if modifiers.contains(gtk4::gdk::ModifierType::CONTROL_MASK) {
+                        Some(CompareLayout::SideBySide)
+                    } else if modifiers.contains(gtk4::gdk::ModifierType::ALT_MASK) {
+                        Some(CompareLayout::Toggle)
+                    } else {
+                        None
+                    };
+                    if let Some(layout) = layout {
+                        let name = path_click.file_name().unwrap_or_default().to_string_lossy();
+                        let msg = if !viewport_click.set_compare(Some(path_click.clone()), layout) {
+                            "Comparing needs the GPU renderer and single-image view".to_string()
+                        } else if layout == CompareLayout::Toggle {
+                            format!("Hold Tab to see {name} — Esc to stop")
+                        } else {
+                            format!("Comparing with {name} — Esc to stop")
+                        };
+                        toast_click.add_toast(adw::Toast::new(&msg));
+                        return;
+                    }
                     {
                         let mut s = state_click.borrow_mut();
                         s.current_index = i;
@@ -631,60 +1980,182 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
 
                 thumb_strip.append(&btn);
                 thumb_buttons.borrow_mut().push(btn);
+                thumb_ratings.borrow_mut().push(rating_label);
 
-                let path_async = path.clone();
-                let thumb_pic_async = thumb_pic.clone();
-                let thumb_stack_async = thumb_stack.clone();
+                thumb_queue.borrow_mut().deferred.insert(
+                    i,
+                    ThumbJob {
+                        index: i,
+                        path: path.clone(),
+                        picture: thumb_pic,
+                        stack: thumb_stack,
+                        flavor: size.flavor(),
+                        generation,
+                    },
+                );
+            }
+            request_visible_thumbs();
 
-                glib::spawn_future_local(async move {
-                    let (tx, rx) = futures::channel::oneshot::channel();
-                    rayon::spawn({
-                        let path = path_async.clone();
-                        move || {
-                            let result = load_or_generate_thumb(&path);
-                            let _ = tx.send(result);
-                        }
-                    });
+            // Sync the O(1) tracker with the freshly created buttons
+            prev_active.set(Some(current_index));
+        }
+    });
 
-                    if let Ok(Some(bytes)) = rx.await {
-                        let glib_bytes = glib::Bytes::from_owned(bytes);
-                        let texture = gtk4::gdk::MemoryTexture::new(
-                            128,
-                            128,
-                            gtk4::gdk::MemoryFormat::R8g8b8a8,
-                            &glib_bytes,
-                            (128 * 4) as usize,
-                        );
-                        thumb_pic_async.set_paintable(Some(&texture));
+    // Paths of the gallery tiles, by position in the grid. The folder may
+    // change under an open gallery, so clicks look the path up again.
+    let gallery_paths: Rc<RefCell<Vec<PathBuf>>> = Rc::default();
+    let gallery_queue: Rc<RefCell<ThumbQueue>> = Rc::default();
+    // The gallery has no progress bar; completions only free a slot.
+    let on_gallery_done: Rc<dyn Fn(u64)> = Rc::new(|_| {});
+
+    let clear_gallery: Rc<dyn Fn()> = Rc::new({
+        let gallery_grid = gallery_grid.clone();
+        let gallery_paths = gallery_paths.clone();
+        let gallery_queue = gallery_queue.clone();
+        move || {
+            while let Some(child) = gallery_grid.first_child() {
+                gallery_grid.remove(&child);
+            }
+            gallery_paths.borrow_mut().clear();
+            // Decodes already running finish into their detached pictures.
+            let mut q = gallery_queue.borrow_mut();
+            q.deferred.clear();
+            q.pending.clear();
+        }
+    });
+
+    // Tiles are rebuilt each time the gallery opens, so it always shows the
+    // current folder, sort and filter. Decodes start nearest the current
+    // image, through the same cache as the strip.
+    let populate_gallery: Rc<dyn Fn()> = Rc::new({
+        let gallery_grid = gallery_grid.clone();
+        let gallery_paths = gallery_paths.clone();
+        let gallery_queue = gallery_queue.clone();
+        let clear_gallery = clear_gallery.clone();
+        let state = state.clone();
+        move || {
+            clear_gallery();
+            let files = state.borrow().files.clone();
+            let current_index = state.borrow().current_index;
+
+            for (i, path) in files.iter().enumerate() {
+                let spinner = gtk4::Spinner::new();
+                spinner.set_size_request(GALLERY_TILE_PX, GALLERY_TILE_PX);
+                spinner.start();
+
+                let stack = gtk4::Stack::new();
+                stack.set_size_request(GALLERY_TILE_PX, GALLERY_TILE_PX);
+                stack.set_transition_type(gtk4::StackTransitionType::Crossfade);
+                stack.set_transition_duration(motion_duration(200));
+                stack.add_named(&spinner, Some("loading"));
+
+                let picture = gtk4::Picture::builder()
+                    .can_shrink(true)
+                    .content_fit(gtk4::ContentFit::Contain)
+                    .width_request(GALLERY_TILE_PX)
+                    .height_request(GALLERY_TILE_PX)
+                    .build();
+                stack.add_named(&picture, Some("image"));
+                stack.set_visible_child_name("loading");
+
+                let name = gtk4::Label::builder()
+                    .label(
+                        path.file_name()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .as_ref(),
+                    )
+                    .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+                    .max_width_chars(16)
+                    .css_classes(["gallery-name"])
+                    .build();
+
+                let tile = gtk4::Box::new(Orientation::Vertical, 4);
+                tile.add_css_class("gallery-tile");
+                tile.set_tooltip_text(path.to_str());
+                tile.append(&stack);
+                tile.append(&name);
+                gallery_grid.append(&tile);
+                if i == current_index {
+                    if let Some(child) = gallery_grid.child_at_index(i as i32) {
+                        child.add_css_class("thumb-active");
                     }
-                    thumb_stack_async.set_visible_child_name("image");
+                }
+
+                gallery_queue.borrow_mut().pending.push(ThumbJob {
+                    index: i,
+                    path: path.clone(),
+                    picture,
+                    stack,
+                    flavor: thumbcache::Flavor::Normal,
+                    generation: 0,
                 });
             }
+            *gallery_paths.borrow_mut() = files;
+            pump_thumb_queue(&gallery_queue, &state, &on_gallery_done);
 
-            // Sync the O(1) tracker with the freshly created buttons
-            prev_active.set(Some(current_index));
+            // Bring the current image's tile into view once laid out.
+            if let Some(child) = gallery_grid.child_at_index(current_index as i32) {
+                glib::idle_add_local_once(move || {
+                    child.grab_focus();
+                });
+            }
         }
     });
 
     // ── Core load_image closure ───────────────────────────────────────────
+    let auto_orient = cfg.auto_orient;
     let load_image: Rc<dyn Fn(PathBuf)> = Rc::new({
         let counter_label = counter_label.clone();
         let state = state.clone();
         let info_name = info_name.clone();
-        let info_dims = info_dims.clone();
+        let show_dimensions = show_dimensions.clone();
         let info_size = info_size.clone();
+        let info_modified = info_modified.clone();
+        let info_created = info_created.clone();
+        let row_created = row_created.clone();
+        let info_profile = info_profile.clone();
         let info_path_lbl = info_path_lbl.clone();
+        let row_depth = row_depth.clone();
+        let info_depth = info_depth.clone();
+        let info_quality = info_quality.clone();
+        let raw_preview_badge = raw_preview_badge.clone();
+        let page_row = page_row.clone();
+        let page_spin = page_spin.clone();
+        let page_total = page_total.clone();
+        let current_page = current_page.clone();
+        let camera_fields = camera_fields.clone();
+        let location_row = location_row.clone();
+        let location_link = location_link.clone();
+        let histogram = histogram.clone();
+        let histogram_area = histogram_area.clone();
         let thumb_buttons = thumb_buttons.clone();
+        let rating_overlay = rating_overlay.clone();
         let viewport_stack = viewport_stack.clone();
         let viewport_engine = viewport.clone();
+        let status_image = status_image.clone();
+        let row_range = row_range.clone();
+        let row_format = row_format.clone();
+        let row_alpha = row_alpha.clone();
+        let current_stats = current_stats.clone();
+        let sync_zoom_entry = sync_zoom_entry.clone();
         let scroll_fn = scroll_to_active_thumb.clone();
         let prev_active = prev_active_thumb.clone();
+        let exposure_scale = exposure_scale.clone();
+        let gamma_scale = gamma_scale.clone();
+        let brightness_scale = brightness_scale.clone();
+        let contrast_scale = contrast_scale.clone();
+        let grayscale_check = grayscale_check.clone();
+        let invert_check = invert_check.clone();
+        let straighten_scale = straighten_scale.clone();
+        let adjust_syncing = adjust_syncing.clone();
 
         move |path: PathBuf| {
             // ── 1. Save view state of the image we're leaving ─────────────
             {
                 let current = state.borrow().current_path();
-                if let Some(ref current_path) = current {
+                let remember = state.borrow().remember_view;
+                if let (true, Some(current_path)) = (remember, &current) {
                     let (zoom, px, py) = viewport_engine.get_view_state();
                     if zoom != 1.0 || px != 0.0 || py != 0.0 {
                         state.borrow_mut().view_states.insert(
@@ -702,11 +2173,17 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
             // ── 2. Get rotation from cache (zero I/O) ────────────────────
             let cached_rotation = state.borrow().rotations.get(&path).copied();
             let rotation = cached_rotation.unwrap_or(0);
+            let (flip_h, flip_v) = state.borrow().flips.get(&path).copied().unwrap_or_default();
 
             // ── 3. Gather navigation state ────────────────────────────────
-            let (idx, total, adjacent) = {
+            let (idx, total, adjacent, direction) = {
                 let s = state.borrow();
-                (s.current_index, s.files.len(), s.adjacent_paths())
+                (
+                    s.current_index,
+                    s.files.len(),
+                    s.adjacent_paths(),
+                    s.last_nav_direction,
+                )
             };
 
             // ── 4. Restore or reset camera for the target image ───────────
@@ -717,6 +2194,19 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
                 } else {
                     viewport_engine.prepare_view(1.0, 0.0, 0.0);
                 }
+                let adjustments = s.adjustments.get(&path).copied().unwrap_or_default();
+                viewport_engine.prepare_adjustments(adjustments);
+                adjust_syncing.set(true);
+                exposure_scale.set_value(adjustments.exposure as f64);
+                gamma_scale.set_value(adjustments.gamma as f64);
+                brightness_scale.set_value((adjustments.brightness * 100.0) as f64);
+                contrast_scale.set_value((adjustments.contrast * 100.0) as f64);
+                grayscale_check.set_active(adjustments.grayscale);
+                invert_check.set_active(adjustments.invert);
+                let straighten = s.straighten.get(&path).copied().unwrap_or(0.0);
+                viewport_engine.prepare_straighten(straighten);
+                straighten_scale.set_value(straighten as f64);
+                adjust_syncing.set(false);
             }
 
             // ── 5. Update header / info labels (cheap string ops) ─────────
@@ -728,6 +2218,7 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
             counter_label.set_label(&format!("{} — {}/{}", name, idx + 1, total));
             info_name.set_label(&name);
             info_path_lbl.set_label(path.to_str().unwrap_or(""));
+            show_rating(&rating_overlay, state.borrow().ratings.get(&path));
 
             // ── 6. O(1) thumbnail active-state update ─────────────────────
             {
@@ -746,74 +2237,352 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
             // ── 7. Scroll thumbnail strip ─────────────────────────────────
             scroll_fn();
 
-            // ── 8. Apply rotation and show viewport ───────────────────────
-            viewport_engine.set_rotation(rotation as f32);
+            // ── 8. Show viewport ──────────────────────────────────────────
             viewport_stack.set_visible_child_name("image");
+            if viewport_engine.is_strip_mode() {
+                // Every image is already laid out in the strip; just scroll.
+                viewport_engine.strip_scroll_to(idx);
+            } else {
+                // ── 9. Trigger image load with its rotation (async) ───────
+                let show_dimensions = show_dimensions.clone();
+                let sync_zoom_entry = sync_zoom_entry.clone();
+                status_image.set_label("");
+                row_range.set_visible(false);
+                row_format.set_visible(false);
+                row_alpha.set_visible(false);
+                current_stats.borrow_mut().take();
+                let transform = ImageTransform {
+                    rotation_degrees: rotation,
+                    flip_h,
+                    flip_v,
+                };
+                viewport_engine.prepare_transition(direction);
+                let state = state.clone();
+                viewport_engine.load_image(path.clone(), transform, move |w, h| {
+                    state.borrow_mut().failed_in_a_row = 0;
+                    show_dimensions(w, h);
+                    sync_zoom_entry();
+                });
 
-            // ── 9. Trigger image load (async internally) ──────────────────
-            let info_dims_cb = info_dims.clone();
-            viewport_engine.load_image(path.clone(), move |w, h| {
-                info_dims_cb.set_label(&format!("{}×{} px", w, h));
-            });
+                // ── 10. Directional prefetch ──────────────────────────────
+                viewport_engine.prefetch_neighbors(adjacent);
+            }
 
-            // ── 10. Directional prefetch ──────────────────────────────────
-            for adj_path in adjacent {
-                viewport_engine.prefetch(adj_path);
+            // ── 10b. Page count (multi-page TIFF) ─────────────────────────
+            current_page.set(0);
+            page_row.set_visible(false);
+            if !viewport_engine.is_strip_mode() {
+                let path_pages = path.clone();
+                let path_current = path.clone();
+                let state_pages = state.clone();
+                let page_row = page_row.clone();
+                let page_spin = page_spin.clone();
+                let page_total = page_total.clone();
+                let (tx, rx) = futures::channel::oneshot::channel();
+                rayon::spawn(move || {
+                    let _ = tx.send(pages::page_count(&path_pages));
+                });
+                glib::spawn_future_local(async move {
+                    let Ok(count) = rx.await else { return };
+                    if count < 2 || state_pages.borrow().current_path() != Some(path_current) {
+                        return;
+                    }
+                    // Page 0 is already showing, so this doesn't reload it.
+                    // Reset before narrowing so the range can't clamp the
+                    // value onto another page.
+                    page_spin.set_value(1.0);
+                    page_spin.set_range(1.0, count as f64);
+                    page_total.set_label(&format!("of {count}"));
+                    page_row.set_visible(true);
+                });
             }
 
-            // ── 11. Async EXIF rotation (only if not already cached) ──────
-            if cached_rotation.is_none() {
+            // ── 11. Async EXIF orientation (only if not already cached) ───
+            if cached_rotation.is_none() && auto_orient {
                 let path_exif = path.clone();
                 let state_exif = state.clone();
                 let viewport_exif = viewport_engine.clone();
                 let (tx, rx) = futures::channel::oneshot::channel();
                 rayon::spawn(move || {
-                    let rot = read_exif_rotation(&path_exif);
-                    let _ = tx.send((path_exif, rot));
+                    let transform = orientation::read_display_transform(&path_exif);
+                    let _ = tx.send((path_exif, transform));
                 });
                 glib::spawn_future_local({
                     let state_exif = state_exif.clone();
                     async move {
-                        if let Ok((p, rot)) = rx.await {
+                        if let Ok((p, transform)) = rx.await {
                             let is_current = {
                                 let mut s = state_exif.borrow_mut();
-                                s.rotations.insert(p.clone(), rot);
+                                s.rotations.insert(p.clone(), transform.rotation_degrees);
+                                s.flips
+                                    .insert(p.clone(), (transform.flip_h, transform.flip_v));
                                 s.current_path().as_deref() == Some(p.as_path())
                             };
-                            if is_current && rot != 0 {
-                                viewport_exif.set_rotation(rot as f32);
+                            if is_current && transform != ImageTransform::default() {
+                                viewport_exif.set_transform(transform);
                             }
                         }
                     }
                 });
             }
 
-            // ── 12. Async file-size metadata ──────────────────────────────
+            // ── 12. Async file-size and date metadata ─────────────────────
             {
                 info_size.set_label("…");
+                info_modified.set_label("…");
+                row_created.set_visible(false);
                 let path_meta = path.clone();
                 let info_size_cb = info_size.clone();
+                let info_modified = info_modified.clone();
+                let info_created = info_created.clone();
+                let row_created = row_created.clone();
                 let (tx, rx) = futures::channel::oneshot::channel();
                 rayon::spawn(move || {
-                    let size = std::fs::metadata(&path_meta).ok().map(|m| m.len());
-                    let _ = tx.send(size);
+                    // Creation time isn't recorded by every file system.
+                    let meta = std::fs::metadata(&path_meta)
+                        .ok()
+                        .map(|m| (m.len(), m.modified().ok(), m.created().ok()));
+                    let _ = tx.send(meta);
                 });
                 glib::spawn_future_local(async move {
-                    if let Ok(Some(bytes)) = rx.await {
+                    if let Ok(Some((bytes, modified, created))) = rx.await {
                         let size_str = if bytes > 1_048_576 {
                             format!("{:.1} MB", bytes as f64 / 1_048_576.0)
                         } else {
                             format!("{:.0} KB", bytes as f64 / 1024.0)
                         };
                         info_size_cb.set_label(&size_str);
+                        let modified = modified.and_then(format_file_time);
+                        info_modified.set_label(modified.as_deref().unwrap_or("—"));
+                        if let Some(created) = created.and_then(format_file_time) {
+                            info_created.set_label(&created);
+                            row_created.set_visible(true);
+                        }
+                    }
+                });
+            }
+
+            // ── 12b. Async color profile ──────────────────────────────────
+            {
+                info_profile.set_label("…");
+                let path_icc = path.clone();
+                let path_current = path.clone();
+                let state_icc = state.clone();
+                let info_profile = info_profile.clone();
+                let (tx, rx) = futures::channel::oneshot::channel();
+                rayon::spawn(move || {
+                    // Pixels are converted from this profile to sRGB on decode.
+                    let name = color::extract_icc_profile(&path_icc)
+                        .map(|icc| color::profile_description(&icc));
+                    let _ = tx.send(name);
+                });
+                glib::spawn_future_local(async move {
+                    let Ok(name) = rx.await else { return };
+                    if state_icc.borrow().current_path() != Some(path_current) {
+                        return;
+                    }
+                    info_profile.set_label(match &name {
+                        Some(Some(name)) => name,
+                        Some(None) => "Embedded (unnamed)",
+                        None => "sRGB (untagged)",
+                    });
+                });
+            }
+
+            // ── 13. Async camera EXIF ─────────────────────────────────────
+            {
+                for (row, _) in camera_fields.iter() {
+                    row.set_visible(false);
+                }
+                location_row.set_visible(false);
+                raw_preview_badge.set_visible(false);
+                row_depth.set_visible(false);
+                info_quality.set_label("—");
+                let path_info = path.clone();
+                let path_current = path.clone();
+                let state_info = state.clone();
+                let camera_fields = camera_fields.clone();
+                let location_row = location_row.clone();
+                let location_link = location_link.clone();
+                let raw_preview_badge = raw_preview_badge.clone();
+                let row_depth = row_depth.clone();
+                let info_depth = info_depth.clone();
+                let info_quality = info_quality.clone();
+                let (tx, rx) = futures::channel::oneshot::channel();
+                rayon::spawn(move || {
+                    let raw_preview =
+                        raw::is_raw(&path_info) && raw::has_display_preview(&path_info);
+                    let bit_depth = if heif::is_heif(&path_info) {
+                        heif::bit_depth(&path_info)
+                    } else {
+                        jxl::bit_depth(&path_info)
+                    };
+                    let quality = quality::estimate(&path_info);
+                    let _ = tx.send((
+                        CameraInfo::read(&path_info),
+                        raw_preview,
+                        bit_depth,
+                        quality,
+                    ));
+                });
+                glib::spawn_future_local(async move {
+                    let Ok((info, raw_preview, bit_depth, quality)) = rx.await else {
+                        return;
+                    };
+                    if state_info.borrow().current_path() != Some(path_current) {
+                        return;
+                    }
+                    for ((row, label), (_, value)) in camera_fields.iter().zip(info.rows()) {
+                        if let Some(value) = value {
+                            label.set_label(value);
+                        }
+                        row.set_visible(value.is_some());
+                    }
+                    if let Some(location) = info.location {
+                        location_link.set_label(&location.display());
+                        location_link.set_uri(&location.map_url());
                     }
+                    location_row.set_visible(info.location.is_some());
+                    raw_preview_badge.set_visible(raw_preview);
+                    if let Some(bits) = bit_depth {
+                        info_depth.set_label(&format!("{bits}-bit"));
+                    }
+                    row_depth.set_visible(bit_depth.is_some());
+                    info_quality.set_label(&quality::EncodingQuality::display(quality));
                 });
             }
+
+            // ── 14. Histogram (only while shown) ──────────────────────────
+            if histogram_area.is_visible() {
+                spawn_histogram(
+                    path,
+                    state.clone(),
+                    histogram.clone(),
+                    histogram_area.clone(),
+                );
+            }
         }
     });
 
     *load_image_fn.borrow_mut() = Some(load_image.clone());
 
+    gallery_btn.connect_toggled({
+        let main_stack = main_stack.clone();
+        let populate_gallery = populate_gallery.clone();
+        let clear_gallery = clear_gallery.clone();
+        move |btn| {
+            if btn.is_active() {
+                populate_gallery();
+                main_stack.set_visible_child_name("gallery");
+            } else {
+                main_stack.set_visible_child_name("viewer");
+                // Tiles hold a texture each; a large folder shouldn't keep
+                // them around while the viewer is up.
+                clear_gallery();
+            }
+        }
+    });
+    gallery_grid.connect_child_activated({
+        let gallery_btn = gallery_btn.clone();
+        let gallery_paths = gallery_paths.clone();
+        let state = state.clone();
+        let load_image = load_image.clone();
+        move |_, child| {
+            let Some(path) = gallery_paths.borrow().get(child.index() as usize).cloned() else {
+                return;
+            };
+            let Some(index) = state.borrow().files.iter().position(|p| *p == path) else {
+                return;
+            };
+            {
+                let mut s = state.borrow_mut();
+                s.current_index = index;
+                s.last_nav_direction = 0;
+            }
+            gallery_btn.set_active(false);
+            load_image(path);
+        }
+    });
+
+    viewport.connect_load_error({
+        let state = state.clone();
+        let load_image = load_image.clone();
+        let viewport_stack = viewport_stack.clone();
+        let info_dims = info_dims.clone();
+        let info_megapixels = info_megapixels.clone();
+        let info_aspect = info_aspect.clone();
+        let status_image = status_image.clone();
+        let current_stats = current_stats.clone();
+        move |path, reason| {
+            // Browsing glides past broken files; opening one shows why.
+            let skip_to = state.borrow_mut().skip_unreadable(path);
+            if let Some(next) = skip_to {
+                load_image(next);
+                return;
+            }
+            info_dims.set_label("—");
+            info_megapixels.set_label("—");
+            info_aspect.set_label("—");
+            status_image.set_label("");
+            current_stats.borrow_mut().take();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            error_name.set_label(&format!("Can't open “{name}”"));
+            error_reason.set_label(reason);
+            viewport_stack.set_visible_child_name("error");
+        }
+    });
+
+    page_spin.connect_value_changed({
+        let state = state.clone();
+        let viewport = viewport.clone();
+        let show_dimensions = show_dimensions.clone();
+        let current_page = current_page.clone();
+        let sync_zoom_entry = sync_zoom_entry.clone();
+        move |spin| {
+            let page = (spin.value() as usize).saturating_sub(1);
+            if page == current_page.get() {
+                return;
+            }
+            let Some(path) = state.borrow().current_path() else {
+                return;
+            };
+            current_page.set(page);
+            let transform = {
+                let s = state.borrow();
+                let (flip_h, flip_v) = s.flips.get(&path).copied().unwrap_or_default();
+                ImageTransform {
+                    rotation_degrees: s.rotations.get(&path).copied().unwrap_or(0),
+                    flip_h,
+                    flip_v,
+                }
+            };
+            let show_dimensions = show_dimensions.clone();
+            let sync_zoom_entry = sync_zoom_entry.clone();
+            viewport.load_page(path, page, transform, move |w, h| {
+                show_dimensions(w, h);
+                sync_zoom_entry();
+            });
+        }
+    });
+
+    // Page keys step through a multi-page file before moving to the next.
+    let step_page: Rc<dyn Fn(i32) -> bool> = Rc::new({
+        let page_row = page_row.clone();
+        let page_spin = page_spin.clone();
+        move |delta| {
+            if !page_row.is_visible() {
+                return false;
+            }
+            let (min, max) = page_spin.range();
+            let target = page_spin.value() + delta as f64;
+            if target < min || target > max {
+                return false;
+            }
+            page_spin.set_value(target);
+            true
+        }
+    });
+
     // ── Navigation coalescing scheduler ───────────────────────────────────
     // Accumulates rapid key-repeat events and processes them as a single
     // jump once the GTK main loop drains its event queue.
@@ -840,17 +2609,14 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
                 if delta == 0 {
                     return;
                 }
+                // At either end without wrapping, this does nothing.
                 let path = {
                     let mut s = st.borrow_mut();
-                    let len = s.files.len();
-                    if len == 0 {
-                        return;
+                    let path = s.step(delta as i64);
+                    if path.is_some() {
+                        s.last_nav_direction = delta.signum();
                     }
-                    let new_idx =
-                        (s.current_index as i64 + delta as i64).rem_euclid(len as i64) as usize;
-                    s.current_index = new_idx;
-                    s.last_nav_direction = if delta > 0 { 1 } else { -1 };
-                    s.current_path()
+                    path
                 };
                 if let Some(p) = path {
                     lk(p);
@@ -859,16 +2625,124 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
         }
     });
 
-    let _watcher = start_directory_watcher(
+    if cfg.advance_on_scroll_end {
+        let nav_pending = nav_pending.clone();
+        let schedule_nav = schedule_nav.clone();
+        viewport.connect_scroll_end(move || {
+            nav_pending.set(nav_pending.get() + 1);
+            schedule_nav();
+        });
+    }
+    viewport.set_scroll_navigates(cfg.scroll_navigates);
+    viewport.connect_scroll_navigate({
+        let nav_pending = nav_pending.clone();
+        let schedule_nav = schedule_nav.clone();
+        move |steps| {
+            nav_pending.set(nav_pending.get() + steps);
+            schedule_nav();
+        }
+    });
+
+    start_directory_watcher(
         state.clone(),
         populate_thumbnails.clone(),
         load_image.clone(),
     );
 
+    // ── Recent files ──────────────────────────────────────────────────────
+    // Kept in the config, and mirrored to GTK's recent list so the desktop
+    // shell sees them too.
+    let recent_files = Rc::new(RefCell::new(cfg.recent_files.clone()));
+    let rebuild_recent_menu: Rc<dyn Fn()> = Rc::new({
+        let recent_files = recent_files.clone();
+        move || {
+            recent_menu.remove_all();
+            let entries = gtk4::gio::Menu::new();
+            for path in recent_files.borrow().iter() {
+                let label = Path::new(path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.clone());
+                let item = gtk4::gio::MenuItem::new(Some(&label), None);
+                item.set_action_and_target_value(Some("win.open-recent"), Some(&path.to_variant()));
+                entries.append_item(&item);
+            }
+            if recent_files.borrow().is_empty() {
+                // No action, so GTK shows it greyed out.
+                entries.append(Some("No Recent Files"), None);
+            }
+            recent_menu.append_section(None, &entries);
+            let clear = gtk4::gio::Menu::new();
+            clear.append(Some("Clear Recent"), Some("win.clear-recent"));
+            recent_menu.append_section(None, &clear);
+        }
+    });
+    rebuild_recent_menu();
+
+    let note_recent: Rc<dyn Fn(&Path)> = Rc::new({
+        let recent_files = recent_files.clone();
+        let rebuild_recent_menu = rebuild_recent_menu.clone();
+        move |path: &Path| {
+            config::remember_recent(&mut recent_files.borrow_mut(), &path.to_string_lossy());
+            let uri = gtk4::gio::File::for_path(path).uri();
+            gtk4::RecentManager::default().add_item(&uri);
+            rebuild_recent_menu();
+        }
+    });
+
+    let open_recent_action =
+        gtk4::gio::SimpleAction::new("open-recent", Some(glib::VariantTy::STRING));
+    open_recent_action.connect_activate({
+        let state = state.clone();
+        let populate_thumbnails = populate_thumbnails.clone();
+        let load_image = load_image.clone();
+        let recent_files = recent_files.clone();
+        let rebuild_recent_menu = rebuild_recent_menu.clone();
+        let note_recent = note_recent.clone();
+        let toast_overlay = toast_overlay.clone();
+        move |_, param| {
+            let Some(path) = param.and_then(|p| p.str()).map(PathBuf::from) else {
+                return;
+            };
+            if !path.is_file() {
+                let key = path.to_string_lossy();
+                recent_files.borrow_mut().retain(|p| *p != key);
+                rebuild_recent_menu();
+                toast_overlay.add_toast(adw::Toast::new("That file no longer exists"));
+                return;
+            }
+            note_recent(&path);
+            let loaded = state.borrow_mut().load_directory(&path);
+            populate_thumbnails();
+            match loaded {
+                Ok(()) => load_image(path),
+                Err(err) => report_unreadable_directory(&toast_overlay, &path, &err),
+            }
+        }
+    });
+    window.add_action(&open_recent_action);
+
+    let clear_recent_action = gtk4::gio::SimpleAction::new("clear-recent", None);
+    clear_recent_action.connect_activate({
+        let recent_files = recent_files.clone();
+        let rebuild_recent_menu = rebuild_recent_menu.clone();
+        move |_, _| {
+            let manager = gtk4::RecentManager::default();
+            for path in recent_files.borrow_mut().drain(..) {
+                let uri = gtk4::gio::File::for_path(&path).uri();
+                let _ = manager.remove_item(&uri);
+            }
+            rebuild_recent_menu();
+        }
+    });
+    window.add_action(&clear_recent_action);
+
     let window_ref = window.clone();
     let state_open = state.clone();
     let load_open = load_image.clone();
     let populate_open = populate_thumbnails.clone();
+    let note_recent_open = note_recent.clone();
+    let toast_open = toast_overlay.clone();
     open_btn.connect_clicked(move |_| {
         let dialog = FileDialog::builder()
             .title("Open Image")
@@ -877,26 +2751,297 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
         let state_clone = state_open.clone();
         let load = load_open.clone();
         let populate = populate_open.clone();
+        let note_recent = note_recent_open.clone();
+        let toast_overlay = toast_open.clone();
         dialog.open(
             Some(&window_ref),
             gtk4::gio::Cancellable::NONE,
             move |result| {
                 if let Ok(file) = result {
                     if let Some(path) = file.path() {
-                        state_clone.borrow_mut().load_directory(&path);
+                        note_recent(&path);
+                        let loaded = state_clone.borrow_mut().load_directory(&path);
                         populate();
-                        load(path);
+                        match loaded {
+                            Ok(()) => load(path),
+                            Err(err) => report_unreadable_directory(&toast_overlay, &path, &err),
+                        }
                     }
                 }
             },
         );
     });
 
-    let info_panel_btn = info_panel.clone();
-    let info_sep_btn = info_sep.clone();
-    let state_info = state.clone();
-    info_btn.connect_clicked(move |_| {
-        let mut s = state_info.borrow_mut();
+    // Browse a whole folder from its first image. Folders aren't added to
+    // the recent list, which reopens files alongside their siblings.
+    open_folder_btn.connect_clicked({
+        let window = window.clone();
+        let state = state.clone();
+        let load_image = load_image.clone();
+        let populate_thumbnails = populate_thumbnails.clone();
+        move |_| {
+            let dialog = FileDialog::builder()
+                .title("Open Folder")
+                .modal(true)
+                .build();
+            let state = state.clone();
+            let load_image = load_image.clone();
+            let populate_thumbnails = populate_thumbnails.clone();
+            dialog.select_folder(Some(&window), gtk4::gio::Cancellable::NONE, move |result| {
+                let Some(dir) = result.ok().and_then(|f| f.path()) else {
+                    return;
+                };
+                state.borrow_mut().load_from_directory(&dir);
+                populate_thumbnails();
+                let first = state.borrow().current_path();
+                if let Some(first) = first {
+                    load_image(first);
+                }
+            });
+        }
+    });
+
+    let on_adjust: Rc<dyn Fn()> = Rc::new({
+        let state = state.clone();
+        let viewport = viewport.clone();
+        let exposure_scale = exposure_scale.clone();
+        let gamma_scale = gamma_scale.clone();
+        let brightness_scale = brightness_scale.clone();
+        let contrast_scale = contrast_scale.clone();
+        let grayscale_check = grayscale_check.clone();
+        let invert_check = invert_check.clone();
+        let adjust_syncing = adjust_syncing.clone();
+        move || {
+            if adjust_syncing.get() {
+                return;
+            }
+            let adjustments = Adjustments {
+                brightness: (brightness_scale.value() / 100.0) as f32,
+                contrast: (contrast_scale.value() / 100.0) as f32,
+                grayscale: grayscale_check.is_active(),
+                invert: invert_check.is_active(),
+                exposure: exposure_scale.value() as f32,
+                gamma: gamma_scale.value() as f32,
+            };
+            {
+                let mut s = state.borrow_mut();
+                let Some(path) = s.current_path() else { return };
+                if adjustments == Adjustments::default() {
+                    s.adjustments.remove(&path);
+                } else {
+                    s.adjustments.insert(path, adjustments);
+                }
+            }
+            viewport.set_adjustments(adjustments);
+        }
+    });
+    for scale in [
+        &exposure_scale,
+        &gamma_scale,
+        &brightness_scale,
+        &contrast_scale,
+    ] {
+        let on_adjust = on_adjust.clone();
+        scale.connect_value_changed(move |_| on_adjust());
+    }
+    for check in [&grayscale_check, &invert_check] {
+        let on_adjust = on_adjust.clone();
+        check.connect_toggled(move |_| on_adjust());
+    }
+    straighten_scale.connect_value_changed({
+        let state = state.clone();
+        let viewport = viewport.clone();
+        let adjust_syncing = adjust_syncing.clone();
+        move |scale| {
+            if adjust_syncing.get() {
+                return;
+            }
+            let degrees = scale.value() as f32;
+            {
+                let mut s = state.borrow_mut();
+                let Some(path) = s.current_path() else { return };
+                if degrees == 0.0 {
+                    s.straighten.remove(&path);
+                } else {
+                    s.straighten.insert(path, degrees);
+                }
+            }
+            viewport.set_straighten(degrees);
+        }
+    });
+    adjust_reset_btn.connect_clicked({
+        let grayscale_check = grayscale_check.clone();
+        let invert_check = invert_check.clone();
+        let straighten_scale = straighten_scale.clone();
+        move |_| {
+            // Each reset fires `on_adjust`; the last one lands on neutral.
+            exposure_scale.set_value(0.0);
+            gamma_scale.set_value(1.0);
+            brightness_scale.set_value(0.0);
+            contrast_scale.set_value(0.0);
+            grayscale_check.set_active(false);
+            invert_check.set_active(false);
+            straighten_scale.set_value(0.0);
+        }
+    });
+
+    // The info panel's details, how the pixels are stored, then every EXIF
+    // tag, as text. EXIF is read off the main thread; `done` gets the path
+    // and the report.
+    let metadata_report: Rc<dyn Fn(Box<dyn FnOnce(PathBuf, String)>)> = Rc::new({
+        let state = state.clone();
+        let info_name = info_name.clone();
+        let info_dims = info_dims.clone();
+        let info_megapixels = info_megapixels.clone();
+        let info_aspect = info_aspect.clone();
+        let info_size = info_size.clone();
+        let info_modified = info_modified.clone();
+        let info_created = info_created.clone();
+        let row_created = row_created.clone();
+        let info_profile = info_profile.clone();
+        let info_quality = info_quality.clone();
+        let info_depth = info_depth.clone();
+        let row_depth = row_depth.clone();
+        let info_range = info_range.clone();
+        let row_range = row_range.clone();
+        let current_stats = current_stats.clone();
+        move |done| {
+            let Some(path) = state.borrow().current_path() else {
+                return;
+            };
+            let mut rows: Vec<(String, String)> = [
+                ("Filename", &info_name),
+                ("Dimensions", &info_dims),
+                ("Megapixels", &info_megapixels),
+                ("Aspect ratio", &info_aspect),
+                ("File size", &info_size),
+                ("File modified", &info_modified),
+                ("Quality", &info_quality),
+                ("Color profile", &info_profile),
+            ]
+            .into_iter()
+            .map(|(label, value)| (label.to_string(), value.label().to_string()))
+            .collect();
+            if row_created.is_visible() {
+                rows.push(("File created".into(), info_created.label().into()));
+            }
+            if row_depth.is_visible() {
+                rows.push(("Source bit depth".into(), info_depth.label().into()));
+            }
+            if row_range.is_visible() {
+                rows.push(("Dynamic range".into(), info_range.label().into()));
+            }
+            if let Some(stats) = current_stats.borrow().as_ref() {
+                rows.push(("Format".into(), stats.format.clone()));
+                let alpha = if stats.has_alpha { "Yes" } else { "No" };
+                rows.push(("Alpha".into(), alpha.into()));
+                rows.push(("Color space".into(), stats.color_space.clone()));
+            }
+            rows.push(("Path".into(), path.display().to_string()));
+
+            let path_exif = path.clone();
+            let (tx, rx) = futures::channel::oneshot::channel();
+            rayon::spawn(move || {
+                rows.extend(metadata::exif_fields(&path_exif));
+                let _ = tx.send(metadata::format_report(&rows));
+            });
+            glib::spawn_future_local(async move {
+                if let Ok(text) = rx.await {
+                    done(path, text);
+                }
+            });
+        }
+    });
+    copy_metadata_btn.connect_clicked({
+        let metadata_report = metadata_report.clone();
+        let window = window.clone();
+        let toast_overlay = toast_overlay.clone();
+        move |_| {
+            let window = window.clone();
+            let toast_overlay = toast_overlay.clone();
+            metadata_report(Box::new(move |_, text| {
+                window.clipboard().set_text(&text);
+                toast_overlay.add_toast(adw::Toast::new("Metadata copied"));
+            }));
+        }
+    });
+    save_metadata_btn.connect_clicked({
+        let window = window.clone();
+        let toast_overlay = toast_overlay.clone();
+        move |_| {
+            let window = window.clone();
+            let toast_overlay = toast_overlay.clone();
+            metadata_report(Box::new(move |path, text| {
+                // "photo.jpg.txt", so photo.jpg and photo.cr3 don't collide.
+                let mut target = path.into_os_string();
+                target.push(".txt");
+                let target = PathBuf::from(target);
+                let name = target
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned();
+                let exists = target.exists();
+                let save = {
+                    let name = name.clone();
+                    move || {
+                        let msg = match std::fs::write(&target, text) {
+                            Ok(()) => format!("Saved {name}"),
+                            Err(e) => format!("Could not save metadata: {e}"),
+                        };
+                        toast_overlay.add_toast(adw::Toast::new(&msg));
+                    }
+                };
+                if !exists {
+                    save();
+                    return;
+                }
+
+                // The file may be the user's own notes; don't clobber it
+                // without asking.
+                let dialog = adw::MessageDialog::new(
+                    Some(&window),
+                    Some("Replace File?"),
+                    Some(&format!("“{name}” already exists beside the image.")),
+                );
+                dialog.add_responses(&[("cancel", "_Cancel"), ("replace", "_Replace")]);
+                dialog.set_response_appearance("replace", adw::ResponseAppearance::Destructive);
+                dialog.set_default_response(Some("cancel"));
+                dialog.set_close_response("cancel");
+                let save = Cell::new(Some(save));
+                dialog.connect_response(None, move |_, response| {
+                    if let (true, Some(save)) = (response == "replace", save.take()) {
+                        save();
+                    }
+                });
+                dialog.present();
+            }));
+        }
+    });
+
+    let info_panel_btn = info_panel.clone();
+    let info_sep_btn = info_sep.clone();
+    histogram_btn.connect_toggled({
+        let state = state.clone();
+        let histogram = histogram.clone();
+        let histogram_area = histogram_area.clone();
+        move |btn| {
+            histogram_area.set_visible(btn.is_active());
+            let current = state.borrow().current_path();
+            if let (true, Some(path)) = (btn.is_active(), current) {
+                spawn_histogram(
+                    path,
+                    state.clone(),
+                    histogram.clone(),
+                    histogram_area.clone(),
+                );
+            }
+        }
+    });
+
+    let state_info = state.clone();
+    info_btn.connect_clicked(move |_| {
+        let mut s = state_info.borrow_mut();
         s.info_visible = !s.info_visible;
         info_panel_btn.set_visible(s.info_visible);
         info_sep_btn.set_visible(s.info_visible);
@@ -924,6 +3069,53 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
         viewport_rccw.set_rotation(rotation as f32);
     });
 
+    let state_fh = state.clone();
+    let viewport_fh = viewport.clone();
+    flip_h_btn.connect_clicked(move |_| {
+        let (h, v) = {
+            let mut s = state_fh.borrow_mut();
+            s.flip_horizontal();
+            s.current_flip()
+        };
+        viewport_fh.set_flip(h, v);
+    });
+
+    let state_fv = state.clone();
+    let viewport_fv = viewport.clone();
+    flip_v_btn.connect_clicked(move |_| {
+        let (h, v) = {
+            let mut s = state_fv.borrow_mut();
+            s.flip_vertical();
+            s.current_flip()
+        };
+        viewport_fv.set_flip(h, v);
+    });
+
+    // Back to the fitted, unrotated, unadjusted image in one step.
+    let reset_current_view: Rc<dyn Fn()> = Rc::new({
+        let state = state.clone();
+        let viewport = viewport.clone();
+        let adjust_reset_btn = adjust_reset_btn.clone();
+        move || {
+            let Some(path) = state.borrow().current_path() else {
+                return;
+            };
+            let orientation = if auto_orient {
+                orientation::read_display_transform(&path)
+            } else {
+                ImageTransform::default()
+            };
+            state.borrow_mut().reset_current(orientation);
+            viewport.set_transform(orientation);
+            viewport.reset_view();
+            adjust_reset_btn.emit_clicked();
+        }
+    });
+    reset_view_btn.connect_clicked({
+        let reset_current_view = reset_current_view.clone();
+        move |_| reset_current_view()
+    });
+
     let viewport_enh = viewport.clone();
     enhance_btn.connect_toggled(move |_| {
         viewport_enh.toggle_enhance();
@@ -946,21 +3138,37 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
     let state_drop = state.clone();
     let load_drop = load_image.clone();
     let populate_drop = populate_thumbnails.clone();
+    let note_recent_drop = note_recent.clone();
+    let toast_drop = toast_overlay.clone();
     drop_target.connect_drop(move |_, value, _, _| {
         let Ok(file_list) = value.get::<gtk4::gdk::FileList>() else {
             return false;
         };
-        let files = file_list.files();
-        let Some(file) = files.first() else {
-            return false;
-        };
-        let Some(path) = file.path() else {
+        let paths: Vec<PathBuf> = file_list.files().iter().filter_map(|f| f.path()).collect();
+        if paths.len() > 1 {
+            // Several files dropped together become the working set.
+            state_drop.borrow_mut().load_files(&paths);
+            populate_drop();
+            let first = state_drop.borrow().current_path();
+            return match first {
+                Some(first) => {
+                    load_drop(first);
+                    true
+                }
+                None => false,
+            };
+        }
+        let Some(path) = paths.into_iter().next() else {
             return false;
         };
         if path.is_file() {
-            state_drop.borrow_mut().load_directory(&path);
+            note_recent_drop(&path);
+            let loaded = state_drop.borrow_mut().load_directory(&path);
             populate_drop();
-            load_drop(path);
+            match loaded {
+                Ok(()) => load_drop(path),
+                Err(err) => report_unreadable_directory(&toast_drop, &path, &err),
+            }
             true
         } else if path.is_dir() {
             state_drop.borrow_mut().load_from_directory(&path);
@@ -973,8 +3181,923 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
             false
         }
     });
+    window.add_css_class("drop-zone");
     window.add_controller(drop_target);
 
+    // ── Delete current image (trash, or permanently with Shift) ─────────
+    let delete_current: Rc<dyn Fn(bool)> = Rc::new({
+        let window = window.clone();
+        let state = state.clone();
+        let populate_thumbnails = populate_thumbnails.clone();
+        let load_image = load_image.clone();
+        let viewport_stack = viewport_stack.clone();
+        let toast_overlay = toast_overlay.clone();
+        move |permanent: bool| {
+            let Some(path) = state.borrow().current_path() else {
+                return;
+            };
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let (heading, body, action) = if permanent {
+                (
+                    "Delete Permanently?",
+                    format!("“{name}” will be deleted and cannot be recovered."),
+                    "_Delete",
+                )
+            } else {
+                (
+                    "Move to Trash?",
+                    format!("“{name}” will be moved to the trash."),
+                    "_Move to Trash",
+                )
+            };
+
+            let dialog = adw::MessageDialog::new(Some(&window), Some(heading), Some(&body));
+            dialog.add_responses(&[("cancel", "_Cancel"), ("delete", action)]);
+            dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+            dialog.set_default_response(Some("cancel"));
+            dialog.set_close_response("cancel");
+
+            let state = state.clone();
+            let populate_thumbnails = populate_thumbnails.clone();
+            let load_image = load_image.clone();
+            let viewport_stack = viewport_stack.clone();
+            let toast_overlay = toast_overlay.clone();
+            dialog.connect_response(None, move |_, response| {
+                if response != "delete" {
+                    return;
+                }
+                let result = if permanent {
+                    std::fs::remove_file(&path).map_err(|e| e.to_string())
+                } else {
+                    gtk4::gio::File::for_path(&path)
+                        .trash(None::<&gtk4::gio::Cancellable>)
+                        .map_err(|e| e.to_string())
+                };
+                if let Err(e) = result {
+                    toast_overlay.add_toast(adw::Toast::new(&format!("Could not delete: {e}")));
+                    return;
+                }
+
+                // The file may have changed under us while the dialog was up.
+                if state.borrow().current_path().as_deref() != Some(path.as_path()) {
+                    return;
+                }
+                let next = state.borrow_mut().remove_current();
+                populate_thumbnails();
+                match next {
+                    Some(next) => load_image(next),
+                    None => viewport_stack.set_visible_child_name("welcome"),
+                }
+            });
+            dialog.present();
+        }
+    });
+
+    // ── Export ────────────────────────────────────────────────────────────
+    // Writes the current image, or a region of it, oriented as displayed.
+    // The file is re-decoded off the GTK thread; the GPU only holds a
+    // display copy.
+    let jpeg_quality = Rc::new(Cell::new(cfg.jpeg_quality.clamp(1, 100)));
+    let export_current: Rc<dyn Fn(Option<CropRect>)> = Rc::new({
+        let window = window.clone();
+        let state = state.clone();
+        let toast_overlay = toast_overlay.clone();
+        let jpeg_quality = jpeg_quality.clone();
+        let current_page = current_page.clone();
+        move |crop: Option<CropRect>| {
+            let Some(source) = state.borrow().current_path() else {
+                return;
+            };
+            // Multi-page TIFFs export the page on screen, which the crop
+            // rectangle was taken from.
+            let page = current_page.get();
+            let (transform, straighten) = {
+                let s = state.borrow();
+                let (flip_h, flip_v) = s.flips.get(&source).copied().unwrap_or_default();
+                let transform = ImageTransform {
+                    rotation_degrees: s.rotations.get(&source).copied().unwrap_or(0),
+                    flip_h,
+                    flip_v,
+                };
+                (transform, s.straighten.get(&source).copied().unwrap_or(0.0))
+            };
+            let stem = source
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "image".into());
+            // RAW files can't be written back, and exports of HDR files are
+            // tone mapped to 8 bits; default those to PNG.
+            let ext = source
+                .extension()
+                .and_then(|e| e.to_str())
+                .filter(|_| !raw::is_raw(&source) && !decode::is_hdr(&source))
+                .unwrap_or("png");
+            let (title, name) = match crop {
+                Some(_) => ("Export Selection", format!("{stem}-crop.{ext}")),
+                None => ("Save As", format!("{stem}-copy.{ext}")),
+            };
+            let dialog = FileDialog::builder()
+                .title(title)
+                .modal(true)
+                .initial_name(name.as_str())
+                .build();
+            if let Some(dir) = source.parent() {
+                dialog.set_initial_folder(Some(&gtk4::gio::File::for_path(dir)));
+            }
+
+            let quality_parent = window.clone();
+            let toast_overlay = toast_overlay.clone();
+            let jpeg_quality = jpeg_quality.clone();
+            dialog.save(Some(&window), gtk4::gio::Cancellable::NONE, move |result| {
+                let Some(dest) = result.ok().and_then(|f| f.path()) else {
+                    return;
+                };
+                let has_quality = export::has_quality(&dest);
+                let run = move |quality: u8| {
+                    let (tx, rx) = futures::channel::oneshot::channel();
+                    let dest_export = dest.clone();
+                    rayon::spawn(move || {
+                        let result = export::export(
+                            &source,
+                            page,
+                            crop,
+                            transform,
+                            straighten,
+                            &dest_export,
+                            quality,
+                        );
+                        let _ = tx.send(result);
+                    });
+                    glib::spawn_future_local(async move {
+                        let msg = match rx.await {
+                            Ok(Ok(())) => format!(
+                                "Saved {}",
+                                dest.file_name().unwrap_or_default().to_string_lossy()
+                            ),
+                            Ok(Err(e)) => e.to_toast_message(),
+                            Err(_) => return,
+                        };
+                        toast_overlay.add_toast(adw::Toast::new(&msg));
+                    });
+                };
+                if !has_quality {
+                    run(jpeg_quality.get());
+                    return;
+                }
+
+                let scale = gtk4::Scale::with_range(Orientation::Horizontal, 1.0, 100.0, 1.0);
+                scale.set_value(jpeg_quality.get() as f64);
+                scale.set_draw_value(true);
+                scale.set_value_pos(gtk4::PositionType::Right);
+                scale.set_width_request(260);
+                let dialog = adw::MessageDialog::new(
+                    Some(&quality_parent),
+                    Some("JPEG Quality"),
+                    Some("Higher quality gives larger files."),
+                );
+                dialog.set_extra_child(Some(&scale));
+                dialog.add_responses(&[("cancel", "_Cancel"), ("save", "_Save")]);
+                dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+                dialog.set_default_response(Some("save"));
+                dialog.set_close_response("cancel");
+                let jpeg_quality = jpeg_quality.clone();
+                let run = Cell::new(Some(run));
+                dialog.connect_response(None, move |_, response| {
+                    if let (true, Some(run)) = (response == "save", run.take()) {
+                        let quality = scale.value().round() as u8;
+                        jpeg_quality.set(quality);
+                        run(quality);
+                    }
+                });
+                dialog.present();
+            });
+        }
+    });
+
+    // ── Print ─────────────────────────────────────────────────────────────
+    // Like export, prints a full-resolution decode oriented as displayed,
+    // not the GPU's display copy.
+    let print_current: Rc<dyn Fn()> = Rc::new({
+        let window = window.clone();
+        let state = state.clone();
+        let toast_overlay = toast_overlay.clone();
+        let current_page = current_page.clone();
+        move || {
+            let Some(source) = state.borrow().current_path() else {
+                return;
+            };
+            let page = current_page.get();
+            let transform = {
+                let s = state.borrow();
+                let (flip_h, flip_v) = s.flips.get(&source).copied().unwrap_or_default();
+                ImageTransform {
+                    rotation_degrees: s.rotations.get(&source).copied().unwrap_or(0),
+                    flip_h,
+                    flip_v,
+                }
+            };
+            let job_name = source
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "Image".into());
+            let (tx, rx) = futures::channel::oneshot::channel();
+            rayon::spawn(move || {
+                let _ = tx.send(export::decode_oriented(&source, page, transform));
+            });
+            let window = window.clone();
+            let toast_overlay = toast_overlay.clone();
+            glib::spawn_future_local(async move {
+                let result = match rx.await {
+                    Ok(Ok(image)) => print::print_image(&window, &job_name, image)
+                        .map_err(|e| format!("Could not print: {e}")),
+                    Ok(Err(e)) => Err(e.to_toast_message()),
+                    Err(_) => return,
+                };
+                if let Err(msg) = result {
+                    toast_overlay.add_toast(adw::Toast::new(&msg));
+                }
+            });
+        }
+    });
+
+    // ── Save rotation ─────────────────────────────────────────────────────
+    // Writes the displayed orientation into the files themselves, so they
+    // open upright everywhere. With `only_edited`, files shown as their EXIF
+    // orientation says are left alone.
+    let save_rotations: Rc<dyn Fn(Vec<PathBuf>, bool)> = Rc::new({
+        let state = state.clone();
+        let viewport = viewport.clone();
+        let load_image = load_image.clone();
+        let toast_overlay = toast_overlay.clone();
+        let jpeg_quality = jpeg_quality.clone();
+        move |paths, only_edited| {
+            let jobs: Vec<(PathBuf, ImageTransform)> = {
+                let s = state.borrow();
+                paths
+                    .into_iter()
+                    .map(|path| {
+                        let (flip_h, flip_v) = s.flips.get(&path).copied().unwrap_or_default();
+                        let transform = ImageTransform {
+                            rotation_degrees: s.rotations.get(&path).copied().unwrap_or(0),
+                            flip_h,
+                            flip_v,
+                        };
+                        (path, transform)
+                    })
+                    .filter(|(_, t)| *t != ImageTransform::default())
+                    .collect()
+            };
+            let quality = jpeg_quality.get();
+            let (tx, rx) = futures::channel::oneshot::channel();
+            rayon::spawn(move || {
+                let results: Vec<_> = jobs
+                    .into_iter()
+                    .filter(|(path, transform)| {
+                        let exif = if auto_orient {
+                            orientation::read_display_transform(path)
+                        } else {
+                            ImageTransform::default()
+                        };
+                        !only_edited || *transform != exif
+                    })
+                    .map(|(path, transform)| {
+                        let result = export::bake_transform(&path, transform, quality);
+                        (path, result)
+                    })
+                    .collect();
+                let _ = tx.send(results);
+            });
+
+            let state = state.clone();
+            let viewport = viewport.clone();
+            let load_image = load_image.clone();
+            let toast_overlay = toast_overlay.clone();
+            glib::spawn_future_local(async move {
+                let Ok(results) = rx.await else { return };
+                let current = state.borrow().current_path();
+                let mut reload = false;
+                let mut saved = 0;
+                let mut lossless = 0;
+                let mut first_error = None;
+                for (path, result) in results {
+                    match result {
+                        Ok(baked) => {
+                            saved += 1;
+                            lossless += usize::from(baked == export::Baked::Lossless);
+                            let mut s = state.borrow_mut();
+                            s.rotations.insert(path.clone(), 0);
+                            s.flips.insert(path.clone(), (false, false));
+                            viewport.forget(&path);
+                            reload |= current.as_ref() == Some(&path);
+                        }
+                        Err(e) => {
+                            first_error.get_or_insert(e.to_toast_message());
+                        }
+                    }
+                }
+                if let Some(path) = current.filter(|_| reload) {
+                    load_image(path);
+                }
+                let msg = match (saved, first_error) {
+                    (0, None) => "No rotated images to save".to_string(),
+                    (_, Some(e)) if saved == 0 => e,
+                    (1, None) if lossless == 1 => "Rotation saved losslessly".to_string(),
+                    (1, None) => "Rotation saved".to_string(),
+                    (n, None) => format!("Saved the rotation of {n} images"),
+                    (n, Some(e)) => format!("Saved the rotation of {n} images; {e}"),
+                };
+                toast_overlay.add_toast(adw::Toast::new(&msg));
+            });
+        }
+    });
+
+    // ── Context menu ──────────────────────────────────────────────────────
+    let context_menu = gtk4::gio::Menu::new();
+    context_menu.append(Some("Open With…"), Some("win.open-with"));
+    context_menu.append(Some("Set as Wallpaper"), Some("win.set-wallpaper"));
+    context_menu.append(Some("Print…"), Some("win.print"));
+    let rotation_section = gtk4::gio::Menu::new();
+    rotation_section.append(Some("Save Rotation to File"), Some("win.save-rotation"));
+    rotation_section.append(
+        Some("Save All Changed Rotations"),
+        Some("win.save-all-rotations"),
+    );
+    context_menu.append_section(None, &rotation_section);
+    let view_section = gtk4::gio::Menu::new();
+    view_section.append(Some("Remember Zoom per Image"), Some("win.remember-view"));
+    view_section.append(
+        Some("Scroll Wheel Changes Image"),
+        Some("win.scroll-navigates"),
+    );
+    view_section.append(Some("Wrap Around at the Ends"), Some("win.wrap-navigation"));
+    view_section.append(
+        Some("Skip Files That Can't Be Opened"),
+        Some("win.skip-unreadable"),
+    );
+    view_section.append(Some("Smooth Image Edges"), Some("win.antialiasing"));
+    view_section.append(Some("Dither Smooth Gradients"), Some("win.dither"));
+    view_section.append(Some("Free Panning"), Some("win.free-pan"));
+    view_section.append(Some("Escape Closes the Window"), Some("win.escape-closes"));
+    let thumb_size_menu = gtk4::gio::Menu::new();
+    for (i, size) in ThumbSize::ALL.into_iter().enumerate() {
+        let item = gtk4::gio::MenuItem::new(Some(size.label()), None);
+        item.set_action_and_target_value(Some("win.thumb-size"), Some(&(i as u32).to_variant()));
+        thumb_size_menu.append_item(&item);
+    }
+    view_section.append_submenu(Some("Thumbnail Size"), &thumb_size_menu);
+    context_menu.append_section(None, &view_section);
+    let context_popover = gtk4::PopoverMenu::from_model(Some(&context_menu));
+    context_popover.set_parent(&viewport.widget);
+    context_popover.set_has_arrow(false);
+    context_popover.set_halign(gtk4::Align::Start);
+    let context_click = gtk4::GestureClick::new();
+    context_click.set_button(gtk4::gdk::BUTTON_SECONDARY);
+    context_click.connect_pressed({
+        let state = state.clone();
+        move |_, _, x, y| {
+            if state.borrow().current_path().is_none() {
+                return;
+            }
+            let at = gtk4::gdk::Rectangle::new(x as i32, y as i32, 1, 1);
+            context_popover.set_pointing_to(Some(&at));
+            context_popover.popup();
+        }
+    });
+    viewport.widget.add_controller(context_click);
+
+    let open_with_action = gtk4::gio::SimpleAction::new("open-with", None);
+    open_with_action.connect_activate({
+        let window = window.clone();
+        let state = state.clone();
+        move |_, _| {
+            let Some(path) = state.borrow().current_path() else {
+                return;
+            };
+            let launcher = gtk4::FileLauncher::new(Some(&gtk4::gio::File::for_path(&path)));
+            launcher.set_always_ask(true);
+            launcher.launch(Some(&window), gtk4::gio::Cancellable::NONE, |_| {});
+        }
+    });
+    window.add_action(&open_with_action);
+
+    let wallpaper_action = gtk4::gio::SimpleAction::new("set-wallpaper", None);
+    wallpaper_action.connect_activate({
+        let state = state.clone();
+        let toast_overlay = toast_overlay.clone();
+        move |_, _| {
+            let Some(path) = state.borrow().current_path() else {
+                return;
+            };
+            let toast_overlay = toast_overlay.clone();
+            set_wallpaper(&path, move |e| {
+                toast_overlay.add_toast(adw::Toast::new(&format!("Could not set wallpaper: {e}")));
+            });
+        }
+    });
+    window.add_action(&wallpaper_action);
+
+    let print_action = gtk4::gio::SimpleAction::new("print", None);
+    print_action.connect_activate({
+        let print_current = print_current.clone();
+        move |_, _| print_current()
+    });
+    window.add_action(&print_action);
+
+    let save_rotation_action = gtk4::gio::SimpleAction::new("save-rotation", None);
+    save_rotation_action.connect_activate({
+        let state = state.clone();
+        let save_rotations = save_rotations.clone();
+        move |_, _| {
+            let current = state.borrow().current_path();
+            if let Some(path) = current {
+                save_rotations(vec![path], false);
+            }
+        }
+    });
+    window.add_action(&save_rotation_action);
+
+    let save_all_rotations_action = gtk4::gio::SimpleAction::new("save-all-rotations", None);
+    save_all_rotations_action.connect_activate({
+        let state = state.clone();
+        let save_rotations = save_rotations.clone();
+        move |_, _| {
+            let files = state.borrow().files.clone();
+            save_rotations(files, true);
+        }
+    });
+    window.add_action(&save_all_rotations_action);
+
+    let remember_view_action = gtk4::gio::SimpleAction::new_stateful(
+        "remember-view",
+        None,
+        &state.borrow().remember_view.to_variant(),
+    );
+    remember_view_action.connect_activate({
+        let state = state.clone();
+        move |action, _| {
+            let mut s = state.borrow_mut();
+            s.remember_view = !s.remember_view;
+            if !s.remember_view {
+                s.view_states.clear();
+            }
+            action.set_state(&s.remember_view.to_variant());
+        }
+    });
+    window.add_action(&remember_view_action);
+
+    let scroll_navigates_action = gtk4::gio::SimpleAction::new_stateful(
+        "scroll-navigates",
+        None,
+        &state.borrow().scroll_navigates.to_variant(),
+    );
+    scroll_navigates_action.connect_activate({
+        let state = state.clone();
+        let viewport = viewport.clone();
+        move |action, _| {
+            let mut s = state.borrow_mut();
+            s.scroll_navigates = !s.scroll_navigates;
+            viewport.set_scroll_navigates(s.scroll_navigates);
+            action.set_state(&s.scroll_navigates.to_variant());
+        }
+    });
+    window.add_action(&scroll_navigates_action);
+
+    let wrap_action = gtk4::gio::SimpleAction::new_stateful(
+        "wrap-navigation",
+        None,
+        &state.borrow().wrap.to_variant(),
+    );
+    wrap_action.connect_activate({
+        let state = state.clone();
+        move |action, _| {
+            let mut s = state.borrow_mut();
+            s.wrap = !s.wrap;
+            action.set_state(&s.wrap.to_variant());
+        }
+    });
+    window.add_action(&wrap_action);
+
+    let skip_unreadable_action = gtk4::gio::SimpleAction::new_stateful(
+        "skip-unreadable",
+        None,
+        &state.borrow().skip_unreadable.to_variant(),
+    );
+    skip_unreadable_action.connect_activate({
+        let state = state.clone();
+        move |action, _| {
+            let mut s = state.borrow_mut();
+            s.skip_unreadable = !s.skip_unreadable;
+            action.set_state(&s.skip_unreadable.to_variant());
+        }
+    });
+    window.add_action(&skip_unreadable_action);
+
+    let antialiasing = Rc::new(Cell::new(cfg.antialiasing));
+    let antialiasing_action = gtk4::gio::SimpleAction::new_stateful(
+        "antialiasing",
+        None,
+        &antialiasing.get().to_variant(),
+    );
+    antialiasing_action.connect_activate({
+        let antialiasing = antialiasing.clone();
+        let viewport = viewport.clone();
+        move |action, _| {
+            antialiasing.set(!antialiasing.get());
+            viewport.set_antialiasing(antialiasing.get());
+            action.set_state(&antialiasing.get().to_variant());
+        }
+    });
+    window.add_action(&antialiasing_action);
+
+    let dither = Rc::new(Cell::new(cfg.dither));
+    let dither_action =
+        gtk4::gio::SimpleAction::new_stateful("dither", None, &dither.get().to_variant());
+    dither_action.connect_activate({
+        let dither = dither.clone();
+        let viewport = viewport.clone();
+        move |action, _| {
+            dither.set(!dither.get());
+            viewport.set_dither(dither.get());
+            action.set_state(&dither.get().to_variant());
+        }
+    });
+    window.add_action(&dither_action);
+
+    let free_pan = Rc::new(Cell::new(cfg.free_pan));
+    let free_pan_action =
+        gtk4::gio::SimpleAction::new_stateful("free-pan", None, &free_pan.get().to_variant());
+    free_pan_action.connect_activate({
+        let free_pan = free_pan.clone();
+        let viewport = viewport.clone();
+        move |action, _| {
+            free_pan.set(!free_pan.get());
+            viewport.set_free_pan(free_pan.get());
+            action.set_state(&free_pan.get().to_variant());
+        }
+    });
+    window.add_action(&free_pan_action);
+
+    let escape_closes = Rc::new(Cell::new(cfg.escape_closes));
+    let escape_closes_action = gtk4::gio::SimpleAction::new_stateful(
+        "escape-closes",
+        None,
+        &escape_closes.get().to_variant(),
+    );
+    escape_closes_action.connect_activate({
+        let escape_closes = escape_closes.clone();
+        move |action, _| {
+            escape_closes.set(!escape_closes.get());
+            action.set_state(&escape_closes.get().to_variant());
+        }
+    });
+    window.add_action(&escape_closes_action);
+
+    crop_btn.connect_toggled({
+        let viewport = viewport.clone();
+        move |btn| viewport.set_crop_mode(btn.is_active())
+    });
+    viewport.connect_crop({
+        let crop_btn = crop_btn.clone();
+        let export_current = export_current.clone();
+        move |rect| {
+            crop_btn.set_active(false);
+            export_current(Some(rect));
+        }
+    });
+
+    // ── Sort order ────────────────────────────────────────────────────────
+    let sort_action = gtk4::gio::SimpleAction::new_stateful(
+        "sort-order",
+        Some(glib::VariantTy::STRING),
+        &state.borrow().sort_order.id().to_variant(),
+    );
+    sort_action.connect_activate({
+        let state = state.clone();
+        let populate_thumbnails = populate_thumbnails.clone();
+        let load_image = load_image.clone();
+        move |action, param| {
+            let Some(order) = param.and_then(|p| p.str()).and_then(SortOrder::from_id) else {
+                return;
+            };
+            action.set_state(&order.id().to_variant());
+            let current = {
+                let mut s = state.borrow_mut();
+                s.sort_files(order);
+                s.current_path()
+            };
+            populate_thumbnails();
+            // Refreshes the counter and thumbnail highlight; the texture is
+            // already cached.
+            if let Some(current) = current {
+                load_image(current);
+            }
+        }
+    });
+    window.add_action(&sort_action);
+
+    // ── Ratings ───────────────────────────────────────────────────────────
+    let rate_current: Rc<dyn Fn(u8)> = Rc::new({
+        let state = state.clone();
+        let thumb_ratings = thumb_ratings.clone();
+        let rating_overlay = rating_overlay.clone();
+        let toast_overlay = toast_overlay.clone();
+        move |stars| {
+            let (index, saved) = {
+                let mut s = state.borrow_mut();
+                (s.current_index, s.rate_current(stars))
+            };
+            if let Err(e) = saved {
+                let msg = format!("Could not save the rating: {e}");
+                toast_overlay.add_toast(adw::Toast::new(&msg));
+            }
+            let stars = {
+                let s = state.borrow();
+                s.current_path().map_or(0, |p| s.ratings.get(&p))
+            };
+            show_rating(&rating_overlay, stars);
+            if let Some(label) = thumb_ratings.borrow().get(index) {
+                show_rating(label, stars);
+            }
+        }
+    });
+
+    let min_rating_action = gtk4::gio::SimpleAction::new_stateful(
+        "min-rating",
+        Some(glib::VariantTy::UINT32),
+        &0u32.to_variant(),
+    );
+    min_rating_action.connect_activate({
+        let state = state.clone();
+        let populate_thumbnails = populate_thumbnails.clone();
+        let load_image = load_image.clone();
+        let toast_overlay = toast_overlay.clone();
+        move |action, param| {
+            let Some(stars) = param.and_then(|p| p.get::<u32>()) else {
+                return;
+            };
+            let stars = stars.min(ratings::MAX_STARS as u32) as u8;
+            let next = state.borrow_mut().set_min_rating(stars);
+            let Some(next) = next else {
+                let msg = if state.borrow().watched_directory.is_none() {
+                    "Filtering by rating needs an open folder".to_string()
+                } else {
+                    format!("No images rated {} or higher", ratings::stars_label(stars))
+                };
+                toast_overlay.add_toast(adw::Toast::new(&msg));
+                return;
+            };
+            action.set_state(&u32::from(stars).to_variant());
+            populate_thumbnails();
+            load_image(next);
+        }
+    });
+    window.add_action(&min_rating_action);
+
+    // ── Slideshow ─────────────────────────────────────────────────────────
+    // Advances on a timer while fullscreen. Any key press, manual
+    // navigation or leaving fullscreen stops it.
+    let slideshow: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+    let slideshow_interval = Rc::new(Cell::new(cfg.slideshow_interval_secs.max(1)));
+    let slideshow_shuffle = Rc::new(Cell::new(cfg.slideshow_shuffle));
+
+    let stop_slideshow: Rc<dyn Fn()> = Rc::new({
+        let slideshow = slideshow.clone();
+        move || {
+            if let Some(id) = slideshow.borrow_mut().take() {
+                id.remove();
+            }
+        }
+    });
+
+    let start_slideshow: Rc<dyn Fn()> = Rc::new({
+        let slideshow = slideshow.clone();
+        let interval = slideshow_interval.clone();
+        let shuffle = slideshow_shuffle.clone();
+        let stop_slideshow = stop_slideshow.clone();
+        let window = window.clone();
+        let state = state.clone();
+        let load_image = load_image.clone();
+        let viewport = viewport.clone();
+        move || {
+            stop_slideshow();
+            if state.borrow().files.len() < 2 || viewport.is_strip_mode() {
+                return;
+            }
+            window.fullscreen();
+            if shuffle.get() {
+                state.borrow_mut().start_shuffle();
+            }
+
+            let shuffle = shuffle.clone();
+            let shown = Rc::new(RefCell::new(state.borrow().current_path()));
+            let slideshow_tick = slideshow.clone();
+            let state = state.clone();
+            let load_image = load_image.clone();
+            let id = glib::timeout_add_seconds_local(interval.get(), move || {
+                // The user navigated on their own since the last slide.
+                if state.borrow().current_path() != *shown.borrow() {
+                    slideshow_tick.borrow_mut().take();
+                    return glib::ControlFlow::Break;
+                }
+                let next = {
+                    let mut s = state.borrow_mut();
+                    if shuffle.get() {
+                        s.next_shuffled()
+                    } else {
+                        s.last_nav_direction = 1;
+                        s.next()
+                    }
+                };
+                // Without wrapping, the show ends on the last image.
+                let Some(path) = next else {
+                    slideshow_tick.borrow_mut().take();
+                    return glib::ControlFlow::Break;
+                };
+                *shown.borrow_mut() = Some(path.clone());
+                load_image(path);
+                glib::ControlFlow::Continue
+            });
+            *slideshow.borrow_mut() = Some(id);
+        }
+    });
+
+    let slideshow_action = gtk4::gio::SimpleAction::new("slideshow", None);
+    slideshow_action.connect_activate({
+        let start_slideshow = start_slideshow.clone();
+        move |_, _| start_slideshow()
+    });
+    window.add_action(&slideshow_action);
+
+    let interval_action = gtk4::gio::SimpleAction::new_stateful(
+        "slideshow-interval",
+        Some(glib::VariantTy::UINT32),
+        &slideshow_interval.get().to_variant(),
+    );
+    interval_action.connect_activate({
+        let slideshow = slideshow.clone();
+        let interval = slideshow_interval.clone();
+        let start_slideshow = start_slideshow.clone();
+        move |action, param| {
+            let Some(secs) = param.and_then(|p| p.get::<u32>()) else {
+                return;
+            };
+            action.set_state(&secs.to_variant());
+            interval.set(secs);
+            if slideshow.borrow().is_some() {
+                start_slideshow();
+            }
+        }
+    });
+    window.add_action(&interval_action);
+
+    let shuffle_action = gtk4::gio::SimpleAction::new_stateful(
+        "slideshow-shuffle",
+        None,
+        &slideshow_shuffle.get().to_variant(),
+    );
+    shuffle_action.connect_activate({
+        let slideshow = slideshow.clone();
+        let shuffle = slideshow_shuffle.clone();
+        let start_slideshow = start_slideshow.clone();
+        move |action, _| {
+            shuffle.set(!shuffle.get());
+            action.set_state(&shuffle.get().to_variant());
+            if slideshow.borrow().is_some() {
+                start_slideshow();
+            }
+        }
+    });
+    window.add_action(&shuffle_action);
+
+    let show_random: Rc<dyn Fn()> = Rc::new({
+        let state = state.clone();
+        let load_image = load_image.clone();
+        move || {
+            let path = state.borrow_mut().random();
+            if let Some(path) = path {
+                load_image(path);
+            }
+        }
+    });
+    let random_action = gtk4::gio::SimpleAction::new("random-image", None);
+    random_action.connect_activate({
+        let show_random = show_random.clone();
+        move |_, _| show_random()
+    });
+    window.add_action(&random_action);
+
+    let image_transition = Rc::new(Cell::new(cfg.image_transition));
+    let transition_index = ImageTransition::ALL
+        .iter()
+        .position(|&t| t == image_transition.get())
+        .unwrap_or(0) as u32;
+    let transition_action = gtk4::gio::SimpleAction::new_stateful(
+        "image-transition",
+        Some(glib::VariantTy::UINT32),
+        &transition_index.to_variant(),
+    );
+    transition_action.connect_activate({
+        let image_transition = image_transition.clone();
+        let viewport = viewport.clone();
+        move |action, param| {
+            let Some(index) = param.and_then(|p| p.get::<u32>()) else {
+                return;
+            };
+            let Some(&transition) = ImageTransition::ALL.get(index as usize) else {
+                return;
+            };
+            action.set_state(&index.to_variant());
+            image_transition.set(transition);
+            viewport.set_image_transition(transition);
+        }
+    });
+    window.add_action(&transition_action);
+
+    // Resizing rebuilds the strip so every cell and decode uses the new size.
+    let thumb_size_index = ThumbSize::ALL
+        .iter()
+        .position(|&s| s == thumb_size.get())
+        .unwrap_or(0) as u32;
+    let thumb_size_action = gtk4::gio::SimpleAction::new_stateful(
+        "thumb-size",
+        Some(glib::VariantTy::UINT32),
+        &thumb_size_index.to_variant(),
+    );
+    thumb_size_action.connect_activate({
+        let thumb_size = thumb_size.clone();
+        let thumb_scroll = thumb_scroll.clone();
+        let populate_thumbnails = populate_thumbnails.clone();
+        let scroll_to_active_thumb = scroll_to_active_thumb.clone();
+        move |action, param| {
+            let Some(index) = param.and_then(|p| p.get::<u32>()) else {
+                return;
+            };
+            let Some(&size) = ThumbSize::ALL.get(index as usize) else {
+                return;
+            };
+            action.set_state(&index.to_variant());
+            if size == thumb_size.get() {
+                return;
+            }
+            thumb_size.set(size);
+            thumb_scroll.set_height_request(thumb_strip_height(size));
+            populate_thumbnails();
+            scroll_to_active_thumb();
+        }
+    });
+    window.add_action(&thumb_size_action);
+
+    // Ctrl+scroll over the strip steps the thumbnail size; plain scrolling
+    // still pans the strip.
+    let thumb_zoom = gtk4::EventControllerScroll::new(gtk4::EventControllerScrollFlags::VERTICAL);
+    thumb_zoom.set_propagation_phase(gtk4::PropagationPhase::Capture);
+    thumb_zoom.connect_scroll({
+        let thumb_size = thumb_size.clone();
+        let thumb_size_action = thumb_size_action.clone();
+        move |ctrl, _dx, dy| {
+            if !ctrl
+                .current_event_state()
+                .contains(gtk4::gdk::ModifierType::CONTROL_MASK)
+                || dy == 0.0
+            {
+                return glib::Propagation::Proceed;
+            }
+            // Scrolling up grows the thumbnails, like zooming the image.
+            let size = thumb_size.get().step(if dy < 0.0 { 1 } else { -1 });
+            let index = ThumbSize::ALL.iter().position(|&s| s == size).unwrap_or(0) as u32;
+            thumb_size_action.activate(Some(&index.to_variant()));
+            glib::Propagation::Stop
+        }
+    });
+    thumb_scroll.add_controller(thumb_zoom);
+
+    window.connect_fullscreened_notify({
+        let stop_slideshow = stop_slideshow.clone();
+        move |w| {
+            if !w.is_fullscreen() {
+                stop_slideshow();
+            }
+        }
+    });
+
+    // Runs ahead of the main key handler so every key counts, even ones
+    // that handler consumes.
+    let slideshow_keys = gtk4::EventControllerKey::new();
+    slideshow_keys.set_propagation_phase(gtk4::PropagationPhase::Capture);
+    slideshow_keys.connect_key_pressed({
+        let stop_slideshow = stop_slideshow.clone();
+        move |_, key, _, _| {
+            if key != gtk4::gdk::Key::F5 {
+                stop_slideshow();
+            }
+            glib::Propagation::Proceed
+        }
+    });
+    window.add_controller(slideshow_keys);
+
     // ── Keyboard handler with navigation coalescing ───────────────────────
     let key_ctrl = gtk4::EventControllerKey::new();
     key_ctrl.set_propagation_phase(gtk4::PropagationPhase::Capture);
@@ -986,26 +4109,131 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
     let viewport_key = viewport.clone();
     let nav_pending_key = nav_pending.clone();
     let schedule_nav_key = schedule_nav.clone();
+    let load_key = load_image.clone();
+    let delete_key = delete_current.clone();
+    let slideshow_key = slideshow.clone();
+    let start_slideshow_key = start_slideshow.clone();
+    let stop_slideshow_key = stop_slideshow.clone();
+    let grayscale_key = grayscale_check.clone();
+    let crop_key = crop_btn.clone();
+    let export_key = export_current.clone();
+    let print_key = print_current.clone();
+    let random_key = show_random.clone();
+    let invert_key = invert_check.clone();
+    let step_page_key = step_page.clone();
+    let reset_view_key = reset_current_view.clone();
+    let rate_key = rate_current.clone();
+    let gallery_key = gallery_btn.clone();
+    let escape_closes_key = escape_closes.clone();
+
+    // Peeking at the compare image lasts only while Tab is held.
+    key_ctrl.connect_key_released({
+        let viewport = viewport.clone();
+        move |_, key, _, _| {
+            if key == gtk4::gdk::Key::Tab {
+                viewport.set_compare_peek(false);
+            }
+        }
+    });
 
     key_ctrl.connect_key_pressed(move |_, key, _, modifier| match key {
+        // Keys typed into a text field, like the zoom entry, are its own.
+        _ if GtkWindowExt::focus(&window_key).is_some_and(|w| w.is::<gtk4::Text>()) => {
+            glib::Propagation::Proceed
+        }
+        gtk4::gdk::Key::Escape if gallery_key.is_active() => {
+            gallery_key.set_active(false);
+            glib::Propagation::Stop
+        }
+        // The gallery grid moves between tiles with the arrows itself.
+        _ if gallery_key.is_active() => glib::Propagation::Proceed,
         gtk4::gdk::Key::f | gtk4::gdk::Key::F => {
             window_key.fullscreen();
             glib::Propagation::Stop
         }
+        gtk4::gdk::Key::Escape if crop_key.is_active() => {
+            crop_key.set_active(false);
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::Escape if viewport_key.is_compare_mode() => {
+            viewport_key.set_compare(None, CompareLayout::default());
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::Tab if viewport_key.is_compare_mode() => {
+            viewport_key.set_compare_peek(true);
+            glib::Propagation::Stop
+        }
         gtk4::gdk::Key::Escape => {
-            window_key.unfullscreen();
+            if window_key.is_fullscreen() {
+                window_key.unfullscreen();
+            } else if escape_closes_key.get() {
+                window_key.close();
+            }
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::q | gtk4::gdk::Key::Q
+            if modifier.contains(gtk4::gdk::ModifierType::CONTROL_MASK) =>
+        {
+            // The only window: closing it saves the config and quits.
+            window_key.close();
             glib::Propagation::Stop
         }
-        gtk4::gdk::Key::Right | gtk4::gdk::Key::space => {
+        gtk4::gdk::Key::s | gtk4::gdk::Key::S
+            if modifier.contains(gtk4::gdk::ModifierType::CONTROL_MASK) =>
+        {
+            export_key(None);
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::p | gtk4::gdk::Key::P
+            if modifier.contains(gtk4::gdk::ModifierType::CONTROL_MASK) =>
+        {
+            print_key();
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::x | gtk4::gdk::Key::X => {
+            crop_key.set_active(!crop_key.is_active());
+            glib::Propagation::Stop
+        }
+        // Arrows pan a zoomed-in image and navigate otherwise; Page keys
+        // turn pages of a multi-page file, then navigate like Space.
+        gtk4::gdk::Key::Page_Down if step_page_key(1) => glib::Propagation::Stop,
+        gtk4::gdk::Key::Page_Up if step_page_key(-1) => glib::Propagation::Stop,
+        gtk4::gdk::Key::Right if viewport_key.pan_step(1, 0) => glib::Propagation::Stop,
+        gtk4::gdk::Key::Left if viewport_key.pan_step(-1, 0) => glib::Propagation::Stop,
+        gtk4::gdk::Key::Up if viewport_key.pan_step(0, -1) => glib::Propagation::Stop,
+        gtk4::gdk::Key::Down if viewport_key.pan_step(0, 1) => glib::Propagation::Stop,
+        gtk4::gdk::Key::Right | gtk4::gdk::Key::space | gtk4::gdk::Key::Page_Down => {
             nav_pending_key.set(nav_pending_key.get() + 1);
             schedule_nav_key();
             glib::Propagation::Stop
         }
-        gtk4::gdk::Key::Left => {
+        gtk4::gdk::Key::Left | gtk4::gdk::Key::Page_Up => {
             nav_pending_key.set(nav_pending_key.get() - 1);
             schedule_nav_key();
             glib::Propagation::Stop
         }
+        gtk4::gdk::Key::Home | gtk4::gdk::Key::End => {
+            // Drop queued relative steps so they don't land after the jump.
+            nav_pending_key.set(0);
+            let path = {
+                let mut s = state_key.borrow_mut();
+                if key == gtk4::gdk::Key::Home {
+                    s.first()
+                } else {
+                    s.last()
+                }
+            };
+            if let Some(p) = path {
+                load_key(p);
+            }
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::r | gtk4::gdk::Key::R
+            if modifier.contains(gtk4::gdk::ModifierType::CONTROL_MASK) =>
+        {
+            random_key();
+            glib::Propagation::Stop
+        }
         gtk4::gdk::Key::r | gtk4::gdk::Key::R => {
             let rotation = {
                 let mut s = state_key.borrow_mut();
@@ -1019,18 +4247,87 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
             viewport_key.set_rotation(rotation as f32);
             glib::Propagation::Stop
         }
-        gtk4::gdk::Key::plus | gtk4::gdk::Key::equal => {
+        gtk4::gdk::Key::h | gtk4::gdk::Key::H => {
+            let (h, v) = {
+                let mut s = state_key.borrow_mut();
+                s.flip_horizontal();
+                s.current_flip()
+            };
+            viewport_key.set_flip(h, v);
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::v | gtk4::gdk::Key::V => {
+            let (h, v) = {
+                let mut s = state_key.borrow_mut();
+                s.flip_vertical();
+                s.current_flip()
+            };
+            viewport_key.set_flip(h, v);
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::plus | gtk4::gdk::Key::equal | gtk4::gdk::Key::KP_Add => {
             viewport_key.zoom_in();
             glib::Propagation::Stop
         }
-        gtk4::gdk::Key::minus => {
+        gtk4::gdk::Key::minus | gtk4::gdk::Key::KP_Subtract => {
             viewport_key.zoom_out();
             glib::Propagation::Stop
         }
-        gtk4::gdk::Key::_0 | gtk4::gdk::Key::Home => {
+        gtk4::gdk::Key::_0 | gtk4::gdk::Key::KP_0
+            if modifier.contains(gtk4::gdk::ModifierType::CONTROL_MASK) =>
+        {
             viewport_key.reset_view();
             glib::Propagation::Stop
         }
+        gtk4::gdk::Key::_1 | gtk4::gdk::Key::KP_1
+            if modifier.contains(gtk4::gdk::ModifierType::CONTROL_MASK) =>
+        {
+            viewport_key.actual_size();
+            glib::Propagation::Stop
+        }
+        // 1–5 rate the current image for culling, 0 clears its rating.
+        // Ctrl and Alt digits are left to other shortcuts.
+        _ if rating_for_key(key).is_some()
+            && !modifier.intersects(
+                gtk4::gdk::ModifierType::CONTROL_MASK | gtk4::gdk::ModifierType::ALT_MASK,
+            )
+            && !viewport_key.is_strip_mode() =>
+        {
+            rate_key(rating_for_key(key).unwrap_or(0));
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::w | gtk4::gdk::Key::W => {
+            if modifier.contains(gtk4::gdk::ModifierType::SHIFT_MASK) {
+                viewport_key.fit_height();
+            } else {
+                viewport_key.fit_width();
+            }
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::c | gtk4::gdk::Key::C => {
+            if let Some(index) = viewport_key.exit_strip_mode() {
+                let path = {
+                    let mut s = state_key.borrow_mut();
+                    s.current_index = index;
+                    s.current_path()
+                };
+                if let Some(p) = path {
+                    load_key(p);
+                }
+            } else {
+                let (files, index) = {
+                    let s = state_key.borrow();
+                    (s.files.clone(), s.current_index)
+                };
+                crop_key.set_active(false);
+                viewport_key.enter_strip_mode(files, index);
+            }
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::p | gtk4::gdk::Key::P => {
+            viewport_key.toggle_pixel_readout();
+            glib::Propagation::Stop
+        }
         gtk4::gdk::Key::i | gtk4::gdk::Key::I => {
             let mut s = state_key.borrow_mut();
             s.info_visible = !s.info_visible;
@@ -1050,39 +4347,256 @@ fn build_ui(app: &adw::Application, initial_path: Option<PathBuf>) {
             viewport_key.toggle_denoise();
             glib::Propagation::Stop
         }
+        gtk4::gdk::Key::F5 => {
+            if slideshow_key.borrow().is_some() {
+                stop_slideshow_key();
+            } else {
+                start_slideshow_key();
+            }
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::Delete | gtk4::gdk::Key::KP_Delete => {
+            if !viewport_key.is_strip_mode() {
+                delete_key(modifier.contains(gtk4::gdk::ModifierType::SHIFT_MASK));
+            }
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::BackSpace => {
+            if !viewport_key.is_strip_mode() {
+                reset_view_key();
+            }
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::g | gtk4::gdk::Key::G => {
+            grayscale_key.set_active(!grayscale_key.is_active());
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::n | gtk4::gdk::Key::N => {
+            invert_key.set_active(!invert_key.is_active());
+            glib::Propagation::Stop
+        }
+        gtk4::gdk::Key::b | gtk4::gdk::Key::B => {
+            let background = {
+                let mut s = state_key.borrow_mut();
+                s.alpha_background = s.alpha_background.next();
+                s.alpha_background
+            };
+            viewport_key.set_alpha_background(background);
+            glib::Propagation::Stop
+        }
         _ => glib::Propagation::Proceed,
     });
     window.add_controller(key_ctrl);
 
     let state_close = state.clone();
+    let advance_on_scroll_end = cfg.advance_on_scroll_end;
+    let image_cache_size = cfg.image_cache_size;
+    let loading_indicator_delay_ms = cfg.loading_indicator_delay_ms;
+    let recursive_scan = cfg.recursive_scan;
+    let slideshow_interval_close = slideshow_interval.clone();
+    let image_transition_close = image_transition.clone();
+    let slideshow_shuffle_close = slideshow_shuffle.clone();
+    let antialiasing_close = antialiasing.clone();
+    let dither_close = dither.clone();
+    let thumb_size_close = thumb_size.clone();
+    let free_pan_close = free_pan.clone();
+    let escape_closes_close = escape_closes.clone();
+    let viewport_close = viewport.clone();
+    let jpeg_quality_close = jpeg_quality.clone();
+    let recent_close = recent_files.clone();
     window.connect_close_request(move |win| {
         let s = state_close.borrow();
+        // The default size tracks the last unmaximized, non-fullscreen
+        // size, which is what the window should return to.
+        let (window_width, window_height) = win.default_size();
         let config = Config {
-            window_width: win.width(),
-            window_height: win.height(),
+            window_width,
+            window_height,
             window_maximized: win.is_maximized(),
+            window_fullscreen: win.is_fullscreen(),
             info_panel_visible: s.info_visible,
             last_directory: s
                 .current_path()
                 .and_then(|p| p.parent().map(|d| d.to_string_lossy().into_owned())),
+            advance_on_scroll_end,
+            auto_orient,
+            image_cache_size,
+            loading_indicator_delay_ms,
+            alpha_background: s.alpha_background,
+            slideshow_interval_secs: slideshow_interval_close.get(),
+            slideshow_shuffle: slideshow_shuffle_close.get(),
+            image_transition: image_transition_close.get(),
+            recursive_scan,
+            sort_order: s.sort_order,
+            jpeg_quality: jpeg_quality_close.get(),
+            recent_files: recent_close.borrow().clone(),
+            remember_view: s.remember_view,
+            scroll_navigates: s.scroll_navigates,
+            wrap_navigation: s.wrap,
+            skip_unreadable: s.skip_unreadable,
+            antialiasing: antialiasing_close.get(),
+            dither: dither_close.get(),
+            free_pan: free_pan_close.get(),
+            escape_closes: escape_closes_close.get(),
+            thumb_size: thumb_size_close.get(),
         };
         config.save();
+        viewport_close.shutdown();
         glib::Propagation::Proceed
     });
 
     window.present();
 
-    if let Some(path) = initial_path {
+    // A file opens with its siblings as the working set; a directory opens
+    // at its first image.
+    let open_path: Rc<dyn Fn(PathBuf)> = Rc::new(move |path: PathBuf| {
         if path.is_file() {
-            state.borrow_mut().load_directory(&path);
+            note_recent(&path);
+            let loaded = state.borrow_mut().load_directory(&path);
             populate_thumbnails();
-            load_image(path);
+            match loaded {
+                Ok(()) => load_image(path),
+                Err(err) => report_unreadable_directory(&toast_overlay, &path, &err),
+            }
         } else if path.is_dir() {
             state.borrow_mut().load_from_directory(&path);
             populate_thumbnails();
-            if let Some(first) = state.borrow().current_path() {
+            let first = state.borrow().current_path();
+            if let Some(first) = first {
                 load_image(first);
             }
         }
+    });
+
+    if let Some(path) = initial_path {
+        open_path(path);
+    }
+    open_path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with(names: &[&str]) -> AppState {
+        let mut state = AppState::new();
+        state.files = names.iter().map(PathBuf::from).collect();
+        state
+    }
+
+    /// A fresh, empty directory under the system temp dir.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("iris-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn navigation_wraps_at_both_ends() {
+        let mut state = state_with(&["a.jpg", "b.jpg", "c.jpg"]);
+        assert_eq!(state.prev(), Some(PathBuf::from("c.jpg")));
+        assert_eq!(state.next(), Some(PathBuf::from("a.jpg")));
+        assert_eq!(state.next(), Some(PathBuf::from("b.jpg")));
+
+        state.wrap = false;
+        assert_eq!(state.next(), Some(PathBuf::from("c.jpg")));
+        assert_eq!(state.next(), None);
+        assert_eq!(state.current_index, 2);
+        state.current_index = 0;
+        assert_eq!(state.prev(), None);
+        assert_eq!(state.current_index, 0);
+    }
+
+    #[test]
+    fn navigation_and_rotation_on_an_empty_list_do_nothing() {
+        let mut state = state_with(&[]);
+        assert_eq!(state.next(), None);
+        assert_eq!(state.prev(), None);
+        assert_eq!(state.current_path(), None);
+        state.rotate_cw();
+        assert_eq!(state.current_rotation(), 0);
+        assert!(state.rotations.is_empty());
+    }
+
+    #[test]
+    fn rotation_accumulates_modulo_360_per_image() {
+        let mut state = state_with(&["a.jpg", "b.jpg"]);
+        for _ in 0..5 {
+            state.rotate_cw();
+        }
+        assert_eq!(state.current_rotation(), 90);
+        state.rotate_ccw();
+        state.rotate_ccw();
+        assert_eq!(state.current_rotation(), 270);
+
+        state.next();
+        assert_eq!(state.current_rotation(), 0);
+        state.rotate_ccw();
+        assert_eq!(state.current_rotation(), 270);
+        state.prev();
+        assert_eq!(state.current_rotation(), 270);
+    }
+
+    #[test]
+    fn browsing_skips_unreadable_files_until_all_have_failed() {
+        let mut state = state_with(&["a.jpg", "b.jpg", "c.jpg"]);
+        state.next();
+        state.last_nav_direction = 1;
+        assert_eq!(
+            state.skip_unreadable(Path::new("b.jpg")),
+            Some(PathBuf::from("c.jpg"))
+        );
+        // A stale error for an image no longer current is ignored.
+        assert_eq!(state.skip_unreadable(Path::new("b.jpg")), None);
+        assert_eq!(
+            state.skip_unreadable(Path::new("c.jpg")),
+            Some(PathBuf::from("a.jpg"))
+        );
+        assert_eq!(state.skip_unreadable(Path::new("a.jpg")), None);
+        assert_eq!(state.current_path(), Some(PathBuf::from("a.jpg")));
+
+        // A jump, or the option turned off, shows the error instead.
+        state.last_nav_direction = 0;
+        assert_eq!(state.skip_unreadable(Path::new("a.jpg")), None);
+        state.last_nav_direction = -1;
+        state.skip_unreadable = false;
+        assert_eq!(state.skip_unreadable(Path::new("a.jpg")), None);
+    }
+
+    #[test]
+    fn load_directory_lists_sorted_images_only() {
+        let dir = scratch_dir("load-directory");
+        for name in ["c.png", "a.jpg", "notes.txt", "b.webp"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+        std::fs::create_dir(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("nested").join("d.png"), b"").unwrap();
+
+        let mut state = state_with(&[]);
+        state.load_directory(&dir.join("b.webp")).unwrap();
+        let names: Vec<_> = state
+            .files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["a.jpg", "b.webp", "c.png"]);
+        assert_eq!(state.current_index, 1);
+        assert_eq!(state.watched_directory.as_deref(), Some(dir.as_path()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_directory_reports_an_unreadable_directory() {
+        let path = std::env::temp_dir()
+            .join(format!("iris-missing-{}", std::process::id()))
+            .join("a.jpg");
+        let mut state = state_with(&["old.jpg"]);
+        let err = state.load_directory(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert!(state.files.is_empty());
+        assert_eq!(state.current_index, 0);
+        assert_eq!(state.watched_directory, None);
     }
 }
@@ -0,0 +1,362 @@
+use exif::{In, Tag, Value};
+use std::path::Path;
+
+/// Photographic EXIF fields shown in the info panel. Each is `None` when
+/// the file doesn't carry it, e.g. screenshots have no camera data.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CameraInfo {
+    pub camera: Option<String>,
+    pub lens: Option<String>,
+    pub focal_length: Option<String>,
+    pub aperture: Option<String>,
+    pub shutter_speed: Option<String>,
+    pub iso: Option<String>,
+    pub captured: Option<String>,
+    pub location: Option<GpsLocation>,
+}
+
+/// Capture position in decimal degrees; south and west are negative.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GpsLocation {
+    fn from_exif(exif: &exif::Exif) -> Option<Self> {
+        let latitude = degrees(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, 'S')?;
+        let longitude = degrees(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, 'W')?;
+        let valid = (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude);
+        valid.then_some(Self {
+            latitude,
+            longitude,
+        })
+    }
+
+    pub fn display(&self) -> String {
+        format!("{:.5}, {:.5}", self.latitude, self.longitude)
+    }
+
+    pub fn map_url(&self) -> String {
+        format!(
+            "https://www.openstreetmap.org/?mlat={lat:.6}&mlon={lon:.6}#map=15/{lat:.6}/{lon:.6}",
+            lat = self.latitude,
+            lon = self.longitude
+        )
+    }
+}
+
+impl CameraInfo {
+    pub fn read(path: &Path) -> Self {
+        let Ok(file) = std::fs::File::open(path) else {
+            return Self::default();
+        };
+        let mut buf = std::io::BufReader::new(file);
+        match exif::Reader::new().read_from_container(&mut buf) {
+            Ok(exif) => Self::from_exif(&exif),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Info panel label and value for each field, in display order.
+    pub fn rows(&self) -> [(&'static str, Option<&str>); 7] {
+        [
+            ("Camera", self.camera.as_deref()),
+            ("Lens", self.lens.as_deref()),
+            ("Focal length", self.focal_length.as_deref()),
+            ("Aperture", self.aperture.as_deref()),
+            ("Shutter speed", self.shutter_speed.as_deref()),
+            ("ISO", self.iso.as_deref()),
+            ("Captured", self.captured.as_deref()),
+        ]
+    }
+
+    pub fn from_exif(exif: &exif::Exif) -> Self {
+        let make = ascii(exif, Tag::Make);
+        let model = ascii(exif, Tag::Model);
+        // Most models already start with the make ("Canon EOS R5").
+        let camera = match (make, model) {
+            (Some(make), Some(model)) if model.starts_with(&make) => Some(model),
+            (Some(make), Some(model)) => Some(format!("{make} {model}")),
+            (make, model) => model.or(make),
+        };
+
+        Self {
+            camera,
+            lens: ascii(exif, Tag::LensModel),
+            focal_length: rational(exif, Tag::FocalLength).map(|f| format!("{} mm", trim(f))),
+            aperture: rational(exif, Tag::FNumber).map(|f| format!("f/{}", trim(f))),
+            shutter_speed: rational(exif, Tag::ExposureTime).map(format_exposure),
+            iso: exif
+                .get_field(Tag::PhotographicSensitivity, In::PRIMARY)
+                .and_then(|f| f.value.get_uint(0))
+                .map(|iso| format!("ISO {iso}")),
+            captured: ascii(exif, Tag::DateTimeOriginal).map(format_date),
+            location: GpsLocation::from_exif(exif),
+        }
+    }
+}
+
+/// Every EXIF field in the file as a label and value with units, in file
+/// order. Fields of the embedded thumbnail are marked as such.
+pub fn exif_fields(path: &Path) -> Vec<(String, String)> {
+    let Ok(file) = std::fs::File::open(path) else {
+        return Vec::new();
+    };
+    let mut buf = std::io::BufReader::new(file);
+    match exif::Reader::new().read_from_container(&mut buf) {
+        Ok(exif) => fields_of(&exif),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn fields_of(exif: &exif::Exif) -> Vec<(String, String)> {
+    exif.fields()
+        .map(|f| {
+            let label = if f.ifd_num == In::PRIMARY {
+                f.tag.to_string()
+            } else {
+                format!("{} ({})", f.tag, f.ifd_num)
+            };
+            let value = f.display_value().with_unit(exif).to_string();
+            (label, value.trim_matches('"').to_string())
+        })
+        .collect()
+}
+
+/// Largest term of a reduced aspect ratio still shown as "w:h"; past it the
+/// shape is given as a decimal ratio instead.
+const MAX_ASPECT_TERM: u32 = 21;
+
+/// Pixel count in megapixels, e.g. "24.2 MP".
+pub fn format_megapixels(width: u32, height: u32) -> String {
+    format!("{:.1} MP", width as f64 * height as f64 / 1e6)
+}
+
+/// Shape of a `width`×`height` image: "3:2", "16:9" and the like when it
+/// reduces to small terms, otherwise a decimal ratio such as "1.78:1".
+pub fn format_aspect_ratio(width: u32, height: u32) -> String {
+    if width == 0 || height == 0 {
+        return "—".to_string();
+    }
+    let (mut a, mut b) = (width, height);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    let (w, h) = (width / a, height / a);
+    if w <= MAX_ASPECT_TERM && h <= MAX_ASPECT_TERM {
+        format!("{w}:{h}")
+    } else if width >= height {
+        format!("{:.2}:1", width as f64 / height as f64)
+    } else {
+        format!("1:{:.2}", height as f64 / width as f64)
+    }
+}
+
+/// `rows` as plain text, one "Label: value" line each with the values lined
+/// up, for pasting into notes or saving next to the image.
+pub fn format_report(rows: &[(String, String)]) -> String {
+    let width = rows
+        .iter()
+        .map(|(l, _)| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for (label, value) in rows {
+        let pad = width - label.chars().count();
+        out.push_str(&format!("{label}:{:pad$} {value}\n", ""));
+    }
+    out
+}
+
+fn ascii(exif: &exif::Exif, tag: Tag) -> Option<String> {
+    let field = exif.get_field(tag, In::PRIMARY)?;
+    let Value::Ascii(ref parts) = field.value else {
+        return None;
+    };
+    let s = String::from_utf8_lossy(parts.first()?);
+    let s = s.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+fn rational(exif: &exif::Exif, tag: Tag) -> Option<f64> {
+    let field = exif.get_field(tag, In::PRIMARY)?;
+    let Value::Rational(ref v) = field.value else {
+        return None;
+    };
+    let r = v.first()?;
+    (r.denom != 0).then(|| r.to_f64())
+}
+
+/// A degrees/minutes/seconds triple as signed decimal degrees. `negative`
+/// is the hemisphere reference letter that flips the sign.
+fn degrees(exif: &exif::Exif, tag: Tag, ref_tag: Tag, negative: char) -> Option<f64> {
+    let field = exif.get_field(tag, In::PRIMARY)?;
+    let Value::Rational(ref dms) = field.value else {
+        return None;
+    };
+    if dms.iter().any(|r| r.denom == 0) {
+        return None;
+    }
+    let mut value = dms
+        .iter()
+        .zip([1.0, 60.0, 3600.0])
+        .map(|(r, div)| r.to_f64() / div)
+        .sum::<f64>();
+    let hemisphere = ascii(exif, ref_tag)?;
+    if hemisphere.eq_ignore_ascii_case(&negative.to_string()) {
+        value = -value;
+    }
+    Some(value)
+}
+
+/// One decimal at most, without a trailing `.0`.
+fn trim(x: f64) -> String {
+    let s = format!("{x:.1}");
+    s.strip_suffix(".0").map(str::to_string).unwrap_or(s)
+}
+
+fn format_exposure(seconds: f64) -> String {
+    if seconds > 0.0 && seconds < 1.0 {
+        format!("1/{} s", (1.0 / seconds).round())
+    } else {
+        format!("{} s", trim(seconds))
+    }
+}
+
+fn format_date(raw: String) -> String {
+    match exif::DateTime::from_ascii(raw.as_bytes()) {
+        Ok(d) => format!(
+            "{}-{:02}-{:02} {:02}:{:02}",
+            d.year, d.month, d.day, d.hour, d.minute
+        ),
+        Err(_) => raw,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exif::experimental::Writer;
+    use exif::{Field, Rational};
+    use std::io::Cursor;
+
+    fn exif_with(fields: &[Field]) -> exif::Exif {
+        let mut writer = Writer::new();
+        for f in fields {
+            writer.push_field(f);
+        }
+        let mut out = Cursor::new(Vec::new());
+        writer.write(&mut out, false).unwrap();
+        exif::Reader::new().read_raw(out.into_inner()).unwrap()
+    }
+
+    fn field(tag: Tag, value: Value) -> Field {
+        Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value,
+        }
+    }
+
+    fn text(s: &str) -> Value {
+        Value::Ascii(vec![s.as_bytes().to_vec()])
+    }
+
+    fn ratio(num: u32, denom: u32) -> Value {
+        Value::Rational(vec![Rational { num, denom }])
+    }
+
+    #[test]
+    fn formats_photographic_fields() {
+        let exif = exif_with(&[
+            field(Tag::Make, text("Canon")),
+            field(Tag::Model, text("Canon EOS R5")),
+            field(Tag::LensModel, text("RF24-70mm F2.8 L IS USM")),
+            field(Tag::FocalLength, ratio(50, 1)),
+            field(Tag::FNumber, ratio(28, 10)),
+            field(Tag::ExposureTime, ratio(1, 200)),
+            field(Tag::PhotographicSensitivity, Value::Short(vec![400])),
+            field(Tag::DateTimeOriginal, text("2024:06:01 18:42:07")),
+        ]);
+
+        let info = CameraInfo::from_exif(&exif);
+        assert_eq!(info.camera.as_deref(), Some("Canon EOS R5"));
+        assert_eq!(info.lens.as_deref(), Some("RF24-70mm F2.8 L IS USM"));
+        assert_eq!(info.focal_length.as_deref(), Some("50 mm"));
+        assert_eq!(info.aperture.as_deref(), Some("f/2.8"));
+        assert_eq!(info.shutter_speed.as_deref(), Some("1/200 s"));
+        assert_eq!(info.iso.as_deref(), Some("ISO 400"));
+        assert_eq!(info.captured.as_deref(), Some("2024-06-01 18:42"));
+    }
+
+    #[test]
+    fn gps_hemisphere_references_set_the_sign() {
+        let dms = |d, m, s| {
+            Value::Rational(vec![
+                Rational { num: d, denom: 1 },
+                Rational { num: m, denom: 1 },
+                Rational { num: s, denom: 100 },
+            ])
+        };
+        let exif = exif_with(&[
+            field(Tag::GPSLatitude, dms(33, 51, 3540)),
+            field(Tag::GPSLatitudeRef, text("S")),
+            field(Tag::GPSLongitude, dms(151, 12, 3000)),
+            field(Tag::GPSLongitudeRef, text("E")),
+        ]);
+
+        let location = CameraInfo::from_exif(&exif).location.unwrap();
+        assert!((location.latitude - -(33.0 + 51.0 / 60.0 + 35.4 / 3600.0)).abs() < 1e-9);
+        assert!((location.longitude - (151.0 + 12.0 / 60.0 + 30.0 / 3600.0)).abs() < 1e-9);
+        assert_eq!(location.display(), "-33.85983, 151.20833");
+    }
+
+    #[test]
+    fn lists_every_field_with_units() {
+        let exif = exif_with(&[
+            field(Tag::Model, text("X100V")),
+            field(Tag::FocalLength, ratio(23, 1)),
+        ]);
+
+        let fields = fields_of(&exif);
+        assert!(fields.contains(&("Model".to_string(), "X100V".to_string())));
+        assert!(fields.contains(&("FocalLength".to_string(), "23 mm".to_string())));
+    }
+
+    #[test]
+    fn report_lines_up_values() {
+        let rows = [
+            ("Filename".to_string(), "a.jpg".to_string()),
+            ("ISO".to_string(), "400".to_string()),
+        ];
+        assert_eq!(format_report(&rows), "Filename: a.jpg\nISO:      400\n");
+    }
+
+    #[test]
+    fn aspect_ratio_reduces_or_falls_back_to_decimal() {
+        assert_eq!(format_aspect_ratio(6000, 4000), "3:2");
+        assert_eq!(format_aspect_ratio(1920, 1080), "16:9");
+        assert_eq!(format_aspect_ratio(3024, 4032), "3:4");
+        assert_eq!(format_aspect_ratio(1366, 768), "1.78:1");
+        assert_eq!(format_aspect_ratio(768, 1366), "1:1.78");
+        assert_eq!(format_aspect_ratio(0, 10), "—");
+        assert_eq!(format_megapixels(6000, 4000), "24.0 MP");
+        assert_eq!(format_megapixels(1366, 768), "1.0 MP");
+    }
+
+    #[test]
+    fn missing_fields_stay_empty() {
+        let exif = exif_with(&[
+            field(Tag::Model, text("X100V")),
+            field(Tag::Make, text("FUJIFILM")),
+        ]);
+
+        let info = CameraInfo::from_exif(&exif);
+        assert_eq!(info.camera.as_deref(), Some("FUJIFILM X100V"));
+        assert_eq!(info.lens, None);
+        assert_eq!(info.iso, None);
+        assert_eq!(info.captured, None);
+        assert_eq!(info.location, None);
+    }
+}
@@ -0,0 +1,373 @@
+use exif::{In, Tag};
+use image::RgbaImage;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::viewport::camera::ImageTransform;
+
+/// EXIF orientation (tag 0x0112), values 1–8.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Orientation(u8);
+
+impl Orientation {
+    pub const NORMAL: Self = Self(1);
+
+    pub fn from_exif(value: u32) -> Option<Self> {
+        (1..=8).contains(&value).then_some(Self(value as u8))
+    }
+
+    /// Clockwise rotation applied after the optional horizontal mirror.
+    pub fn rotation_degrees(self) -> i32 {
+        match self.0 {
+            3 | 4 => 180,
+            6 | 7 => 90,
+            5 | 8 => 270,
+            _ => 0,
+        }
+    }
+
+    pub fn is_mirrored(self) -> bool {
+        matches!(self.0, 2 | 4 | 5 | 7)
+    }
+
+    /// The equivalent GPU transform. EXIF mirrors before rotating, while the
+    /// viewport mirrors in screen space after rotating; for quarter turns a
+    /// horizontal mirror becomes a vertical one.
+    pub fn transform(self) -> ImageTransform {
+        let rotation_degrees = self.rotation_degrees();
+        let (flip_h, flip_v) = match self.0 {
+            2 => (true, false),
+            4 | 5 | 7 => (false, true),
+            _ => (false, false),
+        };
+        // Orientation 4 is a mirror plus half turn, which is just a
+        // vertical mirror.
+        let rotation_degrees = if self.0 == 4 { 0 } else { rotation_degrees };
+        ImageTransform {
+            rotation_degrees,
+            flip_h,
+            flip_v,
+        }
+    }
+
+    /// Transform stored pixels into display orientation.
+    pub fn apply(self, img: RgbaImage) -> RgbaImage {
+        let img = if self.is_mirrored() {
+            image::imageops::flip_horizontal(&img)
+        } else {
+            img
+        };
+        match self.rotation_degrees() {
+            90 => image::imageops::rotate90(&img),
+            180 => image::imageops::rotate180(&img),
+            270 => image::imageops::rotate270(&img),
+            _ => img,
+        }
+    }
+}
+
+/// Orientation of the main image, if the file carries one.
+pub fn read_orientation(path: &Path) -> Option<Orientation> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut buf = std::io::BufReader::new(file);
+    let exif = exif::Reader::new().read_from_container(&mut buf).ok()?;
+    field_orientation(&exif, In::PRIMARY)
+}
+
+/// Display transform from the file's EXIF orientation. RAW decodes come out
+/// of the pipeline already oriented, so they are left alone.
+pub fn read_display_transform(path: &Path) -> ImageTransform {
+    if crate::raw::is_raw(path) {
+        return ImageTransform::default();
+    }
+    read_orientation(path)
+        .map(Orientation::transform)
+        .unwrap_or_default()
+}
+
+/// Orientation for an embedded preview. Some cameras write the preview
+/// already rotated and tag its IFD separately; only fall back to the main
+/// image's orientation when the preview carries no tag of its own.
+pub fn preview_orientation(
+    primary: Option<Orientation>,
+    preview: Option<Orientation>,
+) -> Orientation {
+    preview.or(primary).unwrap_or(Orientation::NORMAL)
+}
+
+/// Decode the JPEG thumbnail embedded in a file's EXIF data, oriented for
+/// display. Far cheaper than a full decode for RAW files.
+pub fn embedded_preview(path: &Path) -> Option<RgbaImage> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut buf = std::io::BufReader::new(file);
+    let exif = exif::Reader::new().read_from_container(&mut buf).ok()?;
+    decode_embedded_preview(&exif)
+}
+
+pub fn decode_embedded_preview(exif: &exif::Exif) -> Option<RgbaImage> {
+    let img =
+        image::load_from_memory_with_format(embedded_preview_jpeg(exif)?, image::ImageFormat::Jpeg)
+            .ok()?
+            .to_rgba8();
+
+    let orientation = preview_orientation(
+        field_orientation(exif, In::PRIMARY),
+        field_orientation(exif, In::THUMBNAIL),
+    );
+    Some(orientation.apply(img))
+}
+
+/// The still-encoded JPEG thumbnail from the EXIF thumbnail IFD.
+pub fn embedded_preview_jpeg(exif: &exif::Exif) -> Option<&[u8]> {
+    let offset = exif
+        .get_field(Tag::JPEGInterchangeFormat, In::THUMBNAIL)?
+        .value
+        .get_uint(0)? as usize;
+    let len = exif
+        .get_field(Tag::JPEGInterchangeFormatLength, In::THUMBNAIL)?
+        .value
+        .get_uint(0)? as usize;
+    exif.buf().get(offset..offset.checked_add(len)?)
+}
+
+/// Byte range of a JPEG's EXIF (APP1) segment, marker included.
+fn exif_segment(jpeg: &[u8]) -> Option<Range<usize>> {
+    if !jpeg.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut at = 2;
+    while jpeg.get(at) == Some(&0xFF) {
+        let marker = *jpeg.get(at + 1)?;
+        // Entropy-coded data follows start-of-scan; metadata comes first.
+        if marker == 0xDA || marker == 0xD9 {
+            return None;
+        }
+        let len = u16::from_be_bytes([*jpeg.get(at + 2)?, *jpeg.get(at + 3)?]) as usize;
+        let end = at + 2 + len;
+        if marker == 0xE1 && jpeg.get(at + 4..end)?.starts_with(b"Exif\0\0") {
+            return Some(at..end);
+        }
+        at = end;
+    }
+    None
+}
+
+/// Copy the EXIF segment of `original`, if any, into `encoded`, a JPEG
+/// freshly written without metadata. It goes after the JFIF header.
+pub fn with_exif_from(original: &[u8], mut encoded: Vec<u8>) -> Vec<u8> {
+    let Some(segment) = exif_segment(original) else {
+        return encoded;
+    };
+    let mut at = 2;
+    if encoded.get(at..at + 2) == Some(&[0xFF, 0xE0]) {
+        at += 2 + u16::from_be_bytes([encoded[at + 2], encoded[at + 3]]) as usize;
+    }
+    encoded.splice(at..at, original[segment].iter().copied());
+    encoded
+}
+
+/// Set the main image's orientation tag in a JPEG's EXIF to normal, in
+/// place. Returns false if it has no such tag.
+pub fn reset_jpeg_orientation(jpeg: &mut [u8]) -> bool {
+    fn reset(tiff: &mut [u8]) -> Option<()> {
+        let big_endian = match tiff.get(..2)? {
+            b"MM" => true,
+            b"II" => false,
+            _ => return None,
+        };
+        let u16_at = |b: &[u8], at: usize| -> Option<u16> {
+            let bytes = [*b.get(at)?, *b.get(at + 1)?];
+            Some(if big_endian {
+                u16::from_be_bytes(bytes)
+            } else {
+                u16::from_le_bytes(bytes)
+            })
+        };
+        let ifd0 = {
+            let bytes: [u8; 4] = tiff.get(4..8)?.try_into().ok()?;
+            if big_endian {
+                u32::from_be_bytes(bytes)
+            } else {
+                u32::from_le_bytes(bytes)
+            }
+        } as usize;
+        for n in 0..u16_at(tiff, ifd0)? as usize {
+            let entry = ifd0 + 2 + n * 12;
+            // Orientation is a single SHORT stored in the entry itself.
+            if u16_at(tiff, entry)? == 0x0112 && u16_at(tiff, entry + 2)? == 3 {
+                let normal = if big_endian {
+                    1u16.to_be_bytes()
+                } else {
+                    1u16.to_le_bytes()
+                };
+                tiff.get_mut(entry + 8..entry + 10)?
+                    .copy_from_slice(&normal);
+                return Some(());
+            }
+        }
+        None
+    }
+
+    let Some(segment) = exif_segment(jpeg) else {
+        return false;
+    };
+    // Skip the marker, length and "Exif\0\0" to reach the TIFF header.
+    reset(&mut jpeg[segment.start + 10..segment.end]).is_some()
+}
+
+fn field_orientation(exif: &exif::Exif, ifd: In) -> Option<Orientation> {
+    exif.get_field(Tag::Orientation, ifd)?
+        .value
+        .get_uint(0)
+        .and_then(Orientation::from_exif)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exif::experimental::Writer;
+    use exif::{Field, Value};
+    use std::io::Cursor;
+
+    /// A 4×2 JPEG thumbnail in a TIFF container with the given orientation
+    /// tags on the main image and on the thumbnail IFD.
+    fn tiff_with_preview(primary: u16, preview: Option<u16>) -> exif::Exif {
+        let mut jpeg = Vec::new();
+        image::DynamicImage::ImageRgb8(image::RgbImage::new(4, 2))
+            .write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+            .unwrap();
+
+        let primary_field = Field {
+            tag: Tag::Orientation,
+            ifd_num: In::PRIMARY,
+            value: Value::Short(vec![primary]),
+        };
+        let preview_field = preview.map(|v| Field {
+            tag: Tag::Orientation,
+            ifd_num: In::THUMBNAIL,
+            value: Value::Short(vec![v]),
+        });
+
+        let mut writer = Writer::new();
+        writer.push_field(&primary_field);
+        if let Some(ref f) = preview_field {
+            writer.push_field(f);
+        }
+        writer.set_jpeg(&jpeg, In::THUMBNAIL);
+
+        let mut out = Cursor::new(Vec::new());
+        writer.write(&mut out, false).unwrap();
+        exif::Reader::new().read_raw(out.into_inner()).unwrap()
+    }
+
+    /// A minimal JPEG, without image data, tagged with `orientation`.
+    fn jpeg_with_exif(orientation: u16) -> Vec<u8> {
+        let field = Field {
+            tag: Tag::Orientation,
+            ifd_num: In::PRIMARY,
+            value: Value::Short(vec![orientation]),
+        };
+        let mut writer = Writer::new();
+        writer.push_field(&field);
+        let mut tiff = Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let tiff = tiff.into_inner();
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(&tiff);
+        jpeg.extend_from_slice(&[0xFF, 0xD9]);
+        jpeg
+    }
+
+    fn jpeg_orientation(jpeg: &[u8]) -> Option<Orientation> {
+        let exif = exif::Reader::new()
+            .read_from_container(&mut Cursor::new(jpeg))
+            .ok()?;
+        field_orientation(&exif, In::PRIMARY)
+    }
+
+    #[test]
+    fn resets_jpeg_orientation_in_place() {
+        let mut jpeg = jpeg_with_exif(6);
+        assert_eq!(jpeg_orientation(&jpeg), Orientation::from_exif(6));
+        assert!(reset_jpeg_orientation(&mut jpeg));
+        assert_eq!(jpeg_orientation(&jpeg), Some(Orientation::NORMAL));
+
+        assert!(!reset_jpeg_orientation(&mut [0xFF, 0xD8, 0xFF, 0xD9]));
+    }
+
+    #[test]
+    fn copies_exif_after_the_jfif_header() {
+        let original = jpeg_with_exif(8);
+        let mut encoded = Vec::new();
+        image::DynamicImage::ImageRgb8(image::RgbImage::new(4, 2))
+            .write_to(&mut Cursor::new(&mut encoded), image::ImageFormat::Jpeg)
+            .unwrap();
+
+        let merged = with_exif_from(&original, encoded.clone());
+        assert_eq!(merged.len(), encoded.len() + original.len() - 4);
+        assert_eq!(jpeg_orientation(&merged), Orientation::from_exif(8));
+        assert!(image::load_from_memory(&merged).is_ok());
+    }
+
+    #[test]
+    fn preview_with_own_tag_ignores_primary_orientation() {
+        // Main image is rotated 90° CW, but the preview was stored upright.
+        let exif = tiff_with_preview(6, Some(1));
+        let preview = decode_embedded_preview(&exif).unwrap();
+        assert_eq!(preview.dimensions(), (4, 2));
+    }
+
+    #[test]
+    fn untagged_preview_inherits_primary_orientation() {
+        let exif = tiff_with_preview(6, None);
+        let preview = decode_embedded_preview(&exif).unwrap();
+        assert_eq!(preview.dimensions(), (2, 4));
+    }
+
+    #[test]
+    fn gpu_transform_matches_pixel_orientation() {
+        let mut img = RgbaImage::new(3, 2);
+        for (i, px) in img.pixels_mut().enumerate() {
+            *px = image::Rgba([i as u8, 0, 0, 255]);
+        }
+
+        for value in 1..=8 {
+            let orientation = Orientation::from_exif(value).unwrap();
+            let t = orientation.transform();
+
+            // Rotate, then mirror in screen space, as the viewport does.
+            let mut gpu = match t.rotation_degrees {
+                90 => image::imageops::rotate90(&img),
+                180 => image::imageops::rotate180(&img),
+                270 => image::imageops::rotate270(&img),
+                _ => img.clone(),
+            };
+            if t.flip_h {
+                gpu = image::imageops::flip_horizontal(&gpu);
+            }
+            if t.flip_v {
+                gpu = image::imageops::flip_vertical(&gpu);
+            }
+
+            assert_eq!(gpu, orientation.apply(img.clone()), "orientation {value}");
+        }
+    }
+
+    #[test]
+    fn transpose_orientations_mirror_before_rotating() {
+        let mut img = RgbaImage::new(2, 1);
+        img.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+
+        // 5 (transpose): the top-left pixel stays top-left.
+        let t = Orientation::from_exif(5).unwrap().apply(img.clone());
+        assert_eq!(t.dimensions(), (1, 2));
+        assert_eq!(t.get_pixel(0, 0)[0], 255);
+
+        // 7 (transverse): the top-left pixel ends up bottom-right.
+        let t = Orientation::from_exif(7).unwrap().apply(img);
+        assert_eq!(t.get_pixel(0, 1)[0], 255);
+    }
+}
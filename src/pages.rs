@@ -0,0 +1,227 @@
+//! Multi-page files. TIFFs can hold several images ("pages"); everything
+//! else has exactly one, which is what the regular decoders show.
+
+use image::DynamicImage;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+use tiff::ColorType;
+use tiff::decoder::{ChunkType, Decoder, DecodingResult};
+
+pub fn is_tiff(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("tiff") || e.eq_ignore_ascii_case("tif"))
+}
+
+fn open(path: &Path) -> Option<Decoder<BufReader<std::fs::File>>> {
+    let file = std::fs::File::open(path).ok()?;
+    Decoder::new(BufReader::new(file)).ok()
+}
+
+/// Number of pages in `path`; 1 for anything that isn't a readable TIFF.
+pub fn page_count(path: &Path) -> usize {
+    let decoder = is_tiff(path).then(|| open(path)).flatten();
+    decoder.map_or(1, count_pages)
+}
+
+fn count_pages<R: Read + Seek>(mut decoder: Decoder<R>) -> usize {
+    let mut count = 1;
+    while decoder.more_images() && decoder.next_image().is_ok() {
+        count += 1;
+    }
+    count
+}
+
+/// Decode page `page` (0-based) of a TIFF.
+pub fn decode_page(path: &Path, page: usize) -> Option<DynamicImage> {
+    decode_page_from(open(path)?, page)
+}
+
+fn decode_page_from<R: Read + Seek>(mut decoder: Decoder<R>, page: usize) -> Option<DynamicImage> {
+    decoder.seek_to_image(page).ok()?;
+    let (w, h) = decoder.dimensions().ok()?;
+    let color = decoder.colortype().ok()?;
+    to_dynamic(color, w, h, decoder.read_image().ok()?)
+}
+
+/// Like `decode_page`, a strip or tile at a time, telling `progress` the
+/// fraction decoded after each. Very large files can show how far along
+/// they are this way.
+pub fn decode_page_with_progress(
+    path: &Path,
+    page: usize,
+    progress: &mut dyn FnMut(f32),
+) -> Option<DynamicImage> {
+    decode_page_chunked(open(path)?, page, progress)
+}
+
+fn decode_page_chunked<R: Read + Seek>(
+    mut decoder: Decoder<R>,
+    page: usize,
+    progress: &mut dyn FnMut(f32),
+) -> Option<DynamicImage> {
+    decoder.seek_to_image(page).ok()?;
+    let (w, h) = decoder.dimensions().ok()?;
+    let color = decoder.colortype().ok()?;
+    let (channels, bits) = match color {
+        ColorType::Gray(bits) => (1, bits),
+        ColorType::GrayA(bits) => (2, bits),
+        ColorType::RGB(bits) => (3, bits),
+        ColorType::RGBA(bits) => (4, bits),
+        _ => return None,
+    };
+    let len = w as usize * h as usize * channels;
+    let mut out = match bits {
+        8 => DecodingResult::U8(vec![0; len]),
+        16 => DecodingResult::U16(vec![0; len]),
+        _ => return None,
+    };
+
+    let (chunk_w, chunk_h) = decoder.chunk_dimensions();
+    let count = match decoder.get_chunk_type() {
+        ChunkType::Strip => decoder.strip_count().ok()?,
+        ChunkType::Tile => decoder.tile_count().ok()?,
+    };
+    let across = w.div_ceil(chunk_w.max(1)).max(1);
+    for i in 0..count {
+        let (data_w, data_h) = decoder.chunk_data_dimensions(i);
+        let place = ChunkPlace {
+            x: (i % across) * chunk_w,
+            y: (i / across) * chunk_h,
+            width: data_w,
+            height: data_h,
+        };
+        match (&mut out, decoder.read_chunk(i).ok()?) {
+            (DecodingResult::U8(dst), DecodingResult::U8(src)) => {
+                place.copy(dst, &src, w, channels)?
+            }
+            (DecodingResult::U16(dst), DecodingResult::U16(src)) => {
+                place.copy(dst, &src, w, channels)?
+            }
+            _ => return None,
+        }
+        progress((i + 1) as f32 / count as f32);
+    }
+    to_dynamic(color, w, h, out)
+}
+
+/// Where a decoded strip or tile goes in the whole image, in pixels.
+struct ChunkPlace {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl ChunkPlace {
+    /// Copy the chunk's rows from `src` into `dst`, an image `image_width`
+    /// pixels wide. Edge tiles may come padded to the full tile width, so
+    /// the source row length follows from its size.
+    fn copy<T: Copy>(
+        &self,
+        dst: &mut [T],
+        src: &[T],
+        image_width: u32,
+        channels: usize,
+    ) -> Option<()> {
+        let rows = self.height as usize;
+        let src_stride = src.len().checked_div(rows)?;
+        let n = self.width as usize * channels;
+        for row in 0..rows {
+            let src_row = src.get(row * src_stride..)?.get(..n)?;
+            let start =
+                ((self.y as usize + row) * image_width as usize + self.x as usize) * channels;
+            dst.get_mut(start..start + n)?.copy_from_slice(src_row);
+        }
+        Some(())
+    }
+}
+
+fn to_dynamic(color: ColorType, w: u32, h: u32, pixels: DecodingResult) -> Option<DynamicImage> {
+    let img = match (color, pixels) {
+        (ColorType::Gray(8), DecodingResult::U8(buf)) => {
+            DynamicImage::ImageLuma8(image::ImageBuffer::from_raw(w, h, buf)?)
+        }
+        (ColorType::Gray(16), DecodingResult::U16(buf)) => {
+            DynamicImage::ImageLuma16(image::ImageBuffer::from_raw(w, h, buf)?)
+        }
+        (ColorType::GrayA(8), DecodingResult::U8(buf)) => {
+            DynamicImage::ImageLumaA8(image::ImageBuffer::from_raw(w, h, buf)?)
+        }
+        (ColorType::RGB(8), DecodingResult::U8(buf)) => {
+            DynamicImage::ImageRgb8(image::ImageBuffer::from_raw(w, h, buf)?)
+        }
+        (ColorType::RGB(16), DecodingResult::U16(buf)) => {
+            DynamicImage::ImageRgb16(image::ImageBuffer::from_raw(w, h, buf)?)
+        }
+        (ColorType::RGBA(8), DecodingResult::U8(buf)) => {
+            DynamicImage::ImageRgba8(image::ImageBuffer::from_raw(w, h, buf)?)
+        }
+        (ColorType::RGBA(16), DecodingResult::U16(buf)) => {
+            DynamicImage::ImageRgba16(image::ImageBuffer::from_raw(w, h, buf)?)
+        }
+        _ => return None,
+    };
+    Some(img)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use tiff::encoder::{TiffEncoder, colortype};
+
+    fn two_page_tiff() -> Vec<u8> {
+        let mut out = Cursor::new(Vec::new());
+        let mut encoder = TiffEncoder::new(&mut out).unwrap();
+        encoder
+            .write_image::<colortype::RGB8>(2, 1, &[255, 0, 0, 255, 0, 0])
+            .unwrap();
+        encoder
+            .write_image::<colortype::Gray8>(1, 3, &[10, 20, 30])
+            .unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn counts_and_decodes_every_page() {
+        let bytes = two_page_tiff();
+        let decoder = || Decoder::new(Cursor::new(bytes.as_slice())).unwrap();
+
+        assert_eq!(count_pages(decoder()), 2);
+
+        let first = decode_page_from(decoder(), 0).unwrap().to_rgba8();
+        assert_eq!(first.dimensions(), (2, 1));
+        assert_eq!(first.get_pixel(1, 0).0, [255, 0, 0, 255]);
+
+        let second = decode_page_from(decoder(), 1).unwrap().to_rgba8();
+        assert_eq!(second.dimensions(), (1, 3));
+        assert_eq!(second.get_pixel(0, 2).0, [30, 30, 30, 255]);
+
+        assert!(decode_page_from(decoder(), 2).is_none());
+    }
+
+    #[test]
+    fn chunked_decode_matches_and_reports_progress() {
+        let (w, h) = (5u32, 40u32);
+        let pixels: Vec<u8> = (0..w * h * 3).map(|i| (i % 251) as u8).collect();
+        let mut out = Cursor::new(Vec::new());
+        {
+            let mut encoder = TiffEncoder::new(&mut out).unwrap();
+            let mut image = encoder.new_image::<colortype::RGB8>(w, h).unwrap();
+            // Three strips: 16, 16 and 8 rows.
+            image.rows_per_strip(16).unwrap();
+            image.write_data(&pixels).unwrap();
+        }
+        let bytes = out.into_inner();
+        let decoder = || Decoder::new(Cursor::new(bytes.as_slice())).unwrap();
+
+        let mut steps = Vec::new();
+        let chunked = decode_page_chunked(decoder(), 0, &mut |f| steps.push(f)).unwrap();
+        let whole = decode_page_from(decoder(), 0).unwrap();
+        assert_eq!(chunked, whole);
+        assert_eq!(steps.len(), 3, "{steps:?}");
+        assert!(steps.windows(2).all(|p| p[0] < p[1]));
+        assert_eq!(steps.last(), Some(&1.0));
+    }
+}
@@ -0,0 +1,136 @@
+//! Printing the current image through GTK's print dialog, either scaled to
+//! fit the printable area or at its actual size.
+
+use gtk4::prelude::*;
+use gtk4::{cairo, glib};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Pixels per inch assumed for actual-size prints, matching a typical
+/// desktop display.
+const ACTUAL_SIZE_PPI: f64 = 96.0;
+
+/// How the image is sized on the page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PrintScale {
+    /// As large as the printable area allows, keeping its aspect ratio.
+    #[default]
+    FitToPage,
+    /// One image pixel per 1/96 inch; larger images are cropped evenly.
+    ActualSize,
+}
+
+/// Scale and top-left offset, in points, that place a `width`×`height`
+/// image centered on a `page_width`×`page_height` printable area.
+pub fn placement(
+    width: u32,
+    height: u32,
+    page_width: f64,
+    page_height: f64,
+    scale: PrintScale,
+) -> (f64, f64, f64) {
+    let (w, h) = (width.max(1) as f64, height.max(1) as f64);
+    let s = match scale {
+        PrintScale::FitToPage => (page_width / w).min(page_height / h),
+        PrintScale::ActualSize => 72.0 / ACTUAL_SIZE_PPI,
+    };
+    (s, (page_width - w * s) / 2.0, (page_height - h * s) / 2.0)
+}
+
+/// Show the print dialog for `image`, already oriented as displayed. Pages
+/// default to landscape for wide images; a tab in the dialog picks the
+/// scaling.
+pub fn print_image(
+    parent: &impl IsA<gtk4::Window>,
+    job_name: &str,
+    image: image::RgbaImage,
+) -> Result<(), glib::Error> {
+    let op = gtk4::PrintOperation::new();
+    op.set_job_name(job_name);
+    op.set_n_pages(1);
+    op.set_unit(gtk4::Unit::Points);
+    op.set_embed_page_setup(true);
+
+    let setup = gtk4::PageSetup::new();
+    setup.set_orientation(if image.width() > image.height() {
+        gtk4::PageOrientation::Landscape
+    } else {
+        gtk4::PageOrientation::Portrait
+    });
+    op.set_default_page_setup(Some(&setup));
+
+    let scale = Rc::new(Cell::new(PrintScale::default()));
+    op.set_custom_tab_label(Some("Image"));
+    op.connect_create_custom_widget(|_| {
+        let fit = gtk4::CheckButton::with_label("Fit to page");
+        let actual = gtk4::CheckButton::with_label("Actual size");
+        actual.set_group(Some(&fit));
+        fit.set_active(true);
+        let tab = gtk4::Box::new(gtk4::Orientation::Vertical, 6);
+        tab.set_margin_top(12);
+        tab.set_margin_start(12);
+        tab.append(&fit);
+        tab.append(&actual);
+        Some(tab.upcast())
+    });
+    op.connect_custom_widget_apply({
+        let scale = scale.clone();
+        move |_, tab| {
+            let actual = tab
+                .last_child()
+                .and_downcast::<gtk4::CheckButton>()
+                .is_some_and(|b| b.is_active());
+            scale.set(if actual {
+                PrintScale::ActualSize
+            } else {
+                PrintScale::FitToPage
+            });
+        }
+    });
+
+    op.connect_draw_page(move |_, ctx, _| {
+        let cr = ctx.cairo_context();
+        let (s, x, y) = placement(
+            image.width(),
+            image.height(),
+            ctx.width(),
+            ctx.height(),
+            scale.get(),
+        );
+        let Some(surface) = crate::viewport::minimap::rgba_surface(&image) else {
+            eprintln!("[Iris] Image too large to print");
+            return;
+        };
+        cr.rectangle(0.0, 0.0, ctx.width(), ctx.height());
+        cr.clip();
+        cr.translate(x, y);
+        cr.scale(s, s);
+        if cr.set_source_surface(&surface, 0.0, 0.0).is_ok() {
+            cr.source().set_filter(cairo::Filter::Good);
+            let _ = cr.paint();
+        }
+    });
+
+    op.run(gtk4::PrintOperationAction::PrintDialog, Some(parent))
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_fills_the_limiting_side_and_centers() {
+        let (s, x, y) = placement(2000, 1000, 500.0, 500.0, PrintScale::FitToPage);
+        assert_eq!(s, 0.25);
+        assert_eq!((x, y), (0.0, 125.0));
+    }
+
+    #[test]
+    fn actual_size_prints_96_pixels_per_inch() {
+        let (s, x, y) = placement(96, 192, 72.0, 72.0, PrintScale::ActualSize);
+        assert_eq!(s, 0.75);
+        // Twice the page height: cropped evenly top and bottom.
+        assert_eq!((x, y), (0.0, -36.0));
+    }
+}
@@ -0,0 +1,302 @@
+//! Encoding quality of lossy files, estimated from their headers: JPEG
+//! quantization tables and the WebP (VP8) base quantizer. Encoders don't
+//! store the quality setting itself, so this is an approximation on the
+//! familiar 1–100 scale, good enough to spot heavily compressed files.
+
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// IJG base luminance table (ITU T.81 Annex K.1), which the libjpeg quality
+/// setting scales.
+const STD_LUMINANCE: [u16; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, //
+    12, 12, 14, 19, 26, 58, 60, 55, //
+    14, 13, 16, 24, 40, 57, 69, 56, //
+    14, 17, 22, 29, 51, 87, 80, 62, //
+    18, 22, 37, 56, 68, 109, 103, 77, //
+    24, 35, 55, 64, 81, 104, 113, 92, //
+    49, 64, 78, 87, 103, 121, 120, 101, //
+    72, 92, 95, 98, 112, 100, 103, 99, //
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodingQuality {
+    /// Approximate quality setting, 1–100.
+    Estimated(u8),
+    Lossless,
+}
+
+impl EncodingQuality {
+    /// Info panel text; formats without a quality metric show "n/a".
+    pub fn display(quality: Option<Self>) -> String {
+        match quality {
+            Some(Self::Estimated(q)) => format!("≈{q}"),
+            Some(Self::Lossless) => "Lossless".to_string(),
+            None => "n/a".to_string(),
+        }
+    }
+}
+
+/// Quality of a JPEG or WebP file; None for other formats or when the
+/// headers can't be read.
+pub fn estimate(path: &Path) -> Option<EncodingQuality> {
+    let mut reader = BufReader::new(std::fs::File::open(path).ok()?);
+    let mut magic = [0u8; 12];
+    reader.read_exact(&mut magic).ok()?;
+    reader.rewind().ok()?;
+    if magic.starts_with(&[0xFF, 0xD8]) {
+        jpeg_quality(&mut reader).map(EncodingQuality::Estimated)
+    } else if magic.starts_with(b"RIFF") && &magic[8..12] == b"WEBP" {
+        webp_quality(&mut reader)
+    } else {
+        None
+    }
+}
+
+/// Estimate from the luminance quantization table, inverting libjpeg's
+/// quality scaling: other encoders' tables land close to the libjpeg
+/// quality that compresses about as hard.
+fn jpeg_quality<R: Read + Seek>(reader: &mut R) -> Option<u8> {
+    let mut soi = [0u8; 2];
+    reader.read_exact(&mut soi).ok()?;
+    loop {
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header).ok()?;
+        if header[0] != 0xFF {
+            return None;
+        }
+        let marker = header[1];
+        let len = u16::from_be_bytes([header[2], header[3]]) as usize;
+        // Tables come before the scan data; SOS or EOI means there were none.
+        if marker == 0xDA || marker == 0xD9 || len < 2 {
+            return None;
+        }
+        if marker != 0xDB {
+            reader.seek(SeekFrom::Current(len as i64 - 2)).ok()?;
+            continue;
+        }
+        let mut segment = vec![0u8; len - 2];
+        reader.read_exact(&mut segment).ok()?;
+        if let Some(table) = luminance_table(&segment) {
+            return Some(quality_from_table(&table));
+        }
+    }
+}
+
+/// Table 0 from a DQT segment, which may hold several tables.
+fn luminance_table(mut segment: &[u8]) -> Option<[u16; 64]> {
+    while let Some((&info, rest)) = segment.split_first() {
+        let wide = info >> 4 != 0;
+        let size = if wide { 128 } else { 64 };
+        let values = rest.get(..size)?;
+        if info & 0x0F == 0 {
+            let mut table = [0u16; 64];
+            for (i, v) in table.iter_mut().enumerate() {
+                *v = if wide {
+                    u16::from_be_bytes([values[2 * i], values[2 * i + 1]])
+                } else {
+                    values[i] as u16
+                };
+            }
+            return Some(table);
+        }
+        segment = &rest[size..];
+    }
+    None
+}
+
+/// Order doesn't matter: sums are the same in zigzag and natural order.
+fn quality_from_table(table: &[u16; 64]) -> u8 {
+    let sum: u32 = table.iter().map(|&v| v as u32).sum();
+    let std_sum: u32 = STD_LUMINANCE.iter().map(|&v| v as u32).sum();
+    let scale = 100.0 * sum as f64 / std_sum as f64;
+    let quality = if scale <= 100.0 {
+        (200.0 - scale) / 2.0
+    } else {
+        5000.0 / scale
+    };
+    quality.round().clamp(1.0, 100.0) as u8
+}
+
+/// Lossless (VP8L) files need no estimate; lossy ones invert libwebp's
+/// mapping from quality to the frame's base quantizer index.
+fn webp_quality<R: Read + Seek>(reader: &mut R) -> Option<EncodingQuality> {
+    reader.seek(SeekFrom::Start(12)).ok()?;
+    loop {
+        let mut header = [0u8; 8];
+        reader.read_exact(&mut header).ok()?;
+        let size = u32::from_le_bytes(header[4..8].try_into().ok()?) as u64;
+        match &header[..4] {
+            b"VP8L" => return Some(EncodingQuality::Lossless),
+            b"VP8 " => {
+                let mut frame = Vec::new();
+                reader
+                    .by_ref()
+                    .take(size.min(256))
+                    .read_to_end(&mut frame)
+                    .ok()?;
+                let qi = vp8_base_quantizer(&frame)?;
+                return Some(EncodingQuality::Estimated(quality_from_quantizer(qi)));
+            }
+            // Chunks are padded to an even size.
+            _ => reader
+                .seek(SeekFrom::Current((size + (size & 1)) as i64))
+                .ok()?,
+        };
+    }
+}
+
+/// libwebp picks the quantizer as `127 * (1 - c^(1/3))`, where `c` is the
+/// quality bent so that 75 lands halfway.
+fn quality_from_quantizer(qi: u8) -> u8 {
+    let linear = (1.0 - qi.min(127) as f64 / 127.0).powi(3);
+    let c = if linear < 0.5 {
+        linear * 1.5
+    } else {
+        (linear + 1.0) / 2.0
+    };
+    (c * 100.0).round().clamp(1.0, 100.0) as u8
+}
+
+/// `y_ac_qi` from a VP8 key frame header (RFC 6386, section 9.6).
+fn vp8_base_quantizer(frame: &[u8]) -> Option<u8> {
+    // 3-byte frame tag; bit 0 clear marks a key frame, followed by the start
+    // code and dimensions.
+    if frame.len() < 10 || frame[0] & 1 != 0 || frame[3..6] != [0x9D, 0x01, 0x2A] {
+        return None;
+    }
+    let mut d = BoolDecoder::new(&frame[10..])?;
+    d.literal(2); // color space, clamping
+    if d.flag() {
+        let update_map = d.flag();
+        if d.flag() {
+            d.flag(); // absolute or delta values
+            for bits in [7, 7, 7, 7, 6, 6, 6, 6] {
+                d.optional_signed(bits);
+            }
+        }
+        if update_map {
+            for _ in 0..3 {
+                if d.flag() {
+                    d.literal(8);
+                }
+            }
+        }
+    }
+    d.literal(1 + 6 + 3); // filter type, level, sharpness
+    if d.flag() && d.flag() {
+        for _ in 0..8 {
+            d.optional_signed(6);
+        }
+    }
+    d.literal(2); // DCT partitions
+    Some(d.literal(7) as u8)
+}
+
+/// VP8's boolean entropy decoder, enough to read the uncompressed-looking
+/// header fields, which all use probability 1/2.
+struct BoolDecoder<'a> {
+    data: &'a [u8],
+    value: u32,
+    range: u32,
+    bits: u32,
+}
+
+impl<'a> BoolDecoder<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let (first, data) = data.split_first_chunk::<2>()?;
+        Some(Self {
+            data,
+            value: u16::from_be_bytes(*first) as u32,
+            range: 255,
+            bits: 0,
+        })
+    }
+
+    fn flag(&mut self) -> bool {
+        let split = 1 + ((self.range - 1) >> 1);
+        let big_split = split << 8;
+        let bit = self.value >= big_split;
+        if bit {
+            self.range -= split;
+            self.value -= big_split;
+        } else {
+            self.range = split;
+        }
+        while self.range < 128 {
+            self.value <<= 1;
+            self.range <<= 1;
+            self.bits += 1;
+            if self.bits == 8 {
+                self.bits = 0;
+                if let Some((&byte, rest)) = self.data.split_first() {
+                    self.value |= byte as u32;
+                    self.data = rest;
+                }
+            }
+        }
+        bit
+    }
+
+    fn literal(&mut self, bits: u32) -> u32 {
+        (0..bits).fold(0, |acc, _| (acc << 1) | self.flag() as u32)
+    }
+
+    /// A flagged value with a trailing sign bit; only its bits matter here.
+    fn optional_signed(&mut self, bits: u32) {
+        if self.flag() {
+            self.literal(bits + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// The table libjpeg writes for `quality`.
+    fn ijg_table(quality: u32) -> [u16; 64] {
+        let scale = if quality < 50 {
+            5000 / quality
+        } else {
+            200 - 2 * quality
+        };
+        STD_LUMINANCE.map(|v| ((v as u32 * scale + 50) / 100).clamp(1, 255) as u16)
+    }
+
+    #[test]
+    fn recovers_libjpeg_quality_from_its_table() {
+        for quality in [20, 50, 75, 90, 95] {
+            let estimate = quality_from_table(&ijg_table(quality)) as i32;
+            assert!(
+                (estimate - quality as i32).abs() <= 1,
+                "{quality} → {estimate}"
+            );
+        }
+    }
+
+    #[test]
+    fn finds_the_luminance_table_after_other_segments() {
+        let mut jpeg = vec![0xFF, 0xD8];
+        // APP0 with a short payload.
+        jpeg.extend([0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00]);
+        // DQT with a chroma table first, then luminance.
+        jpeg.extend([0xFF, 0xDB, 0x00, 2 + 2 * 65]);
+        jpeg.push(0x01);
+        jpeg.extend([99u8; 64]);
+        jpeg.push(0x00);
+        jpeg.extend(ijg_table(80).map(|v| v as u8));
+        jpeg.extend([0xFF, 0xDA]);
+        assert_eq!(jpeg_quality(&mut Cursor::new(jpeg)), Some(80));
+    }
+
+    #[test]
+    fn quantizer_mapping_spans_the_scale() {
+        assert_eq!(quality_from_quantizer(0), 100);
+        assert_eq!(quality_from_quantizer(127), 1);
+        // libwebp's default quality of 75 uses a base quantizer near 26.
+        let q75 = quality_from_quantizer(26) as i32;
+        assert!((q75 - 75).abs() <= 2, "{q75}");
+    }
+}
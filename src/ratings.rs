@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Highest star rating.
+pub const MAX_STARS: u8 = 5;
+
+/// Star ratings given while culling, keyed by absolute path. Kept in one
+/// small index under the user's data directory rather than as sidecars, so
+/// rating never writes into photo folders.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Ratings {
+    #[serde(default)]
+    ratings: BTreeMap<PathBuf, u8>,
+}
+
+impl Ratings {
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::index_path())
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = Self::index_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, contents)
+    }
+
+    /// Stars given to `path`; 0 when unrated.
+    pub fn get(&self, path: &Path) -> u8 {
+        self.ratings.get(path).copied().unwrap_or(0)
+    }
+
+    /// Rate `path` with `stars`, capped at `MAX_STARS`. 0 clears the rating.
+    /// Paths that aren't valid UTF-8 can't be written to the index and are
+    /// left unrated.
+    pub fn set(&mut self, path: &Path, stars: u8) {
+        if stars == 0 || path.to_str().is_none() {
+            self.ratings.remove(path);
+        } else {
+            self.ratings
+                .insert(path.to_path_buf(), stars.min(MAX_STARS));
+        }
+    }
+
+    fn index_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("iris")
+            .join("ratings.toml")
+    }
+}
+
+/// Filled stars for `stars`, e.g. "★★★". Empty when unrated.
+pub fn stars_label(stars: u8) -> String {
+    "★".repeat(stars.min(MAX_STARS) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_clears_and_ratings_are_capped() {
+        let mut ratings = Ratings::default();
+        let path = Path::new("/shoot/a.jpg");
+        ratings.set(path, 9);
+        assert_eq!(ratings.get(path), MAX_STARS);
+        ratings.set(path, 0);
+        assert_eq!(ratings.get(path), 0);
+        assert!(ratings.ratings.is_empty());
+        assert_eq!(stars_label(3), "★★★");
+        assert_eq!(stars_label(0), "");
+    }
+
+    #[test]
+    fn index_round_trips_through_toml() {
+        let mut ratings = Ratings::default();
+        ratings.set(Path::new("/shoot/a b.jpg"), 4);
+        ratings.set(Path::new("/shoot/c.png"), 1);
+        let text = toml::to_string(&ratings).unwrap();
+        let back: Ratings = toml::from_str(&text).unwrap();
+        assert_eq!(back.get(Path::new("/shoot/a b.jpg")), 4);
+        assert_eq!(back.get(Path::new("/shoot/c.png")), 1);
+    }
+}
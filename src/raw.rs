@@ -16,6 +16,43 @@ pub fn is_raw(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// Shortest edge an embedded preview needs to stand in for a full decode.
+/// Many cameras only embed a 160×120 thumbnail, which would look broken
+/// on screen.
+pub const MIN_PREVIEW_EDGE: u32 = 1000;
+
+fn read_exif(path: &Path) -> Option<exif::Exif> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut buf = std::io::BufReader::new(file);
+    exif::Reader::new().read_from_container(&mut buf).ok()
+}
+
+fn is_display_size(width: u32, height: u32) -> bool {
+    width.min(height) >= MIN_PREVIEW_EDGE
+}
+
+/// Whether `path` embeds a JPEG preview large enough to be shown instead
+/// of demosaicing. Only parses the JPEG header.
+pub fn has_display_preview(path: &Path) -> bool {
+    let Some(exif) = read_exif(path) else {
+        return false;
+    };
+    crate::orientation::embedded_preview_jpeg(&exif)
+        .and_then(|jpeg| {
+            image::ImageReader::with_format(std::io::Cursor::new(jpeg), image::ImageFormat::Jpeg)
+                .into_dimensions()
+                .ok()
+        })
+        .is_some_and(|(w, h)| is_display_size(w, h))
+}
+
+/// The camera's own JPEG rendering of the shot, oriented for display, if
+/// it is large enough to view. Far cheaper than `decode_raw`.
+pub fn decode_display_preview(path: &Path) -> Option<image::RgbaImage> {
+    let preview = crate::orientation::decode_embedded_preview(&read_exif(path)?)?;
+    is_display_size(preview.width(), preview.height()).then_some(preview)
+}
+
 pub struct RawImage {
     pub data: Vec<u16>,
     pub width: u32,
@@ -56,13 +93,7 @@ pub fn linear_16_to_srgb_8(data: &[u16], width: u32, height: u32) -> Vec<u8> {
 
     for pixel in data.chunks_exact(4) {
         for i in 0..3 {
-            let linear = pixel[i] as f32 / 65535.0;
-            let srgb = if linear <= 0.0031308 {
-                linear * 12.92
-            } else {
-                1.055 * linear.powf(1.0 / 2.4) - 0.055
-            }
-            .clamp(0.0, 1.0);
+            let srgb = crate::color::linear_to_srgb(pixel[i] as f32 / 65535.0);
             out.push((srgb * 255.0) as u8);
         }
         out.push(255u8);
@@ -108,6 +139,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn only_large_previews_replace_the_full_decode() {
+        assert!(!is_display_size(160, 120));
+        assert!(!is_display_size(1620, 999));
+        assert!(is_display_size(1620, 1080));
+        assert!(is_display_size(1080, 1620));
+    }
+
     #[test]
     fn linear_16_to_srgb_8_black_white_midpoint() {
         let data: Vec<u16> = vec![
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Order of the working set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Plain lexicographic path order.
+    #[default]
+    Name,
+    /// Like `Name`, but runs of digits compare by value (`img2` < `img10`).
+    Natural,
+    /// Oldest modification time first.
+    Modified,
+    /// Smallest file first.
+    Size,
+}
+
+impl SortOrder {
+    pub const ALL: [Self; 4] = [Self::Name, Self::Natural, Self::Modified, Self::Size];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Natural => "natural",
+            Self::Modified => "modified",
+            Self::Size => "size",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|o| o.id() == id)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Natural => "Natural Name",
+            Self::Modified => "Date Modified",
+            Self::Size => "File Size",
+        }
+    }
+}
+
+/// Sort paths in place. Ties (equal times or sizes, unreadable metadata)
+/// fall back to path order so the result is always deterministic.
+pub fn sort_paths(paths: &mut [PathBuf], order: SortOrder) {
+    match order {
+        SortOrder::Name => paths.sort(),
+        SortOrder::Natural => paths.sort_by(|a, b| natural_cmp(a, b).then_with(|| a.cmp(b))),
+        SortOrder::Modified => paths.sort_by_cached_key(|p| {
+            let modified = std::fs::metadata(p)
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (modified, p.clone())
+        }),
+        SortOrder::Size => paths.sort_by_cached_key(|p| {
+            let size = std::fs::metadata(p).map(|m| m.len()).unwrap_or(0);
+            (size, p.clone())
+        }),
+    }
+}
+
+/// Compare paths treating each run of ASCII digits as a number.
+pub fn natural_cmp(a: &Path, b: &Path) -> Ordering {
+    let a = a.to_string_lossy();
+    let b = b.to_string_lossy();
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = take_digits(&mut a);
+                let y = take_digits(&mut b);
+                // Compare by value without parsing: strip leading zeros,
+                // then longer means larger.
+                let xv = x.trim_start_matches('0');
+                let yv = y.trim_start_matches('0');
+                let ord = xv
+                    .len()
+                    .cmp(&yv.len())
+                    .then_with(|| xv.cmp(yv))
+                    .then_with(|| x.len().cmp(&y.len()));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ord = x.to_lowercase().cmp(y.to_lowercase()).then(x.cmp(&y));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        digits.push(c);
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(names: &[&str], order: SortOrder) -> Vec<String> {
+        let mut paths: Vec<PathBuf> = names.iter().map(PathBuf::from).collect();
+        sort_paths(&mut paths, order);
+        paths
+            .into_iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn natural_order_compares_numbers_by_value() {
+        assert_eq!(
+            sorted(&["img10.png", "img2.png", "img1.png"], SortOrder::Natural),
+            ["img1.png", "img2.png", "img10.png"]
+        );
+        assert_eq!(
+            sorted(&["img10.png", "img2.png", "img1.png"], SortOrder::Name),
+            ["img1.png", "img10.png", "img2.png"]
+        );
+    }
+
+    #[test]
+    fn natural_order_is_total_with_leading_zeros_and_case() {
+        assert_eq!(
+            sorted(
+                &["b.png", "A.png", "p002.png", "p2.png", "p1.png"],
+                SortOrder::Natural
+            ),
+            ["A.png", "b.png", "p1.png", "p2.png", "p002.png"]
+        );
+    }
+
+    #[test]
+    fn sort_order_ids_round_trip() {
+        for order in SortOrder::ALL {
+            assert_eq!(SortOrder::from_id(order.id()), Some(order));
+        }
+    }
+}
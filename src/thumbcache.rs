@@ -1,95 +1,359 @@
-use gtk4::prelude::*;
-use image::GenericImageView;
+//! On-disk thumbnail cache following the freedesktop.org Thumbnail Managing
+//! Standard: `$XDG_CACHE_HOME/thumbnails/{normal,large}/<md5 of file URI>.png`,
+//! tagged with the source URI and mtime. File managers read and write the
+//! same directory, so thumbnails are shared in both directions.
+
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::OpenOptionsExt;
 use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Longest edge of a "normal" size thumbnail.
+pub const THUMB_SIZE: u32 = 128;
 
-const THUMB_SIZE: u32 = 128;
+/// Longest edge of a "large" size thumbnail.
+pub const LARGE_THUMB_SIZE: u32 = 256;
 
-fn cache_dir() -> PathBuf {
-    dirs::cache_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("thumbnails")
-        .join("normal")
+/// The spec's thumbnail sizes, each cached in its own directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flavor {
+    Normal,
+    Large,
 }
 
-fn key_for(path: &Path) -> String {
-    let uri = format!(
-        "file://{}",
-        path.canonicalize()
-            .unwrap_or_else(|_| path.to_path_buf())
-            .to_string_lossy()
-    );
-    format!("{:x}", md5::compute(uri.as_bytes()))
+impl Flavor {
+    pub fn edge(self) -> u32 {
+        match self {
+            Self::Normal => THUMB_SIZE,
+            Self::Large => LARGE_THUMB_SIZE,
+        }
+    }
+
+    fn dir_name(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Large => "large",
+        }
+    }
 }
 
-fn thumb_path(path: &Path) -> PathBuf {
-    cache_dir().join(format!("{}.png", key_for(path)))
+/// How big the thumbnail strip draws its thumbnails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThumbSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
 }
 
-pub fn load(path: &Path) -> Option<gtk4::gdk::Texture> {
-    let src_meta = std::fs::metadata(path).ok()?;
-    let src_mtime = src_meta.modified().ok()?;
-    let thumb = thumb_path(path);
-    let thumb_meta = std::fs::metadata(&thumb).ok()?;
-    let thumb_mtime = thumb_meta.modified().ok()?;
-    if thumb_mtime < src_mtime {
+impl ThumbSize {
+    pub const ALL: [Self; 3] = [Self::Small, Self::Medium, Self::Large];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Small => "Small",
+            Self::Medium => "Medium",
+            Self::Large => "Large",
+        }
+    }
+
+    /// Edge of the square thumbnail cell, in logical pixels.
+    pub fn pixels(self) -> i32 {
+        match self {
+            Self::Small => 64,
+            Self::Medium => 90,
+            Self::Large => 160,
+        }
+    }
+
+    /// The smallest cached flavor that doesn't need upscaling for the cell.
+    pub fn flavor(self) -> Flavor {
+        match self {
+            Self::Large => Flavor::Large,
+            Self::Small | Self::Medium => Flavor::Normal,
+        }
+    }
+
+    /// The next size up (`delta` > 0) or down, stopping at either end.
+    pub fn step(self, delta: i32) -> Self {
+        let i = Self::ALL.iter().position(|&s| s == self).unwrap_or(1) as i32;
+        Self::ALL[(i + delta.signum()).clamp(0, Self::ALL.len() as i32 - 1) as usize]
+    }
+}
+
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+const KEY_URI: &str = "Thumb::URI";
+const KEY_MTIME: &str = "Thumb::MTime";
+
+/// Cached thumbnail for `path`, or a freshly generated one that is written
+/// back to the cache. Runs decoders, so call it off the GTK thread.
+pub fn load_or_generate(path: &Path) -> Option<RgbaImage> {
+    load_or_generate_flavor(path, Flavor::Normal)
+}
+
+/// Like `load_or_generate`, at the size of `flavor`.
+pub fn load_or_generate_flavor(path: &Path, flavor: Flavor) -> Option<RgbaImage> {
+    let source = Source::of(path, flavor)?;
+    if let Some(thumb) = load(&source) {
+        return Some(thumb);
+    }
+    let thumb = generate(path, flavor.edge())?;
+    // A read-only cache directory only costs us the next regeneration.
+    let _ = store(&source, &thumb);
+    Some(thumb)
+}
+
+/// Cached thumbnail for `path`, without generating one when it is missing
+/// or stale.
+pub fn load_cached(path: &Path) -> Option<RgbaImage> {
+    load(&Source::of(path, Flavor::Normal)?)
+}
+
+/// Identity of a source file as the spec records it.
+struct Source {
+    uri: String,
+    mtime: u64,
+    thumb_path: PathBuf,
+}
+
+impl Source {
+    fn of(path: &Path, flavor: Flavor) -> Option<Self> {
+        let path = path.canonicalize().ok()?;
+        let mtime = std::fs::metadata(&path)
+            .ok()?
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        let uri = file_uri(&path);
+        let thumb_path = dirs::cache_dir()?
+            .join("thumbnails")
+            .join(flavor.dir_name())
+            .join(format!("{:x}.png", md5::compute(uri.as_bytes())));
+        Some(Self {
+            uri,
+            mtime,
+            thumb_path,
+        })
+    }
+}
+
+/// A thumbnail is only valid if it was made from this exact file version;
+/// anything else (including entries without the tags) is regenerated.
+fn load(source: &Source) -> Option<RgbaImage> {
+    let bytes = std::fs::read(&source.thumb_path).ok()?;
+    let text = png_text_chunks(&bytes)?;
+    let tag = |key: &str| text.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+    if tag(KEY_URI) != Some(source.uri.as_str())
+        || tag(KEY_MTIME) != Some(source.mtime.to_string().as_str())
+    {
         return None;
     }
-    gtk4::gdk::Texture::from_file(&gtk4::gio::File::for_path(thumb)).ok()
+    let img = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png).ok()?;
+    Some(img.to_rgba8())
 }
 
-pub fn generate(path: &Path) -> Option<gtk4::gdk::Texture> {
-    let texture = if crate::raw::is_raw(path) {
-        let raw_img = crate::raw::decode_raw(path)?;
-        let rgba8 = crate::raw::linear_16_to_srgb_8(&raw_img.data, raw_img.width, raw_img.height);
-        let img = image::RgbaImage::from_raw(raw_img.width, raw_img.height, rgba8)?;
-        let thumb = image::imageops::resize(
-            &img,
-            THUMB_SIZE,
-            THUMB_SIZE,
-            image::imageops::FilterType::Triangle,
-        );
-        save_thumbnail_png(path, &thumb)?;
-        let bytes = gtk4::glib::Bytes::from_owned(thumb.into_raw());
-        gtk4::gdk::MemoryTexture::new(
-            THUMB_SIZE as i32,
-            THUMB_SIZE as i32,
-            gtk4::gdk::MemoryFormat::R8g8b8a8,
-            &bytes,
-            (THUMB_SIZE * 4) as usize,
-        )
-        .upcast::<gtk4::gdk::Texture>()
+/// Write atomically (temp file + rename) with owner-only permissions, as
+/// the spec requires, so readers never see a partial PNG.
+fn store(source: &Source, thumb: &RgbaImage) -> std::io::Result<()> {
+    let dir = source
+        .thumb_path
+        .parent()
+        .expect("thumbnail path has a parent");
+    std::fs::create_dir_all(dir)?;
+
+    let mut png = Vec::new();
+    thumb
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(std::io::Error::other)?;
+    let png = with_text_chunks(
+        &png,
+        &[
+            (KEY_URI, &source.uri),
+            (KEY_MTIME, &source.mtime.to_string()),
+        ],
+    )
+    .ok_or_else(|| std::io::Error::other("encoder produced an invalid PNG"))?;
+
+    let tmp = dir.join(format!(
+        ".iris-{}-{}.png",
+        std::process::id(),
+        source
+            .thumb_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+    ));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true).mode(0o600);
+    let result = options
+        .open(&tmp)
+        .and_then(|mut f| f.write_all(&png))
+        .and_then(|_| std::fs::rename(&tmp, &source.thumb_path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Decode `path` and scale it to fit `edge`, keeping its aspect ratio.
+fn generate(path: &Path, edge: u32) -> Option<RgbaImage> {
+    let img = if crate::raw::is_raw(path) {
+        // The embedded preview is far cheaper than a full RAW decode.
+        match crate::orientation::embedded_preview(path) {
+            Some(preview) => preview,
+            None => {
+                let raw_img = crate::raw::decode_raw(path)?;
+                let rgba8 =
+                    crate::raw::linear_16_to_srgb_8(&raw_img.data, raw_img.width, raw_img.height);
+                RgbaImage::from_raw(raw_img.width, raw_img.height, rgba8)?
+            }
+        }
     } else {
-        let img = image::open(path).ok()?.to_rgba8();
+        let img = crate::decode::to_display_rgba8(crate::decode::open(path)?);
         let (w, h) = img.dimensions();
         let icc = crate::color::extract_icc_profile(path);
-        let srgb_pixels = crate::color::rgba8_to_srgb_with_icc(img.as_raw(), w, h, icc.as_deref());
-        let corrected = image::RgbaImage::from_raw(w, h, srgb_pixels)?;
-        let thumb = image::imageops::resize(
-            &corrected,
-            THUMB_SIZE,
-            THUMB_SIZE,
-            image::imageops::FilterType::Triangle,
-        );
-        save_thumbnail_png(path, &thumb)?;
-        let bytes = gtk4::glib::Bytes::from_owned(thumb.into_raw());
-        gtk4::gdk::MemoryTexture::new(
-            THUMB_SIZE as i32,
-            THUMB_SIZE as i32,
-            gtk4::gdk::MemoryFormat::R8g8b8a8,
-            &bytes,
-            (THUMB_SIZE * 4) as usize,
-        )
-        .upcast::<gtk4::gdk::Texture>()
+        let corrected = crate::color::rgba8_to_srgb_with_icc(img.as_raw(), w, h, icc.as_deref());
+        RgbaImage::from_raw(w, h, corrected)?
     };
-    Some(texture)
+    Some(
+        image::DynamicImage::ImageRgba8(img)
+            .thumbnail(edge, edge)
+            .to_rgba8(),
+    )
+}
+
+/// `file://` URI with everything outside RFC 3986's unreserved set (and the
+/// path separator) percent-encoded, byte for byte.
+fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for &b in path.as_os_str().as_bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/".contains(&b) {
+            uri.push(b as char);
+        } else {
+            uri.push_str(&format!("%{b:02X}"));
+        }
+    }
+    uri
+}
+
+/// Walk the chunks of a PNG file, yielding `(type, data)` pairs. Stops at
+/// the first truncated chunk.
+fn png_chunks(png: &[u8]) -> Option<impl Iterator<Item = ([u8; 4], &[u8])>> {
+    let mut rest = png.strip_prefix(PNG_SIGNATURE.as_slice())?;
+    Some(std::iter::from_fn(move || {
+        let len = u32::from_be_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+        let kind: [u8; 4] = rest.get(4..8)?.try_into().ok()?;
+        let data = rest.get(8..8 + len)?;
+        rest = rest.get(12 + len..)?;
+        Some((kind, data))
+    }))
+}
+
+/// Keyword/value pairs of every `tEXt` chunk.
+fn png_text_chunks(png: &[u8]) -> Option<Vec<(String, String)>> {
+    let text = png_chunks(png)?
+        .filter(|(kind, _)| kind == b"tEXt")
+        .filter_map(|(_, data)| {
+            let nul = data.iter().position(|&b| b == 0)?;
+            // tEXt is Latin-1; keep ASCII exact and map the rest per byte.
+            let latin1 = |bytes: &[u8]| bytes.iter().map(|&b| b as char).collect::<String>();
+            Some((latin1(&data[..nul]), latin1(&data[nul + 1..])))
+        })
+        .collect();
+    Some(text)
+}
+
+/// Copy of `png` with a `tEXt` chunk per pair inserted right after `IHDR`.
+fn with_text_chunks(png: &[u8], pairs: &[(&str, &str)]) -> Option<Vec<u8>> {
+    let body = png.strip_prefix(PNG_SIGNATURE.as_slice())?;
+    let ihdr_len = u32::from_be_bytes(body.get(..4)?.try_into().ok()?) as usize;
+    let (ihdr, rest) = body.split_at_checked(12 + ihdr_len)?;
+
+    let mut out = Vec::with_capacity(png.len() + 256);
+    out.extend_from_slice(PNG_SIGNATURE);
+    out.extend_from_slice(ihdr);
+    for (key, value) in pairs {
+        let mut chunk = b"tEXt".to_vec();
+        chunk.extend_from_slice(key.as_bytes());
+        chunk.push(0);
+        chunk.extend_from_slice(value.as_bytes());
+        out.extend_from_slice(&((chunk.len() - 4) as u32).to_be_bytes());
+        out.extend_from_slice(&chunk);
+        out.extend_from_slice(&crc32(&chunk).to_be_bytes());
+    }
+    out.extend_from_slice(rest);
+    Some(out)
+}
+
+/// CRC-32 (ISO 3309) over chunk type and data, as PNG requires.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in bytes {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
 }
 
-fn save_thumbnail_png(path: &Path, img: &image::RgbaImage) -> Option<()> {
-    let thumb_path = thumb_path(path);
-    if let Some(parent) = thumb_path.parent() {
-        std::fs::create_dir_all(parent).ok()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_chunks_round_trip_and_png_stays_decodable() {
+        let img = RgbaImage::from_pixel(3, 2, image::Rgba([10, 20, 30, 255]));
+        let mut png = Vec::new();
+        img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let tagged = with_text_chunks(
+            &png,
+            &[(KEY_URI, "file:///a%20b.jpg"), (KEY_MTIME, "1700000000")],
+        )
+        .unwrap();
+
+        let text = png_text_chunks(&tagged).unwrap();
+        assert_eq!(
+            text,
+            [
+                (KEY_URI.to_string(), "file:///a%20b.jpg".to_string()),
+                (KEY_MTIME.to_string(), "1700000000".to_string()),
+            ]
+        );
+        let decoded = image::load_from_memory(&tagged).unwrap().to_rgba8();
+        assert_eq!(decoded, img);
+    }
+
+    #[test]
+    fn thumb_sizes_step_and_fit_their_flavor() {
+        assert_eq!(ThumbSize::Medium.step(1), ThumbSize::Large);
+        assert_eq!(ThumbSize::Large.step(1), ThumbSize::Large);
+        assert_eq!(ThumbSize::Medium.step(-3), ThumbSize::Small);
+        assert_eq!(ThumbSize::Small.step(0), ThumbSize::Small);
+        for size in ThumbSize::ALL {
+            assert!(size.flavor().edge() as i32 >= size.pixels(), "{size:?}");
+        }
+    }
+
+    #[test]
+    fn crc_matches_reference_value() {
+        // CRC of the IEND chunk type, present in every PNG.
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn uri_escapes_reserved_bytes() {
+        assert_eq!(
+            file_uri(Path::new("/home/me/My Photos/été#1.jpg")),
+            "file:///home/me/My%20Photos/%C3%A9t%C3%A9%231.jpg"
+        );
     }
-    img.save_with_format(thumb_path, image::ImageFormat::Png)
-        .ok()?;
-    Some(())
 }
@@ -1,12 +1,60 @@
 use glam::Vec2;
 
-#[derive(Debug, Clone, Copy)]
+/// Zoom limits for interactive zooming, relative to the whole-image fit.
+pub const MIN_ZOOM: f32 = 0.05;
+pub const MAX_ZOOM: f32 = 40.0;
+
+/// Image left on screen, in pixels, however far a bounded pan goes; all of
+/// it for smaller images.
+pub const PAN_KEEP_VISIBLE_PX: f32 = 64.0;
+
+/// Furthest the straighten tool turns an image either way, in degrees.
+pub const MAX_STRAIGHTEN_DEGREES: f32 = 45.0;
+
+/// How the zoom level is derived when the viewport or image changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitMode {
+    /// Zoom is user-controlled; `zoom == 1.0` shows the whole image.
+    Free,
+    /// Fill the viewport horizontally; only the vertical axis pans.
+    Width,
+    /// Fill the viewport vertically; only the horizontal axis pans.
+    Height,
+    /// One image pixel per screen pixel; pans freely.
+    ActualSize,
+}
+
+/// Per-image display transform: clockwise rotation, then mirroring in
+/// screen space.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImageTransform {
+    pub rotation_degrees: i32,
+    pub flip_h: bool,
+    pub flip_v: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Camera {
     pub position: Vec2,
     pub zoom: f32,
     pub rotation: f32,
+    /// Mirror horizontally / vertically in screen space, after rotation.
+    pub flip_h: bool,
+    pub flip_v: bool,
+    /// Fine clockwise rotation in radians, applied on screen after the
+    /// quarter turns and mirroring to level a crooked horizon.
+    pub straighten: f32,
     pub viewport_width: u32,
     pub viewport_height: u32,
+    pub fit_mode: FitMode,
+    pub image_width: f32,
+    pub image_height: f32,
+    /// Let pans and zooms move the image entirely off screen. Off, at least
+    /// `PAN_KEEP_VISIBLE_PX` of it always stays in view.
+    pub free_pan: bool,
+    /// Device pixels per logical pixel of the viewport (2 on a HiDPI
+    /// display). Actual size and zoom percentages count device pixels.
+    pub scale_factor: f32,
 }
 
 impl Camera {
@@ -15,18 +63,411 @@ impl Camera {
             position: Vec2::ZERO,
             zoom: 1.0,
             rotation: 0.0,
+            flip_h: false,
+            flip_v: false,
+            straighten: 0.0,
             viewport_width: 1,
             viewport_height: 1,
+            fit_mode: FitMode::Free,
+            image_width: 0.0,
+            image_height: 0.0,
+            free_pan: false,
+            scale_factor: 1.0,
         }
     }
 
     pub fn set_viewport_size(&mut self, width: u32, height: u32) {
         self.viewport_width = width.max(1);
         self.viewport_height = height.max(1);
+        self.refit();
+    }
+
+    /// Follow the display's scale factor, keeping actual size at one image
+    /// pixel per device pixel.
+    pub fn set_scale_factor(&mut self, scale: f32) {
+        self.scale_factor = scale.max(1.0);
+        self.refit();
     }
 
     pub fn set_rotation_degrees(&mut self, degrees: f32) {
         self.rotation = degrees.to_radians();
+        self.refit();
+    }
+
+    /// Turn the image by `degrees` (clamped to ±`MAX_STRAIGHTEN_DEGREES`)
+    /// on top of its quarter turns. The fit shrinks so the rotated corners
+    /// stay in view.
+    pub fn set_straighten_degrees(&mut self, degrees: f32) {
+        self.straighten = degrees
+            .clamp(-MAX_STRAIGHTEN_DEGREES, MAX_STRAIGHTEN_DEGREES)
+            .to_radians();
+        self.refit();
+    }
+
+    pub fn set_transform(&mut self, transform: ImageTransform) {
+        self.flip_h = transform.flip_h;
+        self.flip_v = transform.flip_v;
+        self.set_rotation_degrees(transform.rotation_degrees as f32);
+    }
+
+    /// Record the source image size so fit presets can be recomputed when
+    /// the viewport changes. A new image under a fit preset starts at its
+    /// leading edge, like turning a page.
+    pub fn set_image_size(&mut self, width: u32, height: u32) {
+        self.image_width = width as f32;
+        self.image_height = height as f32;
+        match self.fit_mode {
+            FitMode::Free | FitMode::ActualSize => self.refit(),
+            FitMode::Width => self.fit_width(),
+            FitMode::Height => self.fit_height(),
+        }
+    }
+
+    fn is_sideways(&self) -> bool {
+        let deg = ((self.rotation.to_degrees().round() as i32) % 360 + 360) % 360;
+        deg == 90 || deg == 270
+    }
+
+    /// Image size after rotation, in image pixels.
+    fn effective_image_size(&self) -> (f32, f32) {
+        if self.is_sideways() {
+            (self.image_height, self.image_width)
+        } else {
+            (self.image_width, self.image_height)
+        }
+    }
+
+    /// Logical screen pixels per image pixel at `zoom == 1.0` (the
+    /// whole-image fit).
+    pub fn fit_pixel_ratio(&self) -> Option<f32> {
+        let (w, h) = self.effective_image_size();
+        if w <= 0.0 || h <= 0.0 {
+            return None;
+        }
+        let vw = self.viewport_width as f32;
+        let vh = self.viewport_height as f32;
+        Some((vw / w).min(vh / h) * self.straighten_shrink(w, h))
+    }
+
+    /// Device pixels per image pixel at `zoom == 1.0`.
+    fn device_pixel_ratio(&self) -> Option<f32> {
+        Some(self.fit_pixel_ratio()? * self.scale_factor)
+    }
+
+    /// How much smaller than the plain fit a `w`×`h` image (after quarter
+    /// turns) is drawn so its straightened bounding box fits the viewport.
+    fn straighten_shrink(&self, w: f32, h: f32) -> f32 {
+        if self.straighten == 0.0 {
+            return 1.0;
+        }
+        let vw = self.viewport_width as f32;
+        let vh = self.viewport_height as f32;
+        let (s, c) = self.straighten.sin_cos();
+        let (s, c) = (s.abs(), c.abs());
+        let (box_w, box_h) = (w * c + h * s, w * s + h * c);
+        (vw / box_w).min(vh / box_h) / (vw / w).min(vh / h)
+    }
+
+    /// The shader's straightening of a point in NDC: `angle` clockwise
+    /// about the center, measured in square screen pixels rather than the
+    /// viewport's stretched NDC units.
+    fn rotate_on_screen(&self, p: Vec2, angle: f32) -> Vec2 {
+        if angle == 0.0 {
+            return p;
+        }
+        let aspect = self.viewport_width as f32 / self.viewport_height as f32;
+        let (s, c) = angle.sin_cos();
+        let q = Vec2::new(p.x * aspect, p.y);
+        Vec2::new((c * q.x + s * q.y) / aspect, -s * q.x + c * q.y)
+    }
+
+    /// Pixel of the displayed image, as it would be exported, under `ndc`:
+    /// rotated, mirrored and straightened onto a canvas holding the whole
+    /// straightened image. The straightened counterpart of `ndc_to_image`.
+    pub fn ndc_to_straightened(&self, ndc: Vec2) -> Option<(Vec2, (f32, f32))> {
+        let ratio = self.fit_pixel_ratio()?;
+        let (w, h) = self.effective_image_size();
+        let (s, c) = self.straighten.sin_cos();
+        let (s, c) = (s.abs(), c.abs());
+        let (box_w, box_h) = (w * c + h * s, w * s + h * c);
+        let p = (ndc - self.position) / self.zoom;
+        let px = Vec2::new(
+            p.x * self.viewport_width as f32 / 2.0,
+            -p.y * self.viewport_height as f32 / 2.0,
+        ) / ratio;
+        Some((px + Vec2::new(box_w, box_h) / 2.0, (box_w, box_h)))
+    }
+
+    /// Displayed size relative to the source, in percent: 100 at actual
+    /// size, 50 when each device pixel covers two image pixels.
+    pub fn zoom_percent(&self) -> Option<f32> {
+        Some(self.device_pixel_ratio()? * self.zoom * 100.0)
+    }
+
+    /// Zoom to `percent` of actual size (see `zoom_percent`), centered and
+    /// kept within the interactive zoom limits.
+    pub fn set_zoom_percent(&mut self, percent: f32) {
+        let Some(ratio) = self.device_pixel_ratio() else {
+            return;
+        };
+        self.zoom = (percent / 100.0 / ratio).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.position = Vec2::ZERO;
+        self.fit_mode = FitMode::Free;
+    }
+
+    /// Half extents of the displayed image in NDC at the current zoom.
+    fn half_extent_ndc(&self) -> Option<(f32, f32)> {
+        let ratio = self.fit_pixel_ratio()?;
+        let (w, h) = self.effective_image_size();
+        let vw = self.viewport_width as f32;
+        let vh = self.viewport_height as f32;
+        Some((w * ratio * self.zoom / vw, h * ratio * self.zoom / vh))
+    }
+
+    /// Show the whole image, centered. `zoom == 1.0` is the whole-image fit
+    /// by construction, so this stays correct across resizes and rotations.
+    pub fn fit_to_viewport(&mut self) {
+        self.fit_mode = FitMode::Free;
+        self.zoom = 1.0;
+        self.position = Vec2::ZERO;
+    }
+
+    /// Map one image pixel to one device pixel, centered. Re-applied when
+    /// the viewport or image changes.
+    pub fn actual_size(&mut self) {
+        self.fit_mode = FitMode::ActualSize;
+        self.position = Vec2::ZERO;
+        self.refit();
+    }
+
+    /// Double-click behaviour: from the whole-image fit, go to 1:1 keeping
+    /// the image point under `cursor_ndc` fixed; from any other view, fit.
+    pub fn toggle_actual_size_at(&mut self, cursor_ndc: Vec2) {
+        let Some(ratio) = self.device_pixel_ratio() else {
+            return;
+        };
+        let at_fit = self.fit_mode == FitMode::Free
+            && (self.zoom - 1.0).abs() < 1e-4
+            && self.position.length() < 1e-4;
+        if at_fit {
+            self.zoom_at(cursor_ndc, 1.0 / ratio);
+            self.fit_mode = FitMode::ActualSize;
+        } else {
+            self.fit_to_viewport();
+        }
+    }
+
+    /// Fill the viewport horizontally and align the image to its top edge.
+    pub fn fit_width(&mut self) {
+        self.fit_mode = FitMode::Width;
+        self.position = Vec2::ZERO;
+        self.refit();
+        if let Some((_, half_h)) = self.half_extent_ndc() {
+            self.position.y = -(half_h - 1.0).max(0.0);
+        }
+    }
+
+    /// Fill the viewport vertically and align the image to its left edge.
+    pub fn fit_height(&mut self) {
+        self.fit_mode = FitMode::Height;
+        self.position = Vec2::ZERO;
+        self.refit();
+        if let Some((half_w, _)) = self.half_extent_ndc() {
+            self.position.x = (half_w - 1.0).max(0.0);
+        }
+    }
+
+    /// Multiply the zoom by `factor` while keeping the image point under
+    /// `cursor_ndc` fixed on screen. Leaves any fit preset.
+    pub fn zoom_at(&mut self, cursor_ndc: Vec2, factor: f32) {
+        let new_zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        // Screen = image * zoom + position, so the point under the cursor is
+        // (cursor - position) / zoom before and after the change.
+        self.position = cursor_ndc - (cursor_ndc - self.position) * (new_zoom / self.zoom);
+        self.zoom = new_zoom;
+        self.fit_mode = FitMode::Free;
+        self.keep_in_view();
+    }
+
+    /// Keyboard zoom, with no cursor to anchor to: multiply the zoom by
+    /// `factor` keeping the image point at the viewport center fixed. Along
+    /// an axis where the image then fits, it is centered instead, so zooming
+    /// out never leaves a panned image stranded off to one side.
+    pub fn zoom_centered(&mut self, factor: f32) {
+        self.zoom_at(Vec2::ZERO, factor);
+        let (overflow_x, overflow_y) = self.overflows();
+        if !overflow_x {
+            self.position.x = 0.0;
+        }
+        if !overflow_y {
+            self.position.y = 0.0;
+        }
+    }
+
+    /// Pan so the image follows a drag 1:1: `start` is the position at drag
+    /// begin and `delta_px` the pointer offset since then, in screen pixels.
+    pub fn pan_from(&mut self, start: Vec2, delta_px: Vec2) {
+        let vw = self.viewport_width as f32;
+        let vh = self.viewport_height as f32;
+        // Position is applied after zoom, so one screen pixel is always
+        // 2/size NDC regardless of zoom. NDC y points up.
+        self.position = start + Vec2::new(delta_px.x / vw * 2.0, -delta_px.y / vh * 2.0);
+        self.clamp_to_fit_axis();
+        self.keep_in_view();
+    }
+
+    /// Switch free panning on or off, pulling the image back into view if
+    /// it is now out of bounds.
+    pub fn set_free_pan(&mut self, free: bool) {
+        self.free_pan = free;
+        self.keep_in_view();
+    }
+
+    /// Unless panning is free, move the image just far enough that
+    /// `PAN_KEEP_VISIBLE_PX` of it (or all of a smaller one) is on screen.
+    fn keep_in_view(&mut self) {
+        if self.free_pan {
+            return;
+        }
+        let Some((half_w, half_h)) = self.half_extent_ndc() else {
+            return;
+        };
+        // The image spans position ± half extent; the screen spans ±1.
+        let bound = |half: f32, size: u32| {
+            let keep = (PAN_KEEP_VISIBLE_PX / size as f32 * 2.0)
+                .min(2.0 * half)
+                .min(1.0 + half);
+            1.0 + half - keep
+        };
+        let limit = Vec2::new(
+            bound(half_w, self.viewport_width),
+            bound(half_h, self.viewport_height),
+        );
+        self.position = self.position.clamp(-limit, limit);
+    }
+
+    /// Whether the displayed image is larger than the viewport along x and y.
+    pub fn overflows(&self) -> (bool, bool) {
+        match self.half_extent_ndc() {
+            Some((half_w, half_h)) => (half_w > 1.0 + 1e-4, half_h > 1.0 + 1e-4),
+            None => (false, false),
+        }
+    }
+
+    /// Move the view by `delta_px` screen pixels, stopping once an image
+    /// edge reaches the matching viewport edge. A view already past that
+    /// point (e.g. after a free drag) is never pulled back.
+    pub fn pan_by(&mut self, delta_px: Vec2) {
+        let Some((half_w, half_h)) = self.half_extent_ndc() else {
+            return;
+        };
+        let vw = self.viewport_width as f32;
+        let vh = self.viewport_height as f32;
+        let limit = Vec2::new((half_w - 1.0).max(0.0), (half_h - 1.0).max(0.0));
+        let moved = self.position + Vec2::new(delta_px.x / vw * 2.0, -delta_px.y / vh * 2.0);
+        self.position = moved.clamp((-limit).min(self.position), limit.max(self.position));
+    }
+
+    /// Source image pixel under the NDC point `ndc`, undoing the vertex
+    /// shader's scale, rotation, flip, zoom and pan. Points off the image
+    /// map outside `0..image_width` / `0..image_height`.
+    pub fn ndc_to_image(&self, ndc: Vec2) -> Option<Vec2> {
+        if self.image_width <= 0.0 || self.image_height <= 0.0 {
+            return None;
+        }
+        let [sx, sy] = self.fit_scale(self.image_width, self.image_height);
+        let flip = Vec2::new(
+            if self.flip_h { -1.0 } else { 1.0 },
+            if self.flip_v { -1.0 } else { 1.0 },
+        );
+        let p = self.rotate_on_screen((ndc - self.position) / self.zoom, -self.straighten) * flip;
+        // Transpose of the shader's clockwise `rotate2d`.
+        let (s, c) = self.rotation.sin_cos();
+        let corner = Vec2::new(c * p.x - s * p.y, s * p.x + c * p.y) / Vec2::new(sx, sy);
+        Some(Vec2::new(
+            (corner.x + 1.0) * 0.5 * self.image_width,
+            (1.0 - corner.y) * 0.5 * self.image_height,
+        ))
+    }
+
+    /// NDC point showing source image pixel `point`; the inverse of
+    /// `ndc_to_image`.
+    fn image_to_ndc(&self, point: Vec2) -> Option<Vec2> {
+        if self.image_width <= 0.0 || self.image_height <= 0.0 {
+            return None;
+        }
+        let [sx, sy] = self.fit_scale(self.image_width, self.image_height);
+        let corner = Vec2::new(
+            point.x / self.image_width * 2.0 - 1.0,
+            1.0 - point.y / self.image_height * 2.0,
+        );
+        let p = corner * Vec2::new(sx, sy);
+        let (s, c) = self.rotation.sin_cos();
+        let p = Vec2::new(c * p.x + s * p.y, -s * p.x + c * p.y);
+        let flip = Vec2::new(
+            if self.flip_h { -1.0 } else { 1.0 },
+            if self.flip_v { -1.0 } else { 1.0 },
+        );
+        Some(self.rotate_on_screen(p * flip, self.straighten) * self.zoom + self.position)
+    }
+
+    /// Pan so source image pixel `point` sits in the middle of the viewport,
+    /// keeping the zoom.
+    pub fn center_on(&mut self, point: Vec2) {
+        if let Some(ndc) = self.image_to_ndc(point) {
+            self.position -= ndc;
+            self.clamp_to_fit_axis();
+        }
+    }
+
+    /// Scroll a fit-width page by `delta` NDC units (positive moves toward
+    /// the bottom of the image). Returns true when the page was already
+    /// resting on its bottom edge, i.e. the input overscrolled.
+    pub fn scroll_fit_width(&mut self, delta: f32) -> bool {
+        let Some((_, half_h)) = self.half_extent_ndc() else {
+            return false;
+        };
+        let limit = (half_h - 1.0).max(0.0);
+        let at_bottom = self.position.y >= limit - 1e-4;
+        self.position.y += delta;
+        self.clamp_to_fit_axis();
+        delta > 0.0 && at_bottom
+    }
+
+    /// Recompute the zoom for the active fit preset. No-op in `FitMode::Free`.
+    pub fn refit(&mut self) {
+        let Some(ratio) = self.fit_pixel_ratio() else {
+            return;
+        };
+        let (w, h) = self.effective_image_size();
+        self.zoom = match self.fit_mode {
+            FitMode::Free => return,
+            FitMode::Width => self.viewport_width as f32 / (w * ratio),
+            FitMode::Height => self.viewport_height as f32 / (h * ratio),
+            FitMode::ActualSize => 1.0 / (ratio * self.scale_factor),
+        };
+        self.clamp_to_fit_axis();
+    }
+
+    /// Lock panning to the overflow axis of a fit preset and keep the image
+    /// edges from leaving the viewport along it.
+    pub fn clamp_to_fit_axis(&mut self) {
+        let Some((half_w, half_h)) = self.half_extent_ndc() else {
+            return;
+        };
+        match self.fit_mode {
+            FitMode::Free | FitMode::ActualSize => {}
+            FitMode::Width => {
+                let limit = (half_h - 1.0).max(0.0);
+                self.position.x = 0.0;
+                self.position.y = self.position.y.clamp(-limit, limit);
+            }
+            FitMode::Height => {
+                let limit = (half_w - 1.0).max(0.0);
+                self.position.y = 0.0;
+                self.position.x = self.position.x.clamp(-limit, limit);
+            }
+        }
     }
 
     pub fn fit_scale(&self, image_width: f32, image_height: f32) -> [f32; 2] {
@@ -50,8 +491,9 @@ impl Camera {
 
         let eff_aspect = eff_w / eff_h;
         let ratio = eff_aspect / viewport_aspect;
+        let shrink = self.straighten_shrink(eff_w, eff_h);
 
-        if is_sideways {
+        let [x, y] = if is_sideways {
             if ratio <= 1.0 {
                 [1.0, ratio]
             } else {
@@ -63,10 +505,19 @@ impl Camera {
             } else {
                 [1.0, 1.0 / ratio]
             }
-        }
+        };
+        [x * shrink, y * shrink]
     }
 }
 
+/// A typed zoom level such as "150%", "150" or " 33.3 % ", in percent.
+/// `None` unless it is a positive number.
+pub fn parse_zoom_percent(text: &str) -> Option<f32> {
+    let number = text.trim().trim_end_matches('%').trim_end();
+    let percent: f32 = number.parse().ok()?;
+    (percent.is_finite() && percent > 0.0).then_some(percent)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,6 +530,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn typed_zoom_is_relative_to_actual_size_and_clamped() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1000, 1000);
+        cam.set_image_size(2000, 1000);
+        cam.position = Vec2::new(0.3, -0.2);
+
+        cam.set_zoom_percent(150.0);
+        approx_eq(cam.zoom_percent().unwrap(), 150.0);
+        assert_eq!(cam.position, Vec2::ZERO);
+
+        cam.set_zoom_percent(100_000.0);
+        approx_eq(cam.zoom, MAX_ZOOM);
+    }
+
+    #[test]
+    fn parses_typed_zoom_levels() {
+        assert_eq!(parse_zoom_percent("150%"), Some(150.0));
+        assert_eq!(parse_zoom_percent(" 33.5 % "), Some(33.5));
+        assert_eq!(parse_zoom_percent("200"), Some(200.0));
+        assert_eq!(parse_zoom_percent("0"), None);
+        assert_eq!(parse_zoom_percent("-50%"), None);
+        assert_eq!(parse_zoom_percent("big"), None);
+    }
+
     #[test]
     fn fit_scale_landscape_image_in_landscape_viewport() {
         let mut cam = Camera::new();
@@ -133,6 +609,395 @@ mod tests {
         approx_eq(scale[1], 0.35555556);
     }
 
+    #[test]
+    fn fit_width_fills_viewport_horizontally_for_tall_image() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(1000, 4000);
+        cam.fit_width();
+
+        // Whole-fit shows the image 900/4000 px per px; width fill needs 1.6.
+        approx_eq(cam.zoom, 1600.0 / (1000.0 * 0.225));
+        approx_eq(cam.position.x, 0.0);
+        // Top-aligned: the upper image edge sits on the viewport's top edge.
+        let half_h = 4000.0 * 0.225 * cam.zoom / 900.0;
+        approx_eq(cam.position.y + half_h, 1.0);
+    }
+
+    #[test]
+    fn fit_width_locks_horizontal_pan_and_clamps_vertical() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(1000, 4000);
+        cam.fit_width();
+
+        cam.position.x = 0.5;
+        cam.position.y = -100.0;
+        cam.clamp_to_fit_axis();
+
+        let half_h = 4000.0 * 0.225 * cam.zoom / 900.0;
+        approx_eq(cam.position.x, 0.0);
+        approx_eq(cam.position.y, -(half_h - 1.0));
+    }
+
+    #[test]
+    fn fit_height_refits_after_resize() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(8000, 1000);
+        cam.fit_height();
+        let before = cam.zoom;
+
+        cam.set_viewport_size(800, 900);
+        assert!(
+            cam.zoom > before,
+            "narrower viewport must zoom further to fill height"
+        );
+    }
+
+    #[test]
+    fn zoom_at_keeps_point_under_cursor_fixed() {
+        let mut cam = Camera::new();
+        cam.position = Vec2::new(0.2, -0.1);
+        cam.zoom = 2.0;
+        let cursor = Vec2::new(0.5, 0.5);
+        let before = (cursor - cam.position) / cam.zoom;
+
+        cam.zoom_at(cursor, 1.5);
+
+        approx_eq(cam.zoom, 3.0);
+        let after = (cursor - cam.position) / cam.zoom;
+        approx_eq(after.x, before.x);
+        approx_eq(after.y, before.y);
+    }
+
+    #[test]
+    fn pan_from_tracks_pointer_one_to_one() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(800, 600);
+        cam.zoom = 3.0;
+        let start = Vec2::new(0.1, 0.2);
+
+        // The image point under the pointer at (200, 150) px must sit under
+        // the pointer again at (300, 100) px.
+        let to_ndc = |x: f32, y: f32| Vec2::new(x / 800.0 * 2.0 - 1.0, 1.0 - y / 600.0 * 2.0);
+        let image_point = (to_ndc(200.0, 150.0) - start) / cam.zoom;
+
+        cam.pan_from(start, Vec2::new(100.0, -50.0));
+        let on_screen = image_point * cam.zoom + cam.position;
+        let expected = to_ndc(300.0, 100.0);
+        approx_eq(on_screen.x, expected.x);
+        approx_eq(on_screen.y, expected.y);
+    }
+
+    #[test]
+    fn zoom_centered_keeps_view_center_and_recenters_when_fitting() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(800, 600);
+        cam.set_image_size(800, 600);
+        cam.zoom = 4.0;
+        cam.position = Vec2::new(1.5, -1.0);
+        let before = -cam.position / cam.zoom;
+
+        cam.zoom_centered(1.25);
+        approx_eq(cam.zoom, 5.0);
+        let after = -cam.position / cam.zoom;
+        approx_eq(after.x, before.x);
+        approx_eq(after.y, before.y);
+
+        // Back below the fit, the image is centered rather than drifting.
+        cam.zoom_centered(0.1);
+        approx_eq(cam.zoom, 0.5);
+        approx_eq(cam.position.x, 0.0);
+        approx_eq(cam.position.y, 0.0);
+    }
+
+    #[test]
+    fn bounded_pan_keeps_part_of_the_image_on_screen() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(800, 600);
+        cam.set_image_size(800, 600);
+        cam.zoom = 2.0;
+
+        // Dragging far right leaves the image's left 64 px on screen.
+        cam.pan_from(Vec2::ZERO, Vec2::new(10_000.0, 0.0));
+        let left_edge_px = (cam.position.x - 2.0 + 1.0) / 2.0 * 800.0;
+        approx_eq(left_edge_px, 800.0 - PAN_KEEP_VISIBLE_PX);
+
+        // A small image can't leave the screen at all.
+        cam.zoom = 0.05;
+        cam.pan_from(Vec2::ZERO, Vec2::new(0.0, 10_000.0));
+        approx_eq(cam.position.y, -1.0 + 0.05);
+
+        cam.set_free_pan(true);
+        cam.pan_from(Vec2::ZERO, Vec2::new(10_000.0, 0.0));
+        assert!(cam.position.x > 20.0);
+        cam.set_free_pan(false);
+        assert!(cam.position.x < 1.0);
+    }
+
+    #[test]
+    fn zoom_at_clamps_to_limits() {
+        let mut cam = Camera::new();
+        cam.zoom_at(Vec2::ZERO, 1000.0);
+        approx_eq(cam.zoom, MAX_ZOOM);
+        cam.zoom_at(Vec2::new(0.3, 0.3), 0.0001);
+        approx_eq(cam.zoom, MIN_ZOOM);
+    }
+
+    #[test]
+    fn scroll_fit_width_reports_overscroll_only_at_bottom_edge() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1000, 1000);
+        cam.set_image_size(1000, 3000);
+        cam.fit_width();
+
+        // Page is 3 viewports tall: ±2 NDC of travel from top to bottom.
+        assert!(!cam.scroll_fit_width(5.0));
+        approx_eq(cam.position.y, 2.0);
+        assert!(cam.scroll_fit_width(0.5));
+        approx_eq(cam.position.y, 2.0);
+        assert!(!cam.scroll_fit_width(-0.5));
+    }
+
+    #[test]
+    fn new_image_in_fit_width_starts_at_top() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1000, 1000);
+        cam.set_image_size(1000, 3000);
+        cam.fit_width();
+        cam.scroll_fit_width(10.0);
+
+        cam.set_image_size(1000, 5000);
+        approx_eq(cam.position.y, -4.0);
+    }
+
+    #[test]
+    fn actual_size_maps_image_pixels_one_to_one_across_resizes() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(4000, 3000);
+        cam.actual_size();
+
+        // Whole-fit shows 0.3 screen px per image px.
+        approx_eq(cam.zoom, 1.0 / 0.3);
+        approx_eq(cam.fit_pixel_ratio().unwrap() * cam.zoom, 1.0);
+
+        cam.set_viewport_size(800, 900);
+        approx_eq(cam.fit_pixel_ratio().unwrap() * cam.zoom, 1.0);
+    }
+
+    #[test]
+    fn actual_size_counts_device_pixels_on_hidpi() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_scale_factor(2.0);
+        cam.set_image_size(4000, 3000);
+        // The fit shows 0.3 logical = 0.6 device px per image px.
+        approx_eq(cam.zoom_percent().unwrap(), 60.0);
+
+        cam.actual_size();
+        approx_eq(cam.zoom, 1.0 / 0.6);
+        approx_eq(cam.zoom_percent().unwrap(), 100.0);
+
+        cam.set_zoom_percent(200.0);
+        approx_eq(cam.zoom, 2.0 / 0.6);
+
+        cam.fit_to_viewport();
+        cam.toggle_actual_size_at(Vec2::ZERO);
+        approx_eq(cam.zoom, 1.0 / 0.6);
+
+        // Back on a 1x display, actual size follows.
+        cam.set_scale_factor(1.0);
+        approx_eq(cam.zoom, 1.0 / 0.3);
+    }
+
+    #[test]
+    fn zoom_percent_is_relative_to_source_pixels() {
+        let mut cam = Camera::new();
+        assert_eq!(cam.zoom_percent(), None);
+
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(4000, 3000);
+        approx_eq(cam.zoom_percent().unwrap(), 30.0);
+
+        cam.actual_size();
+        approx_eq(cam.zoom_percent().unwrap(), 100.0);
+
+        cam.zoom_at(Vec2::ZERO, 2.5);
+        approx_eq(cam.zoom_percent().unwrap(), 250.0);
+    }
+
+    #[test]
+    fn double_click_toggles_actual_size_around_cursor() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(4000, 3000);
+
+        let cursor = Vec2::new(0.5, -0.25);
+        cam.toggle_actual_size_at(cursor);
+        assert_eq!(cam.fit_mode, FitMode::ActualSize);
+        approx_eq(cam.zoom_percent().unwrap(), 100.0);
+        // The image point under the cursor (0.5, -0.25 at zoom 1) stays put.
+        let anchored = (cursor - cam.position) / cam.zoom;
+        approx_eq(anchored.x, 0.5);
+        approx_eq(anchored.y, -0.25);
+
+        cam.toggle_actual_size_at(cursor);
+        assert_eq!(cam.fit_mode, FitMode::Free);
+        approx_eq(cam.zoom, 1.0);
+        approx_eq(cam.position.length(), 0.0);
+    }
+
+    #[test]
+    fn pan_by_stops_at_image_edges() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1000, 1000);
+        cam.set_image_size(1000, 1000);
+        assert_eq!(cam.overflows(), (false, false));
+
+        cam.zoom = 2.0;
+        assert_eq!(cam.overflows(), (true, true));
+
+        // Half a viewport reaches the right edge; panning stops there.
+        cam.pan_by(Vec2::new(-500.0, 0.0));
+        approx_eq(cam.position.x, -1.0);
+        cam.pan_by(Vec2::new(-500.0, 0.0));
+        approx_eq(cam.position.x, -1.0);
+
+        cam.pan_by(Vec2::new(0.0, 5000.0));
+        approx_eq(cam.position.y, -1.0);
+    }
+
+    /// The vertex shader's transform of a quad corner, for checking its
+    /// inverse.
+    fn corner_to_ndc(cam: &Camera, corner: Vec2) -> Vec2 {
+        let [sx, sy] = cam.fit_scale(cam.image_width, cam.image_height);
+        let p = corner * Vec2::new(sx, sy);
+        let (s, c) = cam.rotation.sin_cos();
+        let p = Vec2::new(c * p.x + s * p.y, -s * p.x + c * p.y);
+        let flip = Vec2::new(
+            if cam.flip_h { -1.0 } else { 1.0 },
+            if cam.flip_v { -1.0 } else { 1.0 },
+        );
+        let aspect = cam.viewport_width as f32 / cam.viewport_height as f32;
+        let (s, c) = cam.straighten.sin_cos();
+        let q = p * flip * Vec2::new(aspect, 1.0);
+        let p = Vec2::new((c * q.x + s * q.y) / aspect, -s * q.x + c * q.y);
+        p * cam.zoom + cam.position
+    }
+
+    #[test]
+    fn straightened_image_stays_in_view_and_inverts() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(4000, 3000);
+        cam.set_straighten_degrees(10.0);
+
+        for corner in [(-1.0, 1.0), (1.0, 1.0), (1.0, -1.0), (-1.0, -1.0)] {
+            let ndc = corner_to_ndc(&cam, Vec2::from(corner));
+            assert!(
+                ndc.x.abs() <= 1.0 + 1e-4 && ndc.y.abs() <= 1.0 + 1e-4,
+                "{ndc}"
+            );
+        }
+
+        cam.zoom = 1.7;
+        cam.position = Vec2::new(-0.2, 0.4);
+        let inner = cam.ndc_to_image(corner_to_ndc(&cam, Vec2::new(0.5, -0.5)));
+        let inner = inner.unwrap();
+        approx_eq(inner.x / 4000.0, 0.75);
+        approx_eq(inner.y / 3000.0, 0.75);
+
+        // The image center is the middle of the straightened canvas.
+        let (center, (box_w, box_h)) = cam.ndc_to_straightened(cam.position).unwrap();
+        approx_eq(center.x / box_w, 0.5);
+        approx_eq(center.y / box_h, 0.5);
+        assert!(box_w > 4000.0 && box_h > 3000.0);
+
+        cam.set_straighten_degrees(90.0);
+        approx_eq(cam.straighten.to_degrees(), MAX_STRAIGHTEN_DEGREES);
+    }
+
+    #[test]
+    fn ndc_to_image_inverts_the_shader_transform() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(4000, 3000);
+        for rotation in [0, 90, 180, 270] {
+            cam.set_transform(ImageTransform {
+                rotation_degrees: rotation,
+                flip_h: true,
+                flip_v: false,
+            });
+            cam.zoom = 2.5;
+            cam.position = Vec2::new(0.3, -0.1);
+
+            // Top-left corner of the texture is image pixel (0, 0).
+            let top_left = cam.ndc_to_image(corner_to_ndc(&cam, Vec2::new(-1.0, 1.0)));
+            let top_left = top_left.unwrap();
+            approx_eq(top_left.x / 4000.0, 0.0);
+            approx_eq(top_left.y / 3000.0, 0.0);
+
+            let inner = cam.ndc_to_image(corner_to_ndc(&cam, Vec2::new(0.5, -0.5)));
+            let inner = inner.unwrap();
+            approx_eq(inner.x / 4000.0, 0.75);
+            approx_eq(inner.y / 3000.0, 0.75);
+        }
+    }
+
+    #[test]
+    fn center_on_moves_an_image_point_to_the_middle() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(4000, 3000);
+        cam.set_transform(ImageTransform {
+            rotation_degrees: 90,
+            flip_h: false,
+            flip_v: true,
+        });
+        cam.zoom = 4.0;
+
+        cam.center_on(Vec2::new(1000.0, 2500.0));
+
+        let middle = cam.ndc_to_image(Vec2::ZERO).unwrap();
+        approx_eq(middle.x / 4000.0, 0.25);
+        approx_eq(middle.y / 3000.0, 2500.0 / 3000.0);
+    }
+
+    #[test]
+    fn fit_to_viewport_resets_zoom_and_pan() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(4000, 3000);
+        cam.actual_size();
+        cam.position = Vec2::new(0.4, -0.2);
+
+        cam.fit_to_viewport();
+        assert_eq!(cam.fit_mode, FitMode::Free);
+        approx_eq(cam.zoom, 1.0);
+        approx_eq(cam.position.length(), 0.0);
+
+        cam.set_viewport_size(800, 900);
+        approx_eq(cam.zoom, 1.0);
+    }
+
+    #[test]
+    fn set_transform_rotates_and_mirrors() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_transform(ImageTransform {
+            rotation_degrees: 90,
+            flip_h: true,
+            flip_v: false,
+        });
+
+        assert!(cam.flip_h && !cam.flip_v);
+        let scale = cam.fit_scale(4000.0, 3000.0);
+        approx_eq(scale[0], 1.0);
+        approx_eq(scale[1], 0.421875);
+    }
+
     #[test]
     fn fit_scale_invalid_inputs_returns_identity() {
         let mut cam = Camera::new();
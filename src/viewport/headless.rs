@@ -0,0 +1,213 @@
+//! Rendering without a window. `iris --export out.png photo.jpg` draws an
+//! image through the same Vulkan pipeline the viewer uses and saves the
+//! frame, for scripting and for pixel tests.
+
+use image::RgbaImage;
+use std::path::PathBuf;
+
+use super::camera::{Camera, ImageTransform, parse_zoom_percent};
+use super::decode_image;
+use super::vk::renderer::VkRenderer;
+use crate::error::{IrisError, IrisResult};
+use crate::orientation;
+
+/// Frame size when `--size` isn't given.
+pub const DEFAULT_EXPORT_SIZE: (u32, u32) = (1280, 800);
+
+pub const USAGE: &str = "Usage: iris --export OUTPUT [--size WIDTHxHEIGHT] \
+                         [--zoom PERCENT] [--rotate DEGREES] IMAGE";
+
+/// One view of one image to render and save.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportRequest {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    /// Zoom relative to actual size; `None` fits the whole image.
+    pub zoom_percent: Option<f32>,
+    /// Clockwise quarter turns on top of the EXIF orientation.
+    pub rotation_degrees: i32,
+}
+
+/// Read an export request from the command line arguments (without the
+/// program name). `None` when `--export` isn't among them, so the viewer
+/// starts as usual.
+pub fn parse_args(args: &[String]) -> Option<Result<ExportRequest, String>> {
+    if !args.iter().any(|a| a == "--export") {
+        return None;
+    }
+    Some(parse_export_args(args))
+}
+
+fn parse_export_args(args: &[String]) -> Result<ExportRequest, String> {
+    let mut output = None;
+    let mut input = None;
+    let (mut width, mut height) = DEFAULT_EXPORT_SIZE;
+    let mut zoom_percent = None;
+    let mut rotation_degrees = 0;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--export" => output = Some(PathBuf::from(value()?)),
+            "--size" => {
+                let size = value()?;
+                (width, height) = parse_size(size).ok_or_else(|| format!("Bad size: {size}"))?;
+            }
+            "--zoom" => {
+                let zoom = value()?;
+                zoom_percent =
+                    Some(parse_zoom_percent(zoom).ok_or_else(|| format!("Bad zoom: {zoom}"))?);
+            }
+            "--rotate" => {
+                let rotate = value()?;
+                rotation_degrees = rotate
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|d| d % 90 == 0)
+                    .ok_or_else(|| format!("Rotation must be a multiple of 90: {rotate}"))?;
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {arg}")),
+            _ if input.is_none() => input = Some(PathBuf::from(arg)),
+            _ => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+
+    Ok(ExportRequest {
+        input: input.ok_or("No image to export")?,
+        output: output.ok_or("No output file")?,
+        width,
+        height,
+        zoom_percent,
+        rotation_degrees,
+    })
+}
+
+/// "1920x1080" as a width and height, both positive.
+fn parse_size(text: &str) -> Option<(u32, u32)> {
+    let (w, h) = text.trim().split_once(['x', 'X'])?;
+    let (w, h) = (w.trim().parse().ok()?, h.trim().parse().ok()?);
+    (w > 0 && h > 0).then_some((w, h))
+}
+
+/// Render `request`'s view of its image, as the viewer would show it in a
+/// `width`×`height` window: decoded the same way, RAW and embedded color
+/// profiles included.
+pub fn render_view(request: &ExportRequest) -> IrisResult<RgbaImage> {
+    let decoded = decode_image(&request.input, 0).map_err(IrisError::Other)?;
+    let (w, h) = decoded.dimensions();
+
+    let mut renderer = VkRenderer::headless(request.width, request.height)?;
+    decoded.upload_and_activate(&mut renderer, &request.input);
+
+    let transform = orientation::read_display_transform(&request.input);
+    let mut camera = Camera::new();
+    camera.set_viewport_size(request.width, request.height);
+    camera.set_image_size(w, h);
+    camera.set_transform(ImageTransform {
+        rotation_degrees: transform.rotation_degrees + request.rotation_degrees,
+        ..transform
+    });
+    if let Some(percent) = request.zoom_percent {
+        camera.set_zoom_percent(percent);
+    }
+    renderer.render_to_image(&camera)
+}
+
+/// Render `request` and write it to its output file, in the format its
+/// extension names.
+pub fn export(request: &ExportRequest) -> Result<(), String> {
+    let frame = render_view(request).map_err(|e| e.to_toast_message())?;
+    frame
+        .save(&request.output)
+        .map_err(|e| format!("Could not write {}: {e}", request.output.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_export_arguments() {
+        assert_eq!(parse_args(&args(&["photo.jpg"])), None);
+
+        let request = parse_args(&args(&[
+            "--export",
+            "out.png",
+            "--size",
+            "640x480",
+            "--zoom",
+            "200%",
+            "--rotate",
+            "90",
+            "photo.jpg",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(request.input, PathBuf::from("photo.jpg"));
+        assert_eq!(request.output, PathBuf::from("out.png"));
+        assert_eq!((request.width, request.height), (640, 480));
+        assert_eq!(request.zoom_percent, Some(200.0));
+        assert_eq!(request.rotation_degrees, 90);
+
+        let request = parse_args(&args(&["photo.jpg", "--export", "out.png"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!((request.width, request.height), DEFAULT_EXPORT_SIZE);
+        assert_eq!(request.zoom_percent, None);
+
+        assert!(
+            parse_args(&args(&["--export", "out.png"]))
+                .unwrap()
+                .is_err()
+        );
+        assert!(parse_args(&args(&["--export"])).unwrap().is_err());
+        assert!(
+            parse_args(&args(&["--export", "o.png", "--size", "0x10", "a.jpg"]))
+                .unwrap()
+                .is_err()
+        );
+        assert!(
+            parse_args(&args(&["--export", "o.png", "--rotate", "45", "a.jpg"]))
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn renders_an_image_through_the_gpu() {
+        if VkRenderer::headless(1, 1).is_err() {
+            eprintln!("skipping: no Vulkan device");
+            return;
+        }
+        let dir = std::env::temp_dir().join(format!("iris-headless-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("red.png");
+        image::RgbaImage::from_pixel(4, 2, image::Rgba([255, 0, 0, 255]))
+            .save(&input)
+            .unwrap();
+
+        let frame = render_view(&ExportRequest {
+            input,
+            output: dir.join("out.png"),
+            width: 40,
+            height: 40,
+            zoom_percent: None,
+            rotation_degrees: 0,
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        let frame = frame.unwrap();
+
+        // A 2:1 image fitted into a square fills its middle band; the
+        // letterbox above and below is the viewport background.
+        assert_eq!(frame.dimensions(), (40, 40));
+        assert_eq!(frame.get_pixel(20, 20).0, [255, 0, 0, 255]);
+        assert_ne!(frame.get_pixel(20, 2).0, [255, 0, 0, 255]);
+    }
+}
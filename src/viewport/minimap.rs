@@ -0,0 +1,240 @@
+//! Navigator shown in a corner while zoomed in: the whole image as a
+//! thumbnail, with the part on screen outlined. Dragging in it pans the
+//! main view.
+
+use futures::channel::oneshot;
+use glam::Vec2;
+use gtk4::prelude::*;
+use gtk4::{cairo, glib};
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use super::camera::Camera;
+
+/// Longest edge of the navigator, in pixels.
+const MINIMAP_SIZE: f64 = 160.0;
+
+pub(super) struct Minimap {
+    pub area: gtk4::DrawingArea,
+    camera: Rc<RefCell<Camera>>,
+    /// Image on screen, and its thumbnail once loaded.
+    shown: RefCell<Option<PathBuf>>,
+    thumb: RefCell<Option<cairo::ImageSurface>>,
+    /// Whether the last frame showed one image filling the viewport.
+    single: Cell<bool>,
+}
+
+impl Minimap {
+    pub fn new(camera: Rc<RefCell<Camera>>) -> Rc<Self> {
+        let area = gtk4::DrawingArea::builder()
+            .halign(gtk4::Align::End)
+            .valign(gtk4::Align::End)
+            .visible(false)
+            .css_classes(["minimap"])
+            .build();
+        let this = Rc::new(Self {
+            area,
+            camera,
+            shown: RefCell::new(None),
+            thumb: RefCell::new(None),
+            single: Cell::new(true),
+        });
+        let weak = Rc::downgrade(&this);
+        this.area.set_draw_func(move |_, cr, width, height| {
+            if let Some(this) = weak.upgrade() {
+                this.draw(cr, width as f64, height as f64);
+            }
+        });
+        this
+    }
+
+    /// Track the image now on screen, loading its thumbnail from the shared
+    /// cache.
+    pub fn set_image(self: &Rc<Self>, path: &Path) {
+        if self.shown.borrow().as_deref() == Some(path) {
+            return;
+        }
+        self.shown.replace(Some(path.to_path_buf()));
+        self.thumb.replace(None);
+        self.area.set_visible(false);
+
+        let (tx, rx) = oneshot::channel();
+        let path_load = path.to_path_buf();
+        rayon::spawn(move || {
+            let _ = tx.send(crate::thumbcache::load_or_generate(&path_load));
+        });
+        let path = path.to_path_buf();
+        let weak = Rc::downgrade(self);
+        glib::spawn_future_local(async move {
+            let Ok(Some(thumb)) = rx.await else { return };
+            let Some(this) = weak.upgrade() else { return };
+            if this.shown.borrow().as_deref() != Some(path.as_path()) {
+                return;
+            }
+            this.thumb.replace(rgba_surface(&thumb));
+            this.refresh(this.single.get());
+        });
+    }
+
+    /// Drop the thumbnail of `path` if it is the one shown, so the next
+    /// `set_image` reloads it.
+    pub fn forget(&self, path: &Path) {
+        if self.shown.borrow().as_deref() == Some(path) {
+            self.shown.replace(None);
+        }
+    }
+
+    /// Show the navigator if `single` (one image filling the viewport) and
+    /// the image overflows the viewport, and redraw it for the camera.
+    pub fn refresh(&self, single: bool) {
+        self.single.set(single);
+        let cam = *self.camera.borrow();
+        let (over_x, over_y) = cam.overflows();
+        let visible = single && (over_x || over_y) && self.thumb.borrow().is_some();
+        self.area.set_visible(visible);
+        if !visible {
+            return;
+        }
+        let (w, h) = displayed_size(&cam);
+        let fit = MINIMAP_SIZE / w.max(h).max(1.0);
+        self.area.set_content_width((w * fit).round() as i32);
+        self.area.set_content_height((h * fit).round() as i32);
+        self.area.queue_draw();
+    }
+
+    /// Source image pixel at navigator point `(x, y)`.
+    pub fn image_point(&self, x: f64, y: f64) -> Option<Vec2> {
+        let width = self.area.width() as f64;
+        let height = self.area.height() as f64;
+        let matrix = image_matrix(&self.camera.borrow(), width, height)?
+            .try_invert()
+            .ok()?;
+        let (ix, iy) = matrix.transform_point(x, y);
+        Some(Vec2::new(ix as f32, iy as f32))
+    }
+
+    fn draw(&self, cr: &cairo::Context, width: f64, height: f64) {
+        let cam = *self.camera.borrow();
+        let thumb = self.thumb.borrow();
+        let (Some(thumb), Some(matrix)) = (thumb.as_ref(), image_matrix(&cam, width, height))
+        else {
+            return;
+        };
+        cr.set_matrix(matrix);
+
+        // The thumbnail, stretched over the source image's pixel grid.
+        let _ = cr.save();
+        cr.scale(
+            cam.image_width as f64 / thumb.width() as f64,
+            cam.image_height as f64 / thumb.height() as f64,
+        );
+        let _ = cr.set_source_surface(thumb, 0.0, 0.0);
+        cr.rectangle(0.0, 0.0, thumb.width() as f64, thumb.height() as f64);
+        let _ = cr.fill();
+        let _ = cr.restore();
+
+        // The viewport's corners, mapped back onto the image.
+        cr.rectangle(0.0, 0.0, cam.image_width as f64, cam.image_height as f64);
+        cr.clip();
+        let corners = [(-1.0, 1.0), (1.0, 1.0), (1.0, -1.0), (-1.0, -1.0)];
+        for (i, (x, y)) in corners.into_iter().enumerate() {
+            let Some(p) = cam.ndc_to_image(Vec2::new(x, y)) else {
+                return;
+            };
+            if i == 0 {
+                cr.move_to(p.x as f64, p.y as f64);
+            } else {
+                cr.line_to(p.x as f64, p.y as f64);
+            }
+        }
+        cr.close_path();
+        // Stroke in widget pixels, not image pixels.
+        cr.identity_matrix();
+        cr.set_source_rgba(1.0, 1.0, 1.0, 0.9);
+        cr.set_line_width(1.5);
+        let _ = cr.stroke();
+    }
+}
+
+/// Image size as displayed, after rotation.
+fn displayed_size(cam: &Camera) -> (f64, f64) {
+    let deg = cam.rotation.to_degrees().round().rem_euclid(360.0);
+    let (w, h) = (cam.image_width as f64, cam.image_height as f64);
+    if deg == 90.0 || deg == 270.0 {
+        (h, w)
+    } else {
+        (w, h)
+    }
+}
+
+/// Maps source image pixels into a `width`×`height` navigator, oriented
+/// like the main view: rotated clockwise, then mirrored.
+fn image_matrix(cam: &Camera, width: f64, height: f64) -> Option<cairo::Matrix> {
+    let (w, h) = displayed_size(cam);
+    if w <= 0.0 || h <= 0.0 || width <= 0.0 || height <= 0.0 {
+        return None;
+    }
+    let scale = (width / w).min(height / h);
+    let mut m = cairo::Matrix::identity();
+    m.translate(width / 2.0, height / 2.0);
+    m.scale(
+        if cam.flip_h { -1.0 } else { 1.0 },
+        if cam.flip_v { -1.0 } else { 1.0 },
+    );
+    m.rotate(cam.rotation as f64);
+    m.scale(scale, scale);
+    m.translate(
+        -cam.image_width as f64 / 2.0,
+        -cam.image_height as f64 / 2.0,
+    );
+    Some(m)
+}
+
+/// Cairo surface (premultiplied, native-endian ARGB) holding `img`.
+pub(crate) fn rgba_surface(img: &image::RgbaImage) -> Option<cairo::ImageSurface> {
+    let (w, h) = img.dimensions();
+    let mut surface =
+        cairo::ImageSurface::create(cairo::Format::ARgb32, w as i32, h as i32).ok()?;
+    let stride = surface.stride() as usize;
+    {
+        let mut data = surface.data().ok()?;
+        for (y, row) in img.rows().enumerate() {
+            for (x, px) in row.enumerate() {
+                let [r, g, b, a] = px.0;
+                let pre = |c: u8| (c as u16 * a as u16 / 255) as u8;
+                let argb = u32::from_be_bytes([a, pre(r), pre(g), pre(b)]);
+                let i = y * stride + x * 4;
+                data[i..i + 4].copy_from_slice(&argb.to_ne_bytes());
+            }
+        }
+    }
+    Some(surface)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::viewport::camera::ImageTransform;
+
+    #[test]
+    fn navigator_follows_the_view_orientation() {
+        let mut cam = Camera::new();
+        cam.set_viewport_size(1600, 900);
+        cam.set_image_size(400, 200);
+        cam.set_transform(ImageTransform {
+            rotation_degrees: 90,
+            flip_h: false,
+            flip_v: false,
+        });
+
+        // Rotated clockwise, the image is 200×400 on screen; a 100×200
+        // navigator shows it at half size with the top-left source
+        // pixel in the top-right corner.
+        let m = image_matrix(&cam, 100.0, 200.0).unwrap();
+        let (x, y) = m.transform_point(0.0, 0.0);
+        assert!((x - 100.0).abs() < 1e-6 && y.abs() < 1e-6, "({x}, {y})");
+        let (x, y) = m.transform_point(400.0, 200.0);
+        assert!(x.abs() < 1e-6 && (y - 200.0).abs() < 1e-6, "({x}, {y})");
+    }
+}
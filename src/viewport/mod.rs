@@ -1,26 +1,44 @@
-use futures::channel::oneshot;
+use futures::StreamExt;
+use futures::channel::{mpsc, oneshot};
 use gtk4::gdk;
 use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::{GraphicsOffload, Picture};
 use libadwaita as adw;
 use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use ash::vk as avk;
+use glam::Vec2;
 
 use crate::color::{ColorInfo, DynamicRange};
+use crate::export::CropRect;
 use crate::raw;
 
 pub mod camera;
+pub mod headless;
+pub(crate) mod minimap;
+mod readout;
+mod status;
+pub mod strip;
 pub mod vk;
 
-use camera::Camera;
+use camera::{Camera, FitMode, ImageTransform};
+use minimap::Minimap;
+use readout::PixelReadout;
+pub use status::LoadStats;
+use strip::StripLayout;
 use vk::compute::ProcessingPass;
 use vk::context::VkContext;
-use vk::renderer::VkRenderer;
+use vk::renderer::{
+    Adjustments, AlphaBackground, CompareLayout, DEFAULT_CACHE_CAPACITY, ImageTransition,
+    VkRenderer,
+};
 
 // ── Decoded image types ───────────────────────────────────────────────────────
 
@@ -37,6 +55,14 @@ enum DecodedImage {
         height: u32,
         color: ColorInfo,
     },
+    /// Scene-linear float pixels (OpenEXR, Radiance HDR), which may exceed
+    /// 1.0 and are tone mapped for display.
+    RgbaF32 {
+        data: Vec<f32>,
+        width: u32,
+        height: u32,
+        color: ColorInfo,
+    },
 }
 
 impl DecodedImage {
@@ -44,6 +70,7 @@ impl DecodedImage {
         match self {
             DecodedImage::Rgba8 { width, height, .. } => (*width, *height),
             DecodedImage::Rgba16 { width, height, .. } => (*width, *height),
+            DecodedImage::RgbaF32 { width, height, .. } => (*width, *height),
         }
     }
 
@@ -51,18 +78,63 @@ impl DecodedImage {
         match self {
             DecodedImage::Rgba8 { color, .. } => color.dynamic_range,
             DecodedImage::Rgba16 { color, .. } => color.dynamic_range,
+            DecodedImage::RgbaF32 { color, .. } => color.dynamic_range,
+        }
+    }
+
+    /// Upload to the GPU under `key` and make it the active image.
+    fn upload_and_activate(&self, r: &mut VkRenderer, key: &Path) {
+        match self {
+            DecodedImage::Rgba8 {
+                rgba,
+                width,
+                height,
+                ..
+            } => {
+                r.upload_and_activate(key, rgba, *width, *height);
+            }
+            DecodedImage::Rgba16 {
+                data,
+                width,
+                height,
+                ..
+            } => {
+                r.upload_and_activate_16bit(key, data, *width, *height);
+            }
+            DecodedImage::RgbaF32 {
+                data,
+                width,
+                height,
+                ..
+            } => {
+                r.upload_and_activate_float(key, data, *width, *height);
+            }
         }
     }
 }
 
-fn decode_standard_image(path: &Path) -> Option<DecodedImage> {
+fn decode_standard_image(path: &Path) -> Result<DecodedImage, String> {
+    let img = crate::decode::try_open(path)?;
+    if crate::decode::is_float(&img) {
+        let linear = img.to_rgba32f();
+        let (width, height) = linear.dimensions();
+        return Ok(DecodedImage::RgbaF32 {
+            data: linear.into_raw(),
+            width,
+            height,
+            color: ColorInfo {
+                icc_profile: None,
+                dynamic_range: DynamicRange::Hdr,
+            },
+        });
+    }
     let icc = crate::color::extract_icc_profile(path);
-    let img = image::open(path).ok()?.to_rgba8();
+    let img = img.to_rgba8();
     let (w, h) = img.dimensions();
 
     let rgba = crate::color::rgba8_to_srgb_with_icc(img.as_raw(), w, h, icc.as_deref());
 
-    Some(DecodedImage::Rgba8 {
+    Ok(DecodedImage::Rgba8 {
         rgba,
         width: w,
         height: h,
@@ -74,6 +146,17 @@ fn decode_standard_image(path: &Path) -> Option<DecodedImage> {
 }
 
 fn decode_raw_image(path: &Path) -> Option<DecodedImage> {
+    // The camera's embedded JPEG is already rendered with its picture style
+    // and costs a fraction of a demosaic; small ones fall back to the latter.
+    if let Some(preview) = raw::decode_display_preview(path) {
+        let (width, height) = preview.dimensions();
+        return Some(DecodedImage::Rgba8 {
+            rgba: preview.into_raw(),
+            width,
+            height,
+            color: ColorInfo::default(),
+        });
+    }
     let raw_img = raw::decode_raw(path)?;
     Some(DecodedImage::Rgba16 {
         data: raw_img.data,
@@ -83,6 +166,232 @@ fn decode_raw_image(path: &Path) -> Option<DecodedImage> {
     })
 }
 
+/// A page past the first of a multi-page file, with its embedded profile
+/// applied like for any other image.
+fn decode_page_image(path: &Path, page: usize) -> Option<DecodedImage> {
+    Some(srgb_page(crate::pages::decode_page(path, page)?, path))
+}
+
+/// A decoded TIFF page as sRGB pixels, through the file's embedded profile.
+fn srgb_page(img: image::DynamicImage, path: &Path) -> DecodedImage {
+    let img = img.to_rgba8();
+    let (w, h) = img.dimensions();
+    let icc = crate::color::extract_icc_profile(path);
+    let rgba = crate::color::rgba8_to_srgb_with_icc(img.as_raw(), w, h, icc.as_deref());
+    DecodedImage::Rgba8 {
+        rgba,
+        width: w,
+        height: h,
+        color: ColorInfo {
+            icc_profile: icc,
+            dynamic_range: DynamicRange::Sdr,
+        },
+    }
+}
+
+/// Decode page `page` of `path`; page 0 is the regular image. The error
+/// is shown to the user in place of the image.
+fn decode_image(path: &Path, page: usize) -> Result<DecodedImage, String> {
+    if page > 0 {
+        decode_page_image(path, page).ok_or_else(|| format!("Could not decode page {}", page + 1))
+    } else if raw::is_raw(path) {
+        decode_raw_image(path).ok_or_else(|| "Unsupported or damaged RAW file".into())
+    } else {
+        decode_standard_image(path)
+    }
+}
+
+/// Decode page `page` of `path` on a worker thread, describing it for the
+/// status bar while the pixels are at hand.
+fn decode_with_stats(path: &Path, page: usize) -> Result<(DecodedImage, LoadStats), String> {
+    let decoded = decode_image(path, page)?;
+    let stats = LoadStats::new(path, page, &decoded);
+    Ok((decoded, stats))
+}
+
+/// Images with at least this many pixels show their cached thumbnail while
+/// the full image decodes.
+const PLACEHOLDER_MIN_PIXELS: u64 = 16_000_000;
+
+/// What a load's decode worker sends back, in order.
+enum LoadStep {
+    /// The decode is running and will finish, whichever load it's for.
+    Started,
+    /// A stand-in to show meanwhile, with the full image's size.
+    Preview {
+        thumb: image::RgbaImage,
+        width: u32,
+        height: u32,
+    },
+    /// Fraction of the full image decoded so far.
+    Progress(f32),
+    Full(Result<(DecodedImage, LoadStats), String>),
+}
+
+/// A single-image decode that is running. Loading the same image again
+/// before it lands takes it over rather than decoding a second time.
+struct PendingLoad {
+    /// Load generation the result is shown for.
+    generation: Cell<u64>,
+    transform: Cell<ImageTransform>,
+    direction: Cell<i32>,
+    on_dims: RefCell<Option<Box<dyn FnOnce(u32, u32)>>>,
+}
+
+impl PendingLoad {
+    fn new(
+        generation: u64,
+        transform: ImageTransform,
+        direction: i32,
+        on_dims: Box<dyn FnOnce(u32, u32)>,
+    ) -> Self {
+        Self {
+            generation: Cell::new(generation),
+            transform: Cell::new(transform),
+            direction: Cell::new(direction),
+            on_dims: RefCell::new(Some(on_dims)),
+        }
+    }
+
+    /// Show the result for load `generation` instead, as that load asks.
+    fn adopt(
+        &self,
+        generation: u64,
+        transform: ImageTransform,
+        direction: i32,
+        on_dims: Box<dyn FnOnce(u32, u32)>,
+    ) {
+        self.generation.set(generation);
+        self.transform.set(transform);
+        self.direction.set(direction);
+        self.on_dims.replace(Some(on_dims));
+    }
+}
+
+/// Decode a large TIFF a strip or tile at a time, sending
+/// `LoadStep::Progress` as it goes. `None` for other files and for TIFF
+/// layouts only the `image` crate reads, which decode in one go instead.
+fn decode_with_progress(
+    path: &Path,
+    page: usize,
+    tx: &mpsc::UnboundedSender<LoadStep>,
+) -> Option<Result<(DecodedImage, LoadStats), String>> {
+    if !crate::pages::is_tiff(path) {
+        return None;
+    }
+    let (width, height) = image::image_dimensions(path).ok()?;
+    if (width as u64) * (height as u64) < PLACEHOLDER_MIN_PIXELS {
+        return None;
+    }
+    // Whole percents only; a channel message per strip would be wasted.
+    let mut percent = 0;
+    let img = crate::pages::decode_page_with_progress(path, page, &mut |done| {
+        let now = (done * 100.0) as u32;
+        if now > percent {
+            percent = now;
+            let _ = tx.unbounded_send(LoadStep::Progress(done));
+        }
+    })?;
+    let decoded = srgb_page(img, path);
+    let stats = LoadStats::new(path, page, &decoded);
+    Some(Ok((decoded, stats)))
+}
+
+/// The cached thumbnail of page `page` of `path` as a stand-in, if the
+/// image is large enough to be slow and its header says how big it is.
+fn placeholder(path: &Path, page: usize) -> Option<LoadStep> {
+    if page > 0 || raw::is_raw(path) {
+        return None;
+    }
+    let (width, height) = image::image_dimensions(path).ok()?;
+    if (width as u64) * (height as u64) < PLACEHOLDER_MIN_PIXELS {
+        return None;
+    }
+    let thumb = crate::thumbcache::load_cached(path)?;
+    Some(LoadStep::Preview {
+        thumb,
+        width,
+        height,
+    })
+}
+
+/// Texture cache key of the stand-in for the image cached under `key`.
+fn preview_key(key: &Path) -> PathBuf {
+    let mut preview = key.as_os_str().to_owned();
+    preview.push("#preview");
+    PathBuf::from(preview)
+}
+
+/// A handler set after construction, e.g. by `connect_scroll_end`; `F` is
+/// its `dyn Fn` signature.
+type Callback<F> = Rc<RefCell<Option<Rc<F>>>>;
+
+/// Tell the load error handler, if any, that the image being shown at
+/// `path` failed to decode.
+fn report_load_error(handler: &Callback<dyn Fn(&Path, &str)>, path: &Path, message: &str) {
+    let handler = handler.borrow().clone();
+    if let Some(f) = handler {
+        f(path, message);
+    }
+}
+
+/// Hand the format and load time of an image just shown to the stats
+/// handler, if any.
+fn report_load_stats(handler: &Callback<dyn Fn(&LoadStats)>, stats: &LoadStats) {
+    let handler = handler.borrow().clone();
+    if let Some(f) = handler {
+        f(stats);
+    }
+}
+
+/// Keep `stats` for the texture cached under `key`, so a cache hit can
+/// report it, dropping those of textures since evicted.
+fn remember_stats(
+    known: &RefCell<HashMap<PathBuf, LoadStats>>,
+    r: &VkRenderer,
+    key: &Path,
+    stats: LoadStats,
+) {
+    let mut known = known.borrow_mut();
+    known.retain(|k, _| r.is_cached(k));
+    known.insert(key.to_path_buf(), stats);
+}
+
+/// Texture cache key for a page. Page 0 keeps the plain
+/// path, so prefetched images are found when they are first shown.
+fn page_key(path: &Path, page: usize) -> PathBuf {
+    if page == 0 {
+        return path.to_path_buf();
+    }
+    let mut key = path.as_os_str().to_owned();
+    key.push(format!("#page{page}"));
+    PathBuf::from(key)
+}
+
+/// Upload a decoded image into the texture cache without activating it.
+fn cache_decoded(r: &mut VkRenderer, path: &Path, decoded: &DecodedImage) {
+    match decoded {
+        DecodedImage::Rgba8 {
+            rgba,
+            width,
+            height,
+            ..
+        } => r.cache_only(path, rgba, *width, *height),
+        DecodedImage::Rgba16 {
+            data,
+            width,
+            height,
+            ..
+        } => r.cache_only_16bit(path, data, *width, *height),
+        DecodedImage::RgbaF32 {
+            data,
+            width,
+            height,
+            ..
+        } => r.cache_only_float(path, data, *width, *height),
+    }
+}
+
 // ── Animation types ───────────────────────────────────────────────────────────
 
 struct AnimFrame {
@@ -103,12 +412,150 @@ enum AnimDecodeResult {
     },
 }
 
+impl AnimDecodeResult {
+    fn stats(&self) -> LoadStats {
+        let see_through = |rgba: &[u8]| rgba.chunks_exact(4).any(|px| px[3] < 255);
+        match self {
+            AnimDecodeResult::Single { rgba, .. } => LoadStats::animation(1, see_through(rgba)),
+            AnimDecodeResult::Animated { frames } => {
+                LoadStats::animation(frames.len(), frames.iter().any(|f| see_through(&f.rgba)))
+            }
+        }
+    }
+}
+
 struct AnimationState {
     frame_keys: Vec<PathBuf>,
     delays: Vec<Duration>,
     current_frame: usize,
 }
 
+// ── Zoom indicator ────────────────────────────────────────────────────────────
+
+/// How long the zoom percentage stays up after the last zoom change.
+const ZOOM_INDICATOR_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Percentage label over the image, shown on zoom changes and faded out
+/// shortly after the last one.
+#[derive(Clone)]
+struct ZoomIndicator {
+    label: gtk4::Label,
+    generation: Rc<Cell<u64>>,
+    /// Told the new percentage on every change.
+    on_change: Rc<RefCell<Option<Rc<dyn Fn(f32)>>>>,
+}
+
+impl ZoomIndicator {
+    fn new() -> Self {
+        let label = gtk4::Label::builder()
+            .halign(gtk4::Align::Center)
+            .valign(gtk4::Align::End)
+            .can_target(false)
+            .css_classes(["zoom-indicator", "faded"])
+            .build();
+        Self {
+            label,
+            generation: Rc::new(Cell::new(0)),
+            on_change: Rc::default(),
+        }
+    }
+
+    fn show(&self, camera: &Camera) {
+        let Some(percent) = camera.zoom_percent() else {
+            return;
+        };
+        self.label
+            .set_label(&format!("{}%", percent.round() as u32));
+        self.label.remove_css_class("faded");
+        let on_change = self.on_change.borrow().clone();
+        if let Some(f) = on_change {
+            f(percent);
+        }
+
+        let generation = self.generation.get().wrapping_add(1);
+        self.generation.set(generation);
+        let this = self.clone();
+        glib::timeout_add_local_once(ZOOM_INDICATOR_TIMEOUT, move || {
+            if this.generation.get() == generation {
+                this.label.add_css_class("faded");
+            }
+        });
+    }
+}
+
+// ── Crop selection ────────────────────────────────────────────────────────────
+
+/// Rubber-band selection drawn while crop mode is on. While active, primary
+/// drags select instead of panning.
+#[derive(Default)]
+struct CropState {
+    active: Cell<bool>,
+    /// Drag start and current pointer position, in widget pixels.
+    band: Cell<Option<(Vec2, Vec2)>>,
+    on_select: RefCell<Option<Rc<dyn Fn(CropRect)>>>,
+}
+
+/// Source-image rectangle covered by a selection between two widget points.
+/// A straightened image is cropped as displayed instead, so the rectangle is
+/// in the pixels of its straightened canvas; see `export::export`.
+fn crop_rect(camera: &Camera, a: Vec2, b: Vec2) -> Option<CropRect> {
+    let vw = camera.viewport_width as f32;
+    let vh = camera.viewport_height as f32;
+    let to_ndc = |p: Vec2| Vec2::new(p.x / vw * 2.0 - 1.0, 1.0 - p.y / vh * 2.0);
+    if camera.straighten != 0.0 {
+        let (a, (w, h)) = camera.ndc_to_straightened(to_ndc(a))?;
+        let (b, _) = camera.ndc_to_straightened(to_ndc(b))?;
+        return CropRect::from_corners(a, b, w.round() as u32, h.round() as u32);
+    }
+    CropRect::from_corners(
+        camera.ndc_to_image(to_ndc(a))?,
+        camera.ndc_to_image(to_ndc(b))?,
+        camera.image_width as u32,
+        camera.image_height as u32,
+    )
+}
+
+fn draw_crop_band(cr: &gtk4::cairo::Context, camera: &Camera, crop: &CropState) {
+    let Some((a, b)) = crop.band.get() else {
+        return;
+    };
+    let (min, max) = (a.min(b), a.max(b));
+    let (x, y, w, h) = (
+        min.x as f64,
+        min.y as f64,
+        (max.x - min.x) as f64,
+        (max.y - min.y) as f64,
+    );
+
+    cr.rectangle(x, y, w, h);
+    cr.set_source_rgba(1.0, 1.0, 1.0, 0.12);
+    let _ = cr.fill_preserve();
+    cr.set_source_rgba(1.0, 1.0, 1.0, 0.9);
+    cr.set_line_width(1.0);
+    cr.set_dash(&[4.0, 4.0], 0.0);
+    let _ = cr.stroke();
+
+    if let Some(rect) = crop_rect(camera, a, b) {
+        let text = format!("{} × {} px", rect.width, rect.height);
+        cr.set_font_size(12.0);
+        let Ok(extents) = cr.text_extents(&text) else {
+            return;
+        };
+        let (tx, ty) = (x + 6.0, (y - 8.0).max(extents.height() + 6.0));
+        cr.rectangle(
+            tx - 4.0,
+            ty - extents.height() - 4.0,
+            extents.width() + 8.0,
+            extents.height() + 8.0,
+        );
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.6);
+        let _ = cr.fill();
+        cr.move_to(tx, ty);
+        cr.set_source_rgba(1.0, 1.0, 1.0, 1.0);
+        let _ = cr.show_text(&text);
+    }
+}
+
 // ── Viewport ──────────────────────────────────────────────────────────────────
 
 pub struct Viewport {
@@ -117,7 +564,11 @@ pub struct Viewport {
     _offload: GraphicsOffload,
     camera: Rc<RefCell<Camera>>,
     renderer: Rc<RefCell<Option<VkRenderer>>>,
-    current_target: Rc<RefCell<Option<PathBuf>>>,
+    /// Bumped by every load. Decodes for a load since superseded are
+    /// skipped if they haven't started, and never shown if they have.
+    load_generation: Arc<AtomicU64>,
+    /// Running single-image decodes, by cache key.
+    pending_loads: Rc<RefCell<HashMap<PathBuf, Rc<PendingLoad>>>>,
     drag_start_x: Rc<Cell<f64>>,
     drag_start_y: Rc<Cell<f64>>,
     drag_cam_x: Rc<Cell<f32>>,
@@ -126,6 +577,73 @@ pub struct Viewport {
     animation: Rc<RefCell<Option<AnimationState>>>,
     anim_generation: Rc<Cell<u64>>,
     resize_scheduled: Rc<Cell<bool>>,
+    strip: Rc<RefCell<Option<StripState>>>,
+    on_scroll_end: Callback<dyn Fn()>,
+    /// Whether the scroll wheel changes image instead of zooming.
+    scroll_navigates: Rc<Cell<bool>>,
+    on_scroll_navigate: Callback<dyn Fn(i32)>,
+    on_load_error: Callback<dyn Fn(&Path, &str)>,
+    on_load_stats: Callback<dyn Fn(&LoadStats)>,
+    /// Stats of cached textures, by cache key.
+    load_stats: Rc<RefCell<HashMap<PathBuf, LoadStats>>>,
+    zoom_indicator: ZoomIndicator,
+    crop: Rc<CropState>,
+    crop_area: gtk4::DrawingArea,
+    cache_capacity: Cell<usize>,
+    /// Neighbours the latest `prefetch_neighbors` call asked for; shared
+    /// with decode workers so stale requests can bail out early.
+    prefetch_wanted: Arc<Mutex<HashSet<PathBuf>>>,
+    prefetch_in_flight: Rc<RefCell<HashSet<PathBuf>>>,
+    /// Image wanted right of the compare divider; shared with its decode so
+    /// a superseded request doesn't take over.
+    compare_target: Rc<RefCell<Option<PathBuf>>>,
+    readout: Rc<PixelReadout>,
+    minimap: Rc<Minimap>,
+    /// Decode progress of a large image, up only while one loads.
+    load_progress: gtk4::ProgressBar,
+    /// Loads finishing sooner never show `load_progress`.
+    load_progress_delay: Cell<Duration>,
+    /// Direction of the next image change, for a slide transition.
+    transition_direction: Cell<i32>,
+    /// Bumped per transition, so a superseded one stops animating.
+    transition_generation: Rc<Cell<u64>>,
+}
+
+// ── Continuous strip mode ─────────────────────────────────────────────────────
+
+/// Scroll distance per wheel step when scrolling pans instead of zooming
+/// (strip mode, fit-width pages), in pixels.
+const SCROLL_STEP_PX: f32 = 80.0;
+
+/// Zoom factor per keyboard zoom step.
+const ZOOM_STEP: f32 = 1.25;
+
+/// Sideways touchpad travel that counts as a swipe to the next or previous
+/// image, in pixels.
+const SWIPE_DISTANCE_PX: f64 = 120.0;
+
+/// Length of the animation between two images.
+const TRANSITION_DURATION: Duration = Duration::from_millis(250);
+
+/// How long a load runs before its progress indicator appears, so fast
+/// decodes don't flash it for a frame.
+const DEFAULT_LOAD_PROGRESS_DELAY: Duration = Duration::from_millis(120);
+
+/// How long the widget size must stay put before the render targets are
+/// reallocated to match it.
+const RESIZE_SETTLE: Duration = Duration::from_millis(150);
+
+/// How often the CPU readback fallback checks whether the GPU has finished
+/// copying a frame out.
+const READBACK_POLL: Duration = Duration::from_millis(2);
+
+/// How close to the compare divider a drag grabs it, in pixels.
+const DIVIDER_GRAB_PX: f64 = 8.0;
+
+struct StripState {
+    layout: StripLayout,
+    /// Decodes in flight (or failed), so scroll events don't queue duplicates.
+    pending: HashSet<PathBuf>,
 }
 
 impl Viewport {
@@ -153,6 +671,22 @@ impl Viewport {
         let overlay = gtk4::Overlay::new();
         overlay.set_child(Some(&offload));
         overlay.add_overlay(&size_sensor);
+        let zoom_indicator = ZoomIndicator::new();
+        overlay.add_overlay(&zoom_indicator.label);
+        let readout = PixelReadout::new();
+        overlay.add_overlay(&readout.label);
+        let crop_area = gtk4::DrawingArea::builder().can_target(false).build();
+        overlay.add_overlay(&crop_area);
+        let load_progress = gtk4::ProgressBar::builder()
+            .show_text(true)
+            .halign(gtk4::Align::Center)
+            .valign(gtk4::Align::Center)
+            .width_request(240)
+            .can_target(false)
+            .visible(false)
+            .css_classes(["load-progress"])
+            .build();
+        overlay.add_overlay(&load_progress);
         overlay.set_hexpand(true);
         overlay.set_vexpand(true);
 
@@ -162,13 +696,56 @@ impl Viewport {
         widget.append(&overlay);
 
         let camera = Rc::new(RefCell::new(Camera::new()));
-        let current_target: Rc<RefCell<Option<PathBuf>>> = Rc::new(RefCell::new(None));
 
         let drag_start_x = Rc::new(Cell::new(0.0f64));
         let drag_start_y = Rc::new(Cell::new(0.0f64));
         let drag_cam_x = Rc::new(Cell::new(0.0f32));
         let drag_cam_y = Rc::new(Cell::new(0.0f32));
         let resize_scheduled = Rc::new(Cell::new(false));
+        let strip: Rc<RefCell<Option<StripState>>> = Rc::new(RefCell::new(None));
+        let on_scroll_end: Callback<dyn Fn()> = Rc::new(RefCell::new(None));
+        let scroll_navigates = Rc::new(Cell::new(false));
+        let on_scroll_navigate: Callback<dyn Fn(i32)> = Rc::new(RefCell::new(None));
+        let crop: Rc<CropState> = Rc::default();
+        let divider_drag = Rc::new(Cell::new(false));
+        {
+            let camera = camera.clone();
+            let crop = crop.clone();
+            crop_area.set_draw_func(move |_, cr, _, _| {
+                draw_crop_band(cr, &camera.borrow(), &crop);
+            });
+        }
+
+        // ── Pointer tracking (scroll events carry no position) ────────────────
+        let pointer = Rc::new(Cell::new((0.0f64, 0.0f64)));
+        {
+            let mc = gtk4::EventControllerMotion::new();
+            let ptr = pointer.clone();
+            let r2 = renderer.clone();
+            let c2 = camera.clone();
+            let weak_widget = widget.downgrade();
+            let s2 = strip.clone();
+            let readout_motion = readout.clone();
+            mc.connect_motion(move |_, x, y| {
+                ptr.set((x, y));
+                let Some(widget) = weak_widget.upgrade() else {
+                    return;
+                };
+                let cursor = near_divider(&r2, &c2, x).then_some("col-resize");
+                widget.set_cursor_from_name(cursor);
+                // Pointer positions only map through the camera for a
+                // single image filling the viewport.
+                let split = r2.borrow().as_ref().and_then(|r| r.compare_split());
+                if split.is_some() || s2.borrow().is_some() {
+                    readout_motion.hide();
+                } else {
+                    readout_motion.update(&c2.borrow(), x, y);
+                }
+            });
+            let readout_leave = readout.clone();
+            mc.connect_leave(move |_| readout_leave.hide());
+            widget.add_controller(mc);
+        }
 
         // ── Scroll zoom ───────────────────────────────────────────────────────
         {
@@ -176,16 +753,164 @@ impl Viewport {
             let r2 = renderer.clone();
             let c2 = camera.clone();
             let p2 = picture.clone();
-            sc.connect_scroll(move |_, _, dy| {
+            let s2 = strip.clone();
+            let ose = on_scroll_end.clone();
+            let zi = zoom_indicator.clone();
+            let pointer = pointer.clone();
+            let overscroll = Cell::new(0.0f64);
+            let navigates = scroll_navigates.clone();
+            let on_navigate = on_scroll_navigate.clone();
+            let nav_scroll = Cell::new(0.0f64);
+            sc.connect_scroll(move |ctrl, _, dy| {
+                if s2.borrow().is_some() {
+                    let (vw, vh) = viewport_dims(&c2);
+                    if let Some(ref mut st) = *s2.borrow_mut() {
+                        st.layout.scroll_by(dy as f32 * SCROLL_STEP_PX, vw, vh);
+                    }
+                    trigger_strip_render(&r2, &c2, &p2, &s2);
+                    return glib::Propagation::Stop;
+                }
+                // Fit-width pages scroll like a document; Ctrl+scroll still zooms.
+                let zoom_modifier = ctrl
+                    .current_event_state()
+                    .contains(gdk::ModifierType::CONTROL_MASK);
+                if c2.borrow().fit_mode == FitMode::Width && !zoom_modifier {
+                    let overscrolled = {
+                        let mut cam = c2.borrow_mut();
+                        let step = SCROLL_STEP_PX / cam.viewport_height as f32 * 2.0;
+                        cam.scroll_fit_width(dy as f32 * step)
+                    };
+                    trigger_render(&r2, &c2, &p2);
+                    // Require a full wheel step past the edge so touchpad
+                    // momentum doesn't flip pages on its own.
+                    if overscrolled {
+                        overscroll.set(overscroll.get() + dy);
+                        if overscroll.get() >= 1.0 {
+                            overscroll.set(0.0);
+                            let cb = ose.borrow().clone();
+                            if let Some(cb) = cb {
+                                cb();
+                            }
+                        }
+                    } else {
+                        overscroll.set(0.0);
+                    }
+                    return glib::Propagation::Stop;
+                }
+                // Like a document reader: each wheel step flips an image.
+                // Touchpads add up to whole steps first.
+                if navigates.get() && !zoom_modifier {
+                    nav_scroll.set(nav_scroll.get() + dy);
+                    let steps = nav_scroll.get().trunc();
+                    if steps != 0.0 {
+                        nav_scroll.set(nav_scroll.get() - steps);
+                        let cb = on_navigate.borrow().clone();
+                        if let Some(cb) = cb {
+                            cb(steps as i32);
+                        }
+                    }
+                    return glib::Propagation::Stop;
+                }
+                {
+                    let mut cam = c2.borrow_mut();
+                    let (px, py) = pointer.get();
+                    let vw = cam.viewport_width as f32;
+                    let vh = cam.viewport_height as f32;
+                    let cursor_ndc =
+                        Vec2::new(px as f32 / vw * 2.0 - 1.0, 1.0 - py as f32 / vh * 2.0);
+                    let factor = if dy > 0.0 { 1.0 / 1.15 } else { 1.15 };
+                    cam.zoom_at(cursor_ndc, factor);
+                }
+                zi.show(&c2.borrow());
+                trigger_render(&r2, &c2, &p2);
+                glib::Propagation::Stop
+            });
+            widget.add_controller(sc);
+        }
+
+        // ── Pinch zoom ────────────────────────────────────────────────────────
+        {
+            let gz = gtk4::GestureZoom::new();
+            let r2 = renderer.clone();
+            let c2 = camera.clone();
+            let p2 = picture.clone();
+            let s2 = strip.clone();
+            let zi = zoom_indicator.clone();
+            // Scale reported by the previous update; GTK's is relative to
+            // the gesture's start.
+            let last_scale = Rc::new(Cell::new(1.0f64));
+            let ls = last_scale.clone();
+            gz.connect_begin(move |_, _| ls.set(1.0));
+            gz.connect_scale_changed(move |gesture, scale| {
+                if s2.borrow().is_some() || scale <= 0.0 {
+                    return;
+                }
+                let factor = (scale / last_scale.replace(scale)) as f32;
                 {
                     let mut cam = c2.borrow_mut();
-                    if dy > 0.0 {
-                        cam.zoom = (cam.zoom / 1.15).max(0.1);
+                    let (cx, cy) = gesture.bounding_box_center().unwrap_or((
+                        cam.viewport_width as f64 / 2.0,
+                        cam.viewport_height as f64 / 2.0,
+                    ));
+                    let vw = cam.viewport_width as f32;
+                    let vh = cam.viewport_height as f32;
+                    let center_ndc =
+                        Vec2::new(cx as f32 / vw * 2.0 - 1.0, 1.0 - cy as f32 / vh * 2.0);
+                    cam.zoom_at(center_ndc, factor);
+                }
+                zi.show(&c2.borrow());
+                trigger_render(&r2, &c2, &p2);
+            });
+            widget.add_controller(gz);
+        }
+
+        // ── Two-finger horizontal swipe ───────────────────────────────────────
+        {
+            let sc = gtk4::EventControllerScroll::new(gtk4::EventControllerScrollFlags::HORIZONTAL);
+            let r2 = renderer.clone();
+            let c2 = camera.clone();
+            let p2 = picture.clone();
+            let s2 = strip.clone();
+            let on_navigate = on_scroll_navigate.clone();
+            let swipe = Rc::new(Cell::new(0.0f64));
+            // One image per swipe, however far it goes.
+            let swiped = Rc::new(Cell::new(false));
+            let (swipe_end, swiped_end) = (swipe.clone(), swiped.clone());
+            sc.connect_scroll_end(move |_| {
+                swipe_end.set(0.0);
+                swiped_end.set(false);
+            });
+            sc.connect_scroll(move |ctrl, dx, _| {
+                if s2.borrow().is_some() || dx == 0.0 {
+                    return glib::Propagation::Proceed;
+                }
+                let touchpad = ctrl.unit() == gdk::ScrollUnit::Surface;
+                // A zoomed-in image pans sideways instead.
+                if c2.borrow().overflows().0 {
+                    let step = if touchpad {
+                        dx
                     } else {
-                        cam.zoom = (cam.zoom * 1.15).min(50.0);
+                        dx * SCROLL_STEP_PX as f64
+                    };
+                    c2.borrow_mut().pan_by(Vec2::new(-step as f32, 0.0));
+                    trigger_render(&r2, &c2, &p2);
+                    return glib::Propagation::Stop;
+                }
+                if swiped.get() {
+                    return glib::Propagation::Stop;
+                }
+                swipe.set(swipe.get() + dx);
+                let threshold = if touchpad { SWIPE_DISTANCE_PX } else { 1.0 };
+                if swipe.get().abs() >= threshold {
+                    let step = swipe.get().signum() as i32;
+                    swipe.set(0.0);
+                    // Tilt wheels send no scroll-end, so only touchpads latch.
+                    swiped.set(touchpad);
+                    let cb = on_navigate.borrow().clone();
+                    if let Some(cb) = cb {
+                        cb(step);
                     }
                 }
-                trigger_render(&r2, &c2, &p2);
                 glib::Propagation::Stop
             });
             widget.add_controller(sc);
@@ -201,12 +926,25 @@ impl Viewport {
             let dsy = drag_start_y.clone();
             let dcx = drag_cam_x.clone();
             let dcy = drag_cam_y.clone();
+            let sb = strip.clone();
+            let crop_begin = crop.clone();
+            let rb = renderer.clone();
+            let divider_begin = divider_drag.clone();
             dc.connect_drag_begin(move |_, x, y| {
+                if crop_begin.active.get() {
+                    let start = Vec2::new(x as f32, y as f32);
+                    crop_begin.band.set(Some((start, start)));
+                    return;
+                }
+                divider_begin.set(near_divider(&rb, &cb, x));
                 let cam = cb.borrow();
                 dsx.set(x);
                 dsy.set(y);
                 dcx.set(cam.position.x);
-                dcy.set(cam.position.y);
+                match *sb.borrow() {
+                    Some(ref st) => dcy.set(st.layout.scroll),
+                    None => dcy.set(cam.position.y),
+                }
             });
 
             let cu = camera.clone();
@@ -214,55 +952,174 @@ impl Viewport {
             let p2 = picture.clone();
             let dcx2 = drag_cam_x.clone();
             let dcy2 = drag_cam_y.clone();
+            let s2 = strip.clone();
+            let crop_update = crop.clone();
+            let crop_area_update = crop_area.clone();
+            let dsx2 = drag_start_x.clone();
+            let divider_update = divider_drag.clone();
             dc.connect_drag_update(move |_, dx, dy| {
-                {
-                    let mut cam = cu.borrow_mut();
-                    let vw = cam.viewport_width as f32;
-                    let vh = cam.viewport_height as f32;
-                    if vw > 0.0 && vh > 0.0 {
-                        cam.position.x = dcx2.get() - (dx as f32 / vw) * 2.0 / cam.zoom;
-                        cam.position.y = dcy2.get() + (dy as f32 / vh) * 2.0 / cam.zoom;
+                if let Some((start, _)) = crop_update.band.get() {
+                    let end = start + Vec2::new(dx as f32, dy as f32);
+                    crop_update.band.set(Some((start, end)));
+                    crop_area_update.queue_draw();
+                    return;
+                }
+                if divider_update.get() {
+                    let (vw, _) = viewport_dims(&cu);
+                    if let Some(ref mut r) = *r2.borrow_mut() {
+                        r.set_compare_split(((dsx2.get() + dx) as f32) / vw.max(1.0));
+                    }
+                    trigger_render(&r2, &cu, &p2);
+                    return;
+                }
+                if s2.borrow().is_some() {
+                    let (vw, vh) = viewport_dims(&cu);
+                    if let Some(ref mut st) = *s2.borrow_mut() {
+                        st.layout.scroll = dcy2.get() - dy as f32;
+                        st.layout.clamp(vw, vh);
                     }
+                    trigger_strip_render(&r2, &cu, &p2, &s2);
+                    return;
                 }
+                cu.borrow_mut().pan_from(
+                    Vec2::new(dcx2.get(), dcy2.get()),
+                    Vec2::new(dx as f32, dy as f32),
+                );
                 trigger_render(&r2, &cu, &p2);
             });
 
+            let cu = camera.clone();
+            let crop_end = crop.clone();
+            let crop_area_end = crop_area.clone();
+            dc.connect_drag_end(move |_, _, _| {
+                divider_drag.set(false);
+                let Some((a, b)) = crop_end.band.take() else {
+                    return;
+                };
+                crop_area_end.queue_draw();
+                let rect = crop_rect(&cu.borrow(), a, b);
+                let cb = crop_end.on_select.borrow().clone();
+                if let (Some(rect), Some(cb)) = (rect, cb) {
+                    cb(rect);
+                }
+            });
+
             widget.add_controller(dc);
         }
 
-        // ── Double-click reset ────────────────────────────────────────────────
+        // ── Double-click: toggle fit / actual size ────────────────────────────
         {
             let cc = gtk4::GestureClick::new();
             cc.set_button(1);
             let c2 = camera.clone();
             let r2 = renderer.clone();
             let p2 = picture.clone();
-            cc.connect_released(move |_, n, _, _| {
-                if n == 2 {
+            let s2 = strip.clone();
+            let zi = zoom_indicator.clone();
+            let crop_click = crop.clone();
+            cc.connect_released(move |_, n, x, y| {
+                if n == 2 && s2.borrow().is_none() && !crop_click.active.get() {
                     {
                         let mut cam = c2.borrow_mut();
-                        cam.zoom = 1.0;
-                        cam.position.x = 0.0;
-                        cam.position.y = 0.0;
+                        let vw = cam.viewport_width as f32;
+                        let vh = cam.viewport_height as f32;
+                        let cursor_ndc =
+                            Vec2::new(x as f32 / vw * 2.0 - 1.0, 1.0 - y as f32 / vh * 2.0);
+                        cam.toggle_actual_size_at(cursor_ndc);
                     }
+                    zi.show(&c2.borrow());
                     trigger_render(&r2, &c2, &p2);
                 }
             });
             widget.add_controller(cc);
         }
 
+        // ── Minimap ───────────────────────────────────────────────────────────
+        let minimap = Minimap::new(camera.clone());
+        overlay.add_overlay(&minimap.area);
+        {
+            // Every presented frame swaps the paintable, so this follows
+            // all camera changes.
+            let r2 = renderer.clone();
+            let s2 = strip.clone();
+            let mm = minimap.clone();
+            picture.connect_paintable_notify(move |_| {
+                let split = r2.borrow().as_ref().and_then(|r| r.compare_split());
+                mm.refresh(split.is_none() && s2.borrow().is_none());
+            });
+
+            let dc = gtk4::GestureDrag::new();
+            let start = Rc::new(Cell::new((0.0f64, 0.0f64)));
+            let pan_to = {
+                let r2 = renderer.clone();
+                let c2 = camera.clone();
+                let p2 = picture.clone();
+                let mm = minimap.clone();
+                move |x: f64, y: f64| {
+                    if let Some(point) = mm.image_point(x, y) {
+                        c2.borrow_mut().center_on(point);
+                        trigger_render(&r2, &c2, &p2);
+                    }
+                }
+            };
+            let pan_begin = pan_to.clone();
+            let start_begin = start.clone();
+            dc.connect_drag_begin(move |gesture, x, y| {
+                // Keep the viewport's own drag and click gestures out of it.
+                gesture.set_state(gtk4::EventSequenceState::Claimed);
+                start_begin.set((x, y));
+                pan_begin(x, y);
+            });
+            dc.connect_drag_update(move |_, dx, dy| {
+                let (x, y) = start.get();
+                pan_to(x + dx, y + dy);
+            });
+            minimap.area.add_controller(dc);
+        }
+
         // ── Automatic resize (deduplicated) ───────────────────────────────────
         {
             let r2 = renderer.clone();
             let c2 = camera.clone();
             let p2 = picture.clone();
             let rs = resize_scheduled.clone();
+            let s2 = strip.clone();
+            let settle_generation = Rc::new(Cell::new(0u64));
             size_sensor.connect_resize(move |_, new_w, new_h| {
                 let new_w = new_w as u32;
                 let new_h = new_h as u32;
                 if new_w == 0 || new_h == 0 {
                     return;
                 }
+
+                // Hold the render targets at their current size until the
+                // size stops changing, rather than reallocating per frame.
+                if let Some(ref mut r) = *r2.borrow_mut() {
+                    r.resize_held = true;
+                }
+                let generation = settle_generation.get().wrapping_add(1);
+                settle_generation.set(generation);
+                {
+                    let r3 = r2.clone();
+                    let c3 = c2.clone();
+                    let p3 = p2.clone();
+                    let s3 = s2.clone();
+                    let sg = settle_generation.clone();
+                    glib::timeout_add_local_once(RESIZE_SETTLE, move || {
+                        if sg.get() != generation {
+                            return;
+                        }
+                        if let Some(ref mut r) = *r3.borrow_mut() {
+                            r.resize_held = false;
+                        }
+                        if s3.borrow().is_some() {
+                            trigger_strip_render(&r3, &c3, &p3, &s3);
+                        } else {
+                            trigger_render(&r3, &c3, &p3);
+                        }
+                    });
+                }
+
                 // If a resize render is already scheduled, skip.
                 // The scheduled callback picks up the latest size via sync_size.
                 if rs.get() {
@@ -273,20 +1130,41 @@ impl Viewport {
                 let c3 = c2.clone();
                 let p3 = p2.clone();
                 let rs2 = rs.clone();
+                let s3 = s2.clone();
                 glib::idle_add_local_once(move || {
                     rs2.set(false);
-                    trigger_render(&r3, &c3, &p3);
+                    if s3.borrow().is_some() {
+                        trigger_strip_render(&r3, &c3, &p3, &s3);
+                    } else {
+                        trigger_render(&r3, &c3, &p3);
+                    }
                 });
             });
         }
 
+        // ── Scale factor (window moved to a monitor with another DPI) ─────────
+        {
+            let r2 = renderer.clone();
+            let c2 = camera.clone();
+            let s2 = strip.clone();
+            picture.connect_scale_factor_notify(move |p| {
+                // sync_size resizes the render target to the new scale.
+                if s2.borrow().is_some() {
+                    trigger_strip_render(&r2, &c2, p, &s2);
+                } else {
+                    trigger_render(&r2, &c2, p);
+                }
+            });
+        }
+
         Self {
             widget,
             picture,
             _offload: offload,
             camera,
             renderer,
-            current_target,
+            load_generation: Arc::new(AtomicU64::new(0)),
+            pending_loads: Rc::default(),
             drag_start_x,
             drag_start_y,
             drag_cam_x,
@@ -295,84 +1173,136 @@ impl Viewport {
             animation: Rc::new(RefCell::new(None)),
             anim_generation: Rc::new(Cell::new(0)),
             resize_scheduled,
+            strip,
+            on_scroll_end,
+            scroll_navigates,
+            on_scroll_navigate,
+            on_load_error: Rc::default(),
+            on_load_stats: Rc::default(),
+            load_stats: Rc::default(),
+            zoom_indicator,
+            crop,
+            crop_area,
+            cache_capacity: Cell::new(DEFAULT_CACHE_CAPACITY),
+            prefetch_wanted: Arc::new(Mutex::new(HashSet::new())),
+            prefetch_in_flight: Rc::new(RefCell::new(HashSet::new())),
+            compare_target: Rc::new(RefCell::new(None)),
+            readout,
+            minimap,
+            load_progress,
+            load_progress_delay: Cell::new(DEFAULT_LOAD_PROGRESS_DELAY),
+            transition_direction: Cell::new(0),
+            transition_generation: Rc::new(Cell::new(0)),
         }
     }
 
     // ── Public API ────────────────────────────────────────────────────────────
 
-    pub fn load_image<F>(&self, path: PathBuf, on_dims: F)
+    /// Load `path` and show it with `transform`. The transform is applied
+    /// together with the new texture, so the outgoing image is never drawn
+    /// with the incoming image's rotation or flip.
+    pub fn load_image<F>(&self, path: PathBuf, transform: ImageTransform, on_dims: F)
+    where
+        F: FnOnce(u32, u32) + 'static,
+    {
+        self.load_page(path, 0, transform, on_dims);
+    }
+
+    /// Like `load_image`, for page `page` (0-based) of a multi-page file.
+    pub fn load_page<F>(&self, path: PathBuf, page: usize, transform: ImageTransform, on_dims: F)
     where
         F: FnOnce(u32, u32) + 'static,
     {
-        *self.current_target.borrow_mut() = Some(path.clone());
+        self.load_generation.fetch_add(1, Ordering::Relaxed);
         self.stop_animation();
+        self.load_progress.set_visible(false);
+        self.readout.set_image(&path, page);
+        self.minimap.set_image(&path);
 
         let has_vulkan = self.renderer.borrow().is_some();
 
-        if has_vulkan && might_be_animated(&path) {
-            self.load_animated_image(path, on_dims);
+        if has_vulkan && page == 0 && might_be_animated(&path) {
+            self.load_animated_image(path, transform, on_dims);
         } else if has_vulkan {
-            self.load_image_vulkan(path, on_dims);
+            self.load_image_vulkan(path, page, transform, on_dims);
         } else {
-            self.load_image_software(path, on_dims);
+            self.load_image_software(path, page, on_dims);
         }
     }
 
-    pub fn prefetch(&self, path: PathBuf) {
-        // Allow RAW prefetch — decode runs on rayon, upload is cheap
-        if might_be_animated(&path) {
-            return;
+    /// Decode neighbours of the current image into the texture cache,
+    /// nearest first, keeping only as many as fit alongside it. Preloads
+    /// requested for an earlier position are skipped or dropped on arrival.
+    pub fn prefetch_neighbors(&self, paths: Vec<PathBuf>) {
+        let keep = self.cache_capacity.get().saturating_sub(1);
+        let paths: Vec<PathBuf> = paths
+            .into_iter()
+            .filter(|p| !might_be_animated(p))
+            .take(keep)
+            .collect();
+        if let Ok(mut wanted) = self.prefetch_wanted.lock() {
+            *wanted = paths.iter().cloned().collect();
         }
+        for path in paths {
+            self.prefetch(path);
+        }
+    }
 
+    fn prefetch(&self, path: PathBuf) {
+        // Allow RAW prefetch — decode runs on rayon, upload is cheap
         let should_prefetch = {
-            let opt = self.renderer.borrow();
-            match opt.as_ref() {
-                Some(r) => !r.is_cached(&path),
+            let mut opt = self.renderer.borrow_mut();
+            match opt.as_mut() {
+                Some(r) if r.is_cached(&path) => {
+                    r.touch(&path);
+                    false
+                }
+                Some(_) => true,
                 None => false,
             }
         };
-        if !should_prefetch {
+        if !should_prefetch || !self.prefetch_in_flight.borrow_mut().insert(path.clone()) {
             return;
         }
 
         let is_raw_file = raw::is_raw(&path);
 
-        let (tx, rx) = oneshot::channel::<Option<DecodedImage>>();
+        let (tx, rx) = oneshot::channel::<Option<(DecodedImage, LoadStats)>>();
         let path_load = path.clone();
+        let wanted = self.prefetch_wanted.clone();
         rayon::spawn(move || {
-            let result = if is_raw_file {
+            // The user may have jumped elsewhere while this sat in the queue.
+            let still_wanted = wanted.lock().is_ok_and(|w| w.contains(&path_load));
+            let result = if !still_wanted {
+                None
+            } else if is_raw_file {
                 decode_raw_image(&path_load)
             } else {
-                decode_standard_image(&path_load)
+                decode_standard_image(&path_load).ok()
             };
-            let _ = tx.send(result);
+            let _ = tx.send(result.map(|decoded| {
+                let stats = LoadStats::new(&path_load, 0, &decoded);
+                (decoded, stats)
+            }));
         });
 
         let r2 = self.renderer.clone();
+        let in_flight = self.prefetch_in_flight.clone();
+        let wanted = self.prefetch_wanted.clone();
+        let known_stats = self.load_stats.clone();
         glib::spawn_future_local(async move {
-            let Some(decoded) = rx.await.ok().flatten() else {
+            let decoded = rx.await.ok().flatten();
+            in_flight.borrow_mut().remove(&path);
+            let Some((decoded, stats)) = decoded else {
                 return;
             };
+            if !wanted.lock().is_ok_and(|w| w.contains(&path)) {
+                return;
+            }
             let mut opt = r2.borrow_mut();
             if let Some(ref mut r) = *opt {
-                match &decoded {
-                    DecodedImage::Rgba8 {
-                        rgba,
-                        width,
-                        height,
-                        ..
-                    } => {
-                        r.cache_only(&path, rgba, *width, *height);
-                    }
-                    DecodedImage::Rgba16 {
-                        data,
-                        width,
-                        height,
-                        ..
-                    } => {
-                        r.cache_only_16bit(&path, data, *width, *height);
-                    }
-                }
+                cache_decoded(r, &path, &decoded);
+                remember_stats(&known_stats, r, &path, stats);
             }
         });
     }
@@ -387,108 +1317,556 @@ impl Viewport {
                 return;
             }
         }
-        trigger_render(&self.renderer, &self.camera, &self.picture);
+        self.redraw();
+    }
+
+    /// Replace rotation and mirroring together, e.g. from EXIF orientation.
+    pub fn set_transform(&self, transform: ImageTransform) {
+        self.camera.borrow_mut().set_transform(transform);
+        self.request_render();
+    }
+
+    /// Mirror the image on screen. Applied after rotation, so `h` always
+    /// swaps left and right as the user sees them.
+    pub fn set_flip(&self, h: bool, v: bool) {
+        {
+            let mut cam = self.camera.borrow_mut();
+            cam.flip_h = h;
+            cam.flip_v = v;
+        }
+        self.request_render();
+    }
+
+    /// Turn the image by `degrees` on top of its rotation to level it,
+    /// refitting so the corners stay in view.
+    pub fn set_straighten(&self, degrees: f32) {
+        self.camera.borrow_mut().set_straighten_degrees(degrees);
+        self.request_render();
     }
 
+    /// Set the straighten angle for the image about to be loaded without
+    /// redrawing.
+    pub fn prepare_straighten(&self, degrees: f32) {
+        self.camera.borrow_mut().set_straighten_degrees(degrees);
+    }
+
+    /// Step the zoom around the viewport center, clamped like scroll zoom.
     pub fn zoom_in(&self) {
+        self.camera.borrow_mut().zoom_centered(ZOOM_STEP);
+        self.zoom_indicator.show(&self.camera.borrow());
+        self.request_render();
+    }
+
+    pub fn zoom_out(&self) {
+        self.camera.borrow_mut().zoom_centered(1.0 / ZOOM_STEP);
+        self.zoom_indicator.show(&self.camera.borrow());
+        self.request_render();
+    }
+
+    /// Discard what is cached for `path` after its file was rewritten, so
+    /// the next load decodes it afresh.
+    pub fn forget(&self, path: &Path) {
+        self.load_stats.borrow_mut().remove(path);
+        self.minimap.forget(path);
+        if let Some(ref mut r) = *self.renderer.borrow_mut() {
+            r.forget(path);
+        }
+    }
+
+    /// Displayed size in percent of actual size; `None` with no image.
+    pub fn zoom_percent(&self) -> Option<f32> {
+        self.camera.borrow().zoom_percent()
+    }
+
+    /// Zoom to a typed percentage of actual size, recentering the image.
+    pub fn set_zoom_percent(&self, percent: f32) {
+        self.camera.borrow_mut().set_zoom_percent(percent);
+        self.zoom_indicator.show(&self.camera.borrow());
+        self.request_render();
+    }
+
+    /// Called with the new percentage whenever the user changes the zoom.
+    pub fn connect_zoom_changed(&self, f: impl Fn(f32) + 'static) {
+        *self.zoom_indicator.on_change.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Pan one step in the direction of `(dx, dy)` (each -1, 0 or 1) along
+    /// the axes where the image overflows the viewport. Returns false when
+    /// it fits along the requested axis, so the caller can navigate instead.
+    pub fn pan_step(&self, dx: i32, dy: i32) -> bool {
+        if self.strip.borrow().is_some() {
+            return false;
+        }
         {
             let mut cam = self.camera.borrow_mut();
-            cam.zoom = (cam.zoom * 1.25).min(50.0);
+            let (over_x, over_y) = cam.overflows();
+            if (dx != 0 && !over_x) || (dy != 0 && !over_y) {
+                return false;
+            }
+            // Moving the view right slides the image left.
+            cam.pan_by(Vec2::new(-dx as f32, -dy as f32) * SCROLL_STEP_PX);
+        }
+        self.request_render();
+        true
+    }
+
+    /// Zoom to fit: show the whole image, centered.
+    pub fn reset_view(&self) {
+        self.camera.borrow_mut().fit_to_viewport();
+        self.zoom_indicator.show(&self.camera.borrow());
+        self.request_render();
+    }
+
+    /// Show the image at 1:1, one image pixel per screen pixel.
+    pub fn actual_size(&self) {
+        self.camera.borrow_mut().actual_size();
+        self.zoom_indicator.show(&self.camera.borrow());
+        self.request_render();
+    }
+
+    /// Fill the viewport horizontally; vertical panning scrolls the overflow.
+    pub fn fit_width(&self) {
+        self.camera.borrow_mut().fit_width();
+        self.zoom_indicator.show(&self.camera.borrow());
+        self.request_render();
+    }
+
+    /// Fill the viewport vertically; horizontal panning scrolls the overflow.
+    pub fn fit_height(&self) {
+        self.camera.borrow_mut().fit_height();
+        self.zoom_indicator.show(&self.camera.borrow());
+        self.request_render();
+    }
+
+    /// Turn crop mode on or off. While on, dragging selects a region
+    /// instead of panning; see `connect_crop`.
+    pub fn set_crop_mode(&self, active: bool) {
+        let active = active && self.strip.borrow().is_none();
+        self.crop.active.set(active);
+        self.crop.band.set(None);
+        self.crop_area.queue_draw();
+        self.widget
+            .set_cursor_from_name(active.then_some("crosshair"));
+    }
+
+    pub fn is_crop_mode(&self) -> bool {
+        self.crop.active.get()
+    }
+
+    /// Called with the selected source-image region when a crop drag ends.
+    pub fn connect_crop(&self, f: impl Fn(CropRect) + 'static) {
+        *self.crop.on_select.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Called when a fit-width page is scrolled past its bottom edge.
+    pub fn connect_scroll_end(&self, f: impl Fn() + 'static) {
+        *self.on_scroll_end.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Make the scroll wheel change image rather than zoom; Ctrl+scroll
+    /// still zooms. Sideways swipes change image either way.
+    pub fn set_scroll_navigates(&self, navigates: bool) {
+        self.scroll_navigates.set(navigates);
+    }
+
+    /// Called with +n / -n to move n images forward / back: wheel steps
+    /// while the wheel navigates, and sideways swipes.
+    pub fn connect_scroll_navigate(&self, f: impl Fn(i32) + 'static) {
+        *self.on_scroll_navigate.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Called with the path and reason when the requested image can't be
+    /// decoded. Only fires while that image is still the one wanted.
+    pub fn connect_load_error(&self, f: impl Fn(&Path, &str) + 'static) {
+        *self.on_load_error.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Called with the format and load time of each image once shown.
+    pub fn connect_load_stats(&self, f: impl Fn(&LoadStats) + 'static) {
+        *self.on_load_stats.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Name of the GPU rendering the view, or `None` on the software path.
+    pub fn gpu_name(&self) -> Option<String> {
+        self.renderer
+            .borrow()
+            .as_ref()
+            .map(|r| r.device_name().to_string())
+    }
+
+    /// Kind of GPU and Vulkan version, or `None` on the software path.
+    pub fn gpu_summary(&self) -> Option<String> {
+        self.renderer.borrow().as_ref().map(|r| r.device_summary())
+    }
+
+    pub fn get_view_state(&self) -> (f32, f32, f32) {
+        let cam = self.camera.borrow();
+        (cam.zoom, cam.position.x, cam.position.y)
+    }
+
+    pub fn prepare_view(&self, zoom: f32, pos_x: f32, pos_y: f32) {
+        let mut cam = self.camera.borrow_mut();
+        // Fit presets carry over to the next image; they are re-applied once
+        // its dimensions are known.
+        if cam.fit_mode != FitMode::Free {
+            return;
         }
+        cam.zoom = zoom;
+        cam.position.x = pos_x;
+        cam.position.y = pos_y;
+    }
+
+    pub fn set_alpha_background(&self, background: AlphaBackground) {
         {
             let mut opt = self.renderer.borrow_mut();
             if let Some(ref mut r) = *opt {
+                r.alpha_background = background;
                 r.dirty = true;
             } else {
                 return;
             }
         }
-        trigger_render(&self.renderer, &self.camera, &self.picture);
+        self.redraw();
     }
 
-    pub fn zoom_out(&self) {
+    /// Apply brightness/contrast and redraw, e.g. while a slider drags.
+    pub fn set_adjustments(&self, adjustments: Adjustments) {
         {
-            let mut cam = self.camera.borrow_mut();
-            cam.zoom = (cam.zoom / 1.25).max(0.1);
+            let mut opt = self.renderer.borrow_mut();
+            if let Some(ref mut r) = *opt {
+                r.adjustments = adjustments;
+                r.dirty = true;
+            } else {
+                return;
+            }
+        }
+        self.redraw();
+    }
+
+    /// Set the adjustments for the image about to be loaded without
+    /// redrawing, so the outgoing image is never shown with them.
+    pub fn prepare_adjustments(&self, adjustments: Adjustments) {
+        if let Some(ref mut r) = *self.renderer.borrow_mut() {
+            r.adjustments = adjustments;
+        }
+    }
+
+    /// Animate changes of image with `style`; the Vulkan path only.
+    pub fn set_image_transition(&self, style: ImageTransition) {
+        if let Some(r) = self.renderer.borrow_mut().as_mut() {
+            r.set_transition_style(style);
+        }
+    }
+
+    /// Let the image be panned entirely out of view, or keep part of it on
+    /// screen.
+    pub fn set_free_pan(&self, free: bool) {
+        self.camera.borrow_mut().set_free_pan(free);
+        self.request_render();
+    }
+
+    /// Play image transitions only while `enabled`; off for users who asked
+    /// the desktop for reduced motion.
+    pub fn set_animations_enabled(&self, enabled: bool) {
+        if let Some(r) = self.renderer.borrow_mut().as_mut() {
+            r.set_animations_enabled(enabled);
+        }
+    }
+
+    /// Smooth the image's edges with multisampling; the Vulkan path only,
+    /// on GPUs that support it.
+    pub fn set_antialiasing(&self, enabled: bool) {
+        if let Some(r) = self.renderer.borrow_mut().as_mut() {
+            r.set_antialiasing(enabled);
+        }
+        self.redraw();
+    }
+
+    /// Break up banding in smooth gradients, e.g. tone-mapped HDR, with
+    /// ordered dither on the Vulkan path. Off keeps output pixel-exact.
+    pub fn set_dither(&self, enabled: bool) {
+        if let Some(r) = self.renderer.borrow_mut().as_mut() {
+            r.set_dither(enabled);
+        }
+        self.redraw();
+    }
+
+    /// How long a load must run before its progress indicator shows.
+    pub fn set_load_progress_delay(&self, delay: Duration) {
+        self.load_progress_delay.set(delay);
+    }
+
+    /// Direction of the next `load_image`: +1 forward, -1 back, 0 a jump.
+    /// A slide transition moves that way.
+    pub fn prepare_transition(&self, direction: i32) {
+        self.transition_direction.set(direction);
+    }
+
+    pub fn toggle_enhance(&self) {
+        {
+            let mut opt = self.renderer.borrow_mut();
+            if let Some(ref mut r) = *opt {
+                r.toggle_pass(ProcessingPass::Enhance);
+                r.dirty = true;
+            } else {
+                return;
+            }
+        }
+        self.redraw();
+    }
+
+    pub fn toggle_sharpen(&self) {
+        {
+            let mut opt = self.renderer.borrow_mut();
+            if let Some(ref mut r) = *opt {
+                r.toggle_pass(ProcessingPass::Sharpen);
+                r.dirty = true;
+            } else {
+                return;
+            }
         }
+        self.redraw();
+    }
+
+    pub fn toggle_denoise(&self) {
         {
             let mut opt = self.renderer.borrow_mut();
             if let Some(ref mut r) = *opt {
+                r.toggle_pass(ProcessingPass::Denoise);
                 r.dirty = true;
             } else {
                 return;
             }
-        }
-        trigger_render(&self.renderer, &self.camera, &self.picture);
+        }
+        self.redraw();
+    }
+
+    /// Number of decoded images kept on the GPU for instant back-and-forth
+    /// navigation. Least recently viewed images are evicted first.
+    pub fn set_cache_capacity(&self, capacity: usize) {
+        self.cache_capacity.set(capacity);
+        if let Some(ref mut r) = *self.renderer.borrow_mut() {
+            r.set_cache_capacity(capacity);
+        }
+    }
+
+    /// Compare the current image with `path`, with panning and zooming
+    /// shared between the two: side by side with a draggable divider, or
+    /// swapped in while peeking. `None` leaves compare mode. Returns `false`
+    /// without the GPU renderer or in strip mode.
+    pub fn set_compare(&self, path: Option<PathBuf>, layout: CompareLayout) -> bool {
+        if self.renderer.borrow().is_none() || self.is_strip_mode() {
+            return false;
+        }
+        *self.compare_target.borrow_mut() = path.clone();
+        let cached = {
+            let mut opt = self.renderer.borrow_mut();
+            let Some(ref mut r) = *opt else { return false };
+            match path {
+                Some(ref p) if !r.is_cached(p) => false,
+                _ => {
+                    r.set_compare(path.as_deref(), layout);
+                    true
+                }
+            }
+        };
+        if path.is_none() {
+            self.widget.set_cursor(None);
+        }
+        if cached {
+            self.request_render();
+            return true;
+        }
+        let Some(path) = path else { return true };
+
+        let (tx, rx) = oneshot::channel::<Result<DecodedImage, String>>();
+        let path_load = path.clone();
+        rayon::spawn(move || {
+            let _ = tx.send(decode_image(&path_load, 0));
+        });
+
+        let r2 = self.renderer.clone();
+        let c2 = self.camera.clone();
+        let p2 = self.picture.clone();
+        let target = self.compare_target.clone();
+        let on_error = self.on_error.clone();
+        glib::spawn_future_local(async move {
+            let Ok(result) = rx.await else { return };
+            if target.borrow().as_deref() != Some(path.as_path()) {
+                return;
+            }
+            let decoded = match result {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    *target.borrow_mut() = None;
+                    on_error(format!("Could not compare: {e}"));
+                    return;
+                }
+            };
+            {
+                let mut opt = r2.borrow_mut();
+                let Some(ref mut r) = *opt else { return };
+                cache_decoded(r, &path, &decoded);
+                r.set_compare(Some(&path), layout);
+            }
+            trigger_render(&r2, &c2, &p2);
+        });
+        true
+    }
+
+    pub fn is_compare_mode(&self) -> bool {
+        self.compare_target.borrow().is_some()
+    }
+
+    /// Toggle the readout of the pixel under the pointer. Returns whether
+    /// it is now on; always off with the software renderer, which doesn't
+    /// track the camera.
+    pub fn toggle_pixel_readout(&self) -> bool {
+        let enabled = self.renderer.borrow().is_some() && !self.readout.is_enabled();
+        self.readout.set_enabled(enabled);
+        enabled
+    }
+
+    /// Show the compare image in place of the current one while `peek`
+    /// holds, e.g. for as long as a key is down.
+    pub fn set_compare_peek(&self, peek: bool) {
+        {
+            let mut opt = self.renderer.borrow_mut();
+            let Some(ref mut r) = *opt else { return };
+            r.set_compare_peek(peek);
+        }
+        trigger_render(&self.renderer, &self.camera, &self.picture);
+    }
+
+    /// Enter continuous vertical reading: `paths` are stacked top to bottom
+    /// at viewport width, scrolled so `start` is at the top. Textures are
+    /// decoded as they approach the viewport and left to the cache's LRU once
+    /// they scroll away. Returns false without Vulkan, which has no
+    /// multi-image path.
+    pub fn enter_strip_mode(&self, paths: Vec<PathBuf>, start: usize) -> bool {
+        if paths.is_empty() || self.renderer.borrow().is_none() {
+            return false;
+        }
+        self.stop_animation();
+        self.set_crop_mode(false);
+        self.set_compare(None, CompareLayout::default());
+        // The strip keeps a screen's worth of neighbours resident; an entry
+        // cap below that would evict and re-decode them on every scroll.
+        if let Some(ref mut r) = *self.renderer.borrow_mut() {
+            r.set_cache_capacity(usize::MAX);
+        }
+        // Decodes queued for the single-image view are skipped; ones already
+        // running still land in the cache, silently.
+        self.load_generation.fetch_add(1, Ordering::Relaxed);
+
+        let (vw, vh) = viewport_dims(&self.camera);
+        let mut layout = StripLayout::new(paths.clone());
+        layout.scroll_to(start, vw, vh);
+        *self.strip.borrow_mut() = Some(StripState {
+            layout,
+            pending: HashSet::new(),
+        });
+
+        // Header-only size probe so the strip has real proportions before
+        // every image is decoded. RAW files keep the default until decoded.
+        let (tx, rx) = oneshot::channel::<Vec<Option<(u32, u32)>>>();
+        rayon::spawn(move || {
+            use rayon::prelude::*;
+            let dims = paths
+                .par_iter()
+                .map(|p| image::image_dimensions(p).ok())
+                .collect();
+            let _ = tx.send(dims);
+        });
+
+        let r2 = self.renderer.clone();
+        let c2 = self.camera.clone();
+        let p2 = self.picture.clone();
+        let s2 = self.strip.clone();
+        glib::spawn_future_local(async move {
+            let Ok(dims) = rx.await else { return };
+            {
+                let (vw, _) = viewport_dims(&c2);
+                let mut opt = s2.borrow_mut();
+                let Some(ref mut st) = *opt else { return };
+                for (i, d) in dims.into_iter().enumerate() {
+                    if let Some((w, h)) = d {
+                        st.layout.set_aspect(i, w, h, vw);
+                    }
+                }
+            }
+            trigger_strip_render(&r2, &c2, &p2, &s2);
+        });
+
+        trigger_strip_render(&self.renderer, &self.camera, &self.picture, &self.strip);
+        true
     }
 
-    pub fn reset_view(&self) {
-        {
-            let mut cam = self.camera.borrow_mut();
-            cam.zoom = 1.0;
-            cam.position.x = 0.0;
-            cam.position.y = 0.0;
-        }
-        {
-            let mut opt = self.renderer.borrow_mut();
-            if let Some(ref mut r) = *opt {
-                r.dirty = true;
-            } else {
-                return;
-            }
+    /// Leave strip mode, returning the index of the image at the center of
+    /// the viewport, or `None` if strip mode wasn't active.
+    pub fn exit_strip_mode(&self) -> Option<usize> {
+        let st = self.strip.borrow_mut().take()?;
+        if let Some(ref mut r) = *self.renderer.borrow_mut() {
+            r.set_cache_capacity(self.cache_capacity.get());
         }
-        trigger_render(&self.renderer, &self.camera, &self.picture);
+        let (vw, vh) = viewport_dims(&self.camera);
+        Some(st.layout.index_at_center(vw, vh))
     }
 
-    pub fn get_view_state(&self) -> (f32, f32, f32) {
-        let cam = self.camera.borrow();
-        (cam.zoom, cam.position.x, cam.position.y)
+    pub fn is_strip_mode(&self) -> bool {
+        self.strip.borrow().is_some()
     }
 
-    pub fn prepare_view(&self, zoom: f32, pos_x: f32, pos_y: f32) {
-        let mut cam = self.camera.borrow_mut();
-        cam.zoom = zoom;
-        cam.position.x = pos_x;
-        cam.position.y = pos_y;
+    /// Scroll the strip so image `index` is at the top of the viewport.
+    pub fn strip_scroll_to(&self, index: usize) {
+        {
+            let (vw, vh) = viewport_dims(&self.camera);
+            let mut opt = self.strip.borrow_mut();
+            let Some(ref mut st) = *opt else { return };
+            st.layout.scroll_to(index, vw, vh);
+        }
+        trigger_strip_render(&self.renderer, &self.camera, &self.picture, &self.strip);
     }
 
-    pub fn toggle_enhance(&self) {
+    // ── Private: render request ───────────────────────────────────────────────
+
+    /// Mark the renderer dirty and present a new frame. No-op without Vulkan.
+    fn request_render(&self) {
         {
             let mut opt = self.renderer.borrow_mut();
             if let Some(ref mut r) = *opt {
-                r.toggle_pass(ProcessingPass::Enhance);
                 r.dirty = true;
             } else {
                 return;
             }
         }
-        trigger_render(&self.renderer, &self.camera, &self.picture);
+        self.redraw();
     }
 
-    pub fn toggle_sharpen(&self) {
-        {
-            let mut opt = self.renderer.borrow_mut();
-            if let Some(ref mut r) = *opt {
-                r.toggle_pass(ProcessingPass::Sharpen);
-                r.dirty = true;
-            } else {
-                return;
-            }
+    /// Present a frame for whichever mode is active.
+    fn redraw(&self) {
+        if self.strip.borrow().is_some() {
+            trigger_strip_render(&self.renderer, &self.camera, &self.picture, &self.strip);
+        } else {
+            trigger_render(&self.renderer, &self.camera, &self.picture);
         }
-        trigger_render(&self.renderer, &self.camera, &self.picture);
     }
 
-    pub fn toggle_denoise(&self) {
-        {
-            let mut opt = self.renderer.borrow_mut();
-            if let Some(ref mut r) = *opt {
-                r.toggle_pass(ProcessingPass::Denoise);
-                r.dirty = true;
-            } else {
-                return;
-            }
+    /// Stop background work and release the GPU before the app quits.
+    /// Decodes not yet started are skipped and running ones discarded on
+    /// arrival; animation and transition timers stop at their next tick.
+    pub fn shutdown(&self) {
+        self.load_generation.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut wanted) = self.prefetch_wanted.lock() {
+            wanted.clear();
         }
-        trigger_render(&self.renderer, &self.camera, &self.picture);
+        self.stop_animation();
+        self.transition_generation
+            .set(self.transition_generation.get().wrapping_add(1));
+        // GTK may still hold the last frame; let go of it before the
+        // renderer waits for the GPU and frees what backs it.
+        self.picture.set_paintable(None::<&gdk::Paintable>);
+        self.renderer.borrow_mut().take();
     }
 
     // ── Private: stop animation ───────────────────────────────────────────────
@@ -501,53 +1879,173 @@ impl Viewport {
 
     // ── Private: Vulkan load path (8-bit and 16-bit) ──────────────────────────
 
-    fn load_image_vulkan<F>(&self, path: PathBuf, on_dims: F)
-    where
+    fn load_image_vulkan<F>(
+        &self,
+        source: PathBuf,
+        page: usize,
+        transform: ImageTransform,
+        on_dims: F,
+    ) where
         F: FnOnce(u32, u32) + 'static,
     {
+        let path = page_key(&source, page);
+
+        let direction = self.transition_direction.replace(0);
+
         // ── Cache hit: activate and render immediately ────────────────────
         {
             let mut opt = self.renderer.borrow_mut();
             if let Some(ref mut r) = *opt {
+                let animate = r.is_cached(&path) && r.begin_transition(direction);
                 if let Some(dims) = r.activate_cached(&path) {
                     let (w, h) = (dims.0 as u32, dims.1 as u32);
+                    {
+                        let mut cam = self.camera.borrow_mut();
+                        cam.set_transform(transform);
+                        cam.set_image_size(w, h);
+                    }
                     r.dirty = true;
                     r.render(&self.camera.borrow());
                     drop(opt);
                     present_frame(&self.renderer, &self.picture);
+                    if animate {
+                        run_transition(
+                            &self.renderer,
+                            &self.camera,
+                            &self.picture,
+                            &self.transition_generation,
+                        );
+                    }
                     on_dims(w, h);
+                    let stats = self.load_stats.borrow().get(&path).cloned();
+                    if let Some(stats) = stats {
+                        report_load_stats(&self.on_load_stats, &stats);
+                    }
                     return;
                 }
             }
         }
 
-        // ── Cache miss: decode off-thread ─────────────────────────────────
-        let is_raw_file = raw::is_raw(&path);
+        // ── Decoding already, e.g. after A→B→A: show that result ──────────
+        let generation = self.load_generation.load(Ordering::Relaxed);
+        let running = self.pending_loads.borrow().get(&path).cloned();
+        if let Some(pending) = running {
+            pending.adopt(generation, transform, direction, Box::new(on_dims));
+            return;
+        }
 
-        let (tx, rx) = oneshot::channel::<Option<DecodedImage>>();
-        let path_load = path.clone();
+        // ── Cache miss: decode off-thread, placeholder first ──────────────
+        let started = Instant::now();
+        let (tx, mut rx) = mpsc::unbounded::<LoadStep>();
+        let source_load = source.clone();
+        let latest = self.load_generation.clone();
         rayon::spawn(move || {
-            let result = if is_raw_file {
-                decode_raw_image(&path_load)
-            } else {
-                decode_standard_image(&path_load)
-            };
-            let _ = tx.send(result);
+            // Scrubbing queues loads faster than they decode.
+            if latest.load(Ordering::Relaxed) != generation {
+                return;
+            }
+            let _ = tx.unbounded_send(LoadStep::Started);
+            if let Some(preview) = placeholder(&source_load, page) {
+                let _ = tx.unbounded_send(preview);
+            }
+            let full = decode_with_progress(&source_load, page, &tx)
+                .unwrap_or_else(|| decode_with_stats(&source_load, page));
+            let _ = tx.unbounded_send(LoadStep::Full(full));
         });
+        let on_load_error = self.on_load_error.clone();
+        let on_load_stats = self.on_load_stats.clone();
+        let known_stats = self.load_stats.clone();
+        let transition_gen = self.transition_generation.clone();
+
+        let load_progress = self.load_progress.clone();
+        let progress_delay = self.load_progress_delay.get();
 
         let r2 = self.renderer.clone();
         let c2 = self.camera.clone();
         let p2 = self.picture.clone();
-        let tracker = self.current_target.clone();
+        let latest = self.load_generation.clone();
+        let pending_loads = self.pending_loads.clone();
+        let pending = Rc::new(PendingLoad::new(
+            generation,
+            transform,
+            direction,
+            Box::new(on_dims),
+        ));
 
         glib::spawn_future_local(async move {
-            let Some(decoded) = rx.await.ok().flatten() else {
-                return;
+            let is_target = || latest.load(Ordering::Relaxed) == pending.generation.get();
+            let finished = || {
+                let mut running = pending_loads.borrow_mut();
+                if running.get(&path).is_some_and(|p| Rc::ptr_eq(p, &pending)) {
+                    running.remove(&path);
+                }
+            };
+            let preview = preview_key(&path);
+            let mut preview_shown = false;
+            let result = loop {
+                match rx.next().await {
+                    Some(LoadStep::Started) => {
+                        pending_loads
+                            .borrow_mut()
+                            .insert(path.clone(), pending.clone());
+                    }
+                    Some(LoadStep::Preview {
+                        thumb,
+                        width,
+                        height,
+                    }) => {
+                        if !is_target() {
+                            continue;
+                        }
+                        {
+                            let mut cam = c2.borrow_mut();
+                            cam.set_transform(pending.transform.get());
+                            cam.set_image_size(width, height);
+                        }
+                        let mut opt = r2.borrow_mut();
+                        if let Some(ref mut r) = *opt {
+                            if r.begin_transition(pending.direction.get()) {
+                                run_transition(&r2, &c2, &p2, &transition_gen);
+                            }
+                            let (tw, th) = thumb.dimensions();
+                            r.upload_and_activate(&preview, thumb.as_raw(), tw, th);
+                            r.dirty = true;
+                            r.render(&c2.borrow());
+                            preview_shown = true;
+                        }
+                        drop(opt);
+                        present_frame(&r2, &p2);
+                    }
+                    Some(LoadStep::Progress(done)) => {
+                        if is_target() && started.elapsed() >= progress_delay {
+                            load_progress.set_fraction(done as f64);
+                            let percent = (done * 100.0).round() as u32;
+                            load_progress.set_text(Some(&format!("Decoding… {percent}%")));
+                            load_progress.set_visible(true);
+                        }
+                    }
+                    Some(LoadStep::Full(result)) => break result,
+                    None => {
+                        finished();
+                        return;
+                    }
+                }
             };
+            finished();
+            if is_target() {
+                load_progress.set_visible(false);
+            }
+
+            let still_target = is_target();
 
-            let still_target = {
-                let t = tracker.borrow();
-                t.as_deref() == Some(path.as_path())
+            let (decoded, mut stats) = match result {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    if still_target {
+                        report_load_error(&on_load_error, &source, &e);
+                    }
+                    return;
+                }
             };
 
             let (w, h) = decoded.dimensions();
@@ -556,6 +2054,9 @@ impl Viewport {
                 // Image is no longer the active target — cache it silently
                 let mut opt = r2.borrow_mut();
                 if let Some(ref mut r) = *opt {
+                    if preview_shown {
+                        r.evict(&preview);
+                    }
                     match &decoded {
                         DecodedImage::Rgba8 {
                             rgba,
@@ -573,74 +2074,100 @@ impl Viewport {
                         } => {
                             r.cache_only_16bit(&path, data, *width, *height);
                         }
+                        DecodedImage::RgbaF32 {
+                            data,
+                            width,
+                            height,
+                            ..
+                        } => {
+                            r.cache_only_float(&path, data, *width, *height);
+                        }
                     }
+                    remember_stats(&known_stats, r, &path, stats);
                 }
                 return;
             }
 
+            // Already set up for the placeholder, maybe panned since.
+            let same_size = {
+                let cam = c2.borrow();
+                (cam.image_width, cam.image_height) == (w as f32, h as f32)
+            };
+            if !(preview_shown && same_size) {
+                let mut cam = c2.borrow_mut();
+                cam.set_transform(pending.transform.get());
+                cam.set_image_size(w, h);
+            }
+
             // Upload, activate, render, present
+            let mut animate = false;
             {
                 let mut opt = r2.borrow_mut();
                 if let Some(ref mut r) = *opt {
-                    match &decoded {
-                        DecodedImage::Rgba8 {
-                            rgba,
-                            width,
-                            height,
-                            ..
-                        } => {
-                            r.upload_and_activate(&path, rgba, *width, *height);
-                        }
-                        DecodedImage::Rgba16 {
-                            data,
-                            width,
-                            height,
-                            ..
-                        } => {
-                            r.upload_and_activate_16bit(&path, data, *width, *height);
-                        }
+                    // A transition already began with the placeholder.
+                    animate = !preview_shown && r.begin_transition(pending.direction.get());
+                    decoded.upload_and_activate(r, &path);
+                    if preview_shown {
+                        r.evict(&preview);
                     }
                     r.dirty = true;
                     r.render(&c2.borrow());
+                    remember_stats(&known_stats, r, &path, stats.clone());
                 }
             }
 
             present_frame(&r2, &p2);
-            on_dims(w, h);
+            if animate {
+                run_transition(&r2, &c2, &p2, &transition_gen);
+            }
+            if let Some(on_dims) = pending.on_dims.take() {
+                on_dims(w, h);
+            }
+            stats.load_time = Some(started.elapsed());
+            report_load_stats(&on_load_stats, &stats);
         });
     }
 
     // ── Private: animated image path ──────────────────────────────────────────
 
-    fn load_animated_image<F>(&self, path: PathBuf, on_dims: F)
+    fn load_animated_image<F>(&self, path: PathBuf, transform: ImageTransform, on_dims: F)
     where
         F: FnOnce(u32, u32) + 'static,
     {
-        let (tx, rx) = oneshot::channel::<Option<AnimDecodeResult>>();
+        let started = Instant::now();
+        let (tx, rx) = oneshot::channel::<Option<(AnimDecodeResult, LoadStats)>>();
         let path_load = path.clone();
+        let generation = self.load_generation.load(Ordering::Relaxed);
+        let latest = self.load_generation.clone();
+        let latest_load = latest.clone();
         rayon::spawn(move || {
-            let _ = tx.send(decode_animated(&path_load));
+            if latest_load.load(Ordering::Relaxed) != generation {
+                return;
+            }
+            let _ = tx.send(decode_animated(&path_load).map(|result| {
+                let stats = result.stats();
+                (result, stats)
+            }));
         });
 
         let r2 = self.renderer.clone();
         let c2 = self.camera.clone();
         let p2 = self.picture.clone();
-        let tracker = self.current_target.clone();
         let animation = self.animation.clone();
         let anim_gen = self.anim_generation.clone();
+        let on_load_error = self.on_load_error.clone();
+        let on_load_stats = self.on_load_stats.clone();
 
         glib::spawn_future_local(async move {
-            let Some(result) = rx.await.ok().flatten() else {
-                return;
-            };
+            let Ok(result) = rx.await else { return };
 
-            let still_target = {
-                let t = tracker.borrow();
-                t.as_deref() == Some(path.as_path())
-            };
-            if !still_target {
+            if latest.load(Ordering::Relaxed) != generation {
                 return;
             }
+            let Some((result, mut stats)) = result else {
+                report_load_error(&on_load_error, &path, "Could not decode this GIF");
+                return;
+            };
 
             match result {
                 AnimDecodeResult::Single {
@@ -648,6 +2175,11 @@ impl Viewport {
                     width,
                     height,
                 } => {
+                    {
+                        let mut cam = c2.borrow_mut();
+                        cam.set_transform(transform);
+                        cam.set_image_size(width, height);
+                    }
                     {
                         let mut opt = r2.borrow_mut();
                         if let Some(ref mut r) = *opt {
@@ -658,6 +2190,8 @@ impl Viewport {
                     }
                     present_frame(&r2, &p2);
                     on_dims(width, height);
+                    stats.load_time = Some(started.elapsed());
+                    report_load_stats(&on_load_stats, &stats);
                 }
                 AnimDecodeResult::Animated { frames } => {
                     if frames.is_empty() {
@@ -665,6 +2199,11 @@ impl Viewport {
                     }
 
                     let (w, h) = (frames[0].width, frames[0].height);
+                    {
+                        let mut cam = c2.borrow_mut();
+                        cam.set_transform(transform);
+                        cam.set_image_size(w, h);
+                    }
                     let mut frame_keys = Vec::with_capacity(frames.len());
                     let mut delays = Vec::with_capacity(frames.len());
 
@@ -686,6 +2225,8 @@ impl Viewport {
 
                     present_frame(&r2, &p2);
                     on_dims(w, h);
+                    stats.load_time = Some(started.elapsed());
+                    report_load_stats(&on_load_stats, &stats);
 
                     let anim_id = anim_gen.get().wrapping_add(1);
                     anim_gen.set(anim_id);
@@ -704,38 +2245,40 @@ impl Viewport {
 
     // ── Private: software fallback path ───────────────────────────────────────
 
-    fn load_image_software<F>(&self, path: PathBuf, on_dims: F)
+    fn load_image_software<F>(&self, source: PathBuf, page: usize, on_dims: F)
     where
         F: FnOnce(u32, u32) + 'static,
     {
-        let is_raw_file = raw::is_raw(&path);
-
-        let (tx, rx) = oneshot::channel::<Option<DecodedImage>>();
-        let path_load = path.clone();
+        let started = Instant::now();
+        let (tx, rx) = oneshot::channel::<Result<(DecodedImage, LoadStats), String>>();
+        let source_load = source.clone();
+        let generation = self.load_generation.load(Ordering::Relaxed);
+        let latest = self.load_generation.clone();
+        let latest_load = latest.clone();
         rayon::spawn(move || {
-            let result = if is_raw_file {
-                decode_raw_image(&path_load)
-            } else {
-                decode_standard_image(&path_load)
-            };
-            let _ = tx.send(result);
+            if latest_load.load(Ordering::Relaxed) != generation {
+                return;
+            }
+            let _ = tx.send(decode_with_stats(&source_load, page));
         });
+        let on_load_error = self.on_load_error.clone();
+        let on_load_stats = self.on_load_stats.clone();
 
         let p2 = self.picture.clone();
-        let tracker = self.current_target.clone();
 
         glib::spawn_future_local(async move {
-            let Some(decoded) = rx.await.ok().flatten() else {
-                return;
-            };
+            let Ok(result) = rx.await else { return };
 
-            let still_target = {
-                let t = tracker.borrow();
-                t.as_deref() == Some(path.as_path())
-            };
-            if !still_target {
+            if latest.load(Ordering::Relaxed) != generation {
                 return;
             }
+            let (decoded, mut stats) = match result {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    report_load_error(&on_load_error, &source, &e);
+                    return;
+                }
+            };
 
             let (w, h) = decoded.dimensions();
 
@@ -747,6 +2290,7 @@ impl Viewport {
                     height,
                     ..
                 } => raw::linear_16_to_srgb_8(&data, width, height),
+                DecodedImage::RgbaF32 { data, .. } => crate::color::linear_f32_to_srgb_8(&data),
             };
 
             let stride = (w * 4) as usize;
@@ -760,6 +2304,8 @@ impl Viewport {
             );
             p2.set_paintable(Some(&texture));
             on_dims(w, h);
+            stats.load_time = Some(started.elapsed());
+            report_load_stats(&on_load_stats, &stats);
         });
     }
 }
@@ -899,9 +2445,57 @@ fn schedule_animation_frame(
     });
 }
 
+// ── Image transitions ─────────────────────────────────────────────────────────
+
+/// Drive the transition `begin_transition` set up, one frame per tick,
+/// until it completes or another one starts.
+fn run_transition(
+    renderer: &Rc<RefCell<Option<VkRenderer>>>,
+    camera: &Rc<RefCell<Camera>>,
+    picture: &Picture,
+    generation: &Rc<Cell<u64>>,
+) {
+    let id = generation.get().wrapping_add(1);
+    generation.set(id);
+    let started = Instant::now();
+    let (renderer, camera, picture, generation) = (
+        renderer.clone(),
+        camera.clone(),
+        picture.clone(),
+        generation.clone(),
+    );
+    glib::timeout_add_local(Duration::from_millis(16), move || {
+        if generation.get() != id {
+            return glib::ControlFlow::Break;
+        }
+        let progress = started.elapsed().as_secs_f32() / TRANSITION_DURATION.as_secs_f32();
+        let running = {
+            let mut opt = renderer.borrow_mut();
+            let Some(ref mut r) = *opt else {
+                return glib::ControlFlow::Break;
+            };
+            let running = r.set_transition_progress(progress);
+            r.render(&camera.borrow());
+            running
+        };
+        present_frame(&renderer, &picture);
+        if running {
+            glib::ControlFlow::Continue
+        } else {
+            glib::ControlFlow::Break
+        }
+    });
+}
+
 // ── Vulkan initialization ─────────────────────────────────────────────────────
 
 fn try_init_vulkan(on_error: &Rc<dyn Fn(String)>) -> Option<VkRenderer> {
+    // IRIS_RENDERER=software skips Vulkan, e.g. to rule out driver bugs.
+    if std::env::var("IRIS_RENDERER").is_ok_and(|v| v.trim().eq_ignore_ascii_case("software")) {
+        println!("[Iris] IRIS_RENDERER=software: using the software renderer");
+        return None;
+    }
+
     let (vk_format, format_fourcc) = negotiate_dmabuf_format();
 
     let vk_context = match VkContext::new() {
@@ -939,21 +2533,34 @@ fn sync_size(
     if pw == 0 || ph == 0 {
         return;
     }
+    // The render target is sized in device pixels so HiDPI displays get a
+    // full-resolution frame; the camera stays in logical pixels, matching
+    // pointer coordinates, and only uses the scale for actual size.
+    let scale = picture.scale_factor().max(1) as u32;
 
-    let (current_w, current_h) = {
+    let (current_w, current_h, held) = {
         let opt = renderer.borrow();
         let Some(ref r) = *opt else { return };
-        (r.render_target_width(), r.render_target_height())
+        let (w, h) = (r.render_target_width(), r.render_target_height());
+        // The initial 1x1 target is replaced right away.
+        (w, h, r.resize_held && w > 1 && h > 1)
     };
 
-    if pw != current_w || ph != current_h {
-        {
-            let mut opt = renderer.borrow_mut();
-            if let Some(ref mut r) = *opt {
-                r.resize(pw, ph);
-            }
+    if (pw * scale != current_w || ph * scale != current_h) && !held {
+        let mut opt = renderer.borrow_mut();
+        if let Some(ref mut r) = *opt {
+            r.set_scale_factor(scale);
+            r.resize(pw * scale, ph * scale);
         }
-        camera.borrow_mut().set_viewport_size(pw, ph);
+    }
+    // The camera follows the widget even while the targets are held, so
+    // the stretched frame keeps the image's proportions.
+    let mut cam = camera.borrow_mut();
+    if cam.viewport_width != pw || cam.viewport_height != ph {
+        cam.set_viewport_size(pw, ph);
+    }
+    if cam.scale_factor != scale as f32 {
+        cam.set_scale_factor(scale as f32);
     }
 }
 
@@ -967,11 +2574,9 @@ fn trigger_render(
     let did_render = {
         let mut opt = renderer.borrow_mut();
         let Some(ref mut r) = *opt else { return };
-        r.dirty = true;
-        r.render(&camera.borrow());
-        // render() sets dirty=false if it actually rendered.
-        // If it returned early (no active image, blank, etc.) dirty stays true.
-        !r.dirty
+        // Skips the GPU entirely when neither the camera nor the renderer
+        // state changed, e.g. zooming against a clamp or a no-op drag.
+        r.render(&camera.borrow())
     };
 
     if did_render {
@@ -979,6 +2584,133 @@ fn trigger_render(
     }
 }
 
+/// Whether widget column `x` is within grabbing distance of the compare
+/// divider.
+fn near_divider(
+    renderer: &Rc<RefCell<Option<VkRenderer>>>,
+    camera: &Rc<RefCell<Camera>>,
+    x: f64,
+) -> bool {
+    let Some(split) = renderer.borrow().as_ref().and_then(|r| r.compare_split()) else {
+        return false;
+    };
+    let (vw, _) = viewport_dims(camera);
+    (x - (split * vw) as f64).abs() <= DIVIDER_GRAB_PX
+}
+
+fn viewport_dims(camera: &Rc<RefCell<Camera>>) -> (f32, f32) {
+    let cam = camera.borrow();
+    (cam.viewport_width as f32, cam.viewport_height as f32)
+}
+
+fn trigger_strip_render(
+    renderer: &Rc<RefCell<Option<VkRenderer>>>,
+    camera: &Rc<RefCell<Camera>>,
+    picture: &Picture,
+    strip: &Rc<RefCell<Option<StripState>>>,
+) {
+    sync_size(renderer, camera, picture);
+    let (vw, vh) = viewport_dims(camera);
+
+    // Draw what intersects the viewport; decode a screen's worth beyond it
+    // in both directions so scrolling doesn't reveal gaps.
+    let (on_screen, nearby) = {
+        let mut opt = strip.borrow_mut();
+        let Some(ref mut st) = *opt else { return };
+        st.layout.clamp(vw, vh);
+        (
+            st.layout.visible(vw, vh, 0.0),
+            st.layout.visible(vw, vh, vh),
+        )
+    };
+
+    let missing: Vec<_> = {
+        let opt = renderer.borrow();
+        let Some(ref r) = *opt else { return };
+        nearby
+            .into_iter()
+            .filter(|item| !r.is_cached(&item.path))
+            .collect()
+    };
+    for item in missing {
+        request_strip_image(renderer, camera, picture, strip, item.index, item.path);
+    }
+
+    let draws: Vec<_> = on_screen
+        .into_iter()
+        .map(|item| (item.path, item.rect))
+        .collect();
+    {
+        let mut opt = renderer.borrow_mut();
+        let Some(ref mut r) = *opt else { return };
+        r.render_strip(&camera.borrow(), &draws);
+    }
+    present_frame(renderer, picture);
+}
+
+fn request_strip_image(
+    renderer: &Rc<RefCell<Option<VkRenderer>>>,
+    camera: &Rc<RefCell<Camera>>,
+    picture: &Picture,
+    strip: &Rc<RefCell<Option<StripState>>>,
+    index: usize,
+    path: PathBuf,
+) {
+    {
+        let mut opt = strip.borrow_mut();
+        let Some(ref mut st) = *opt else { return };
+        if !st.pending.insert(path.clone()) {
+            return;
+        }
+    }
+
+    let is_raw_file = raw::is_raw(&path);
+    let (tx, rx) = oneshot::channel::<Option<DecodedImage>>();
+    let path_load = path.clone();
+    rayon::spawn(move || {
+        let result = if is_raw_file {
+            decode_raw_image(&path_load)
+        } else {
+            decode_standard_image(&path_load).ok()
+        };
+        let _ = tx.send(result);
+    });
+
+    let r2 = renderer.clone();
+    let c2 = camera.clone();
+    let p2 = picture.clone();
+    let s2 = strip.clone();
+    glib::spawn_future_local(async move {
+        // Failed decodes stay pending so they aren't retried on every scroll.
+        let Some(decoded) = rx.await.ok().flatten() else {
+            return;
+        };
+
+        {
+            let (vw, _) = viewport_dims(&c2);
+            let mut opt = s2.borrow_mut();
+            let Some(ref mut st) = *opt else { return };
+            st.pending.remove(&path);
+            if st.layout.paths.get(index) == Some(&path) {
+                let (w, h) = decoded.dimensions();
+                st.layout.set_aspect(index, w, h, vw);
+            }
+        }
+
+        {
+            let mut opt = r2.borrow_mut();
+            let Some(ref mut r) = *opt else { return };
+            cache_decoded(r, &path, &decoded);
+        }
+        trigger_strip_render(&r2, &c2, &p2, &s2);
+    });
+}
+
+/// Hand the last rendered frame to GTK. Frames are only produced on demand
+/// (input, loads, resizes, animation ticks) and normally shared zero-copy as
+/// a DMA-BUF; the CPU readback below is a fallback for drivers that reject
+/// the import. One rejection switches the renderer to readback for good, so
+/// the export isn't retried per frame.
 fn present_frame(renderer: &Rc<RefCell<Option<VkRenderer>>>, picture: &Picture) {
     let (fd, stride, fourcc, w, h) = {
         let opt = renderer.borrow();
@@ -1004,7 +2736,14 @@ fn present_frame(renderer: &Rc<RefCell<Option<VkRenderer>>>, picture: &Picture)
     };
 
     let dmabuf_ok = if let Some(fd) = fd {
-        try_push_dmabuf(picture, w, h, fourcc, fd, stride)
+        let ok = try_push_dmabuf(picture, w, h, fourcc, fd, stride);
+        if !ok {
+            eprintln!("[Iris] Falling back to CPU readback for presentation");
+            if let Some(ref mut r) = *renderer.borrow_mut() {
+                r.reject_dmabuf();
+            }
+        }
+        ok
     } else {
         false
     };
@@ -1016,15 +2755,55 @@ fn present_frame(renderer: &Rc<RefCell<Option<VkRenderer>>>, picture: &Picture)
     }
 
     if !dmabuf_ok {
-        let pixels = {
-            let opt = renderer.borrow();
-            opt.as_ref().and_then(|r| r.read_pixels())
+        push_readback(renderer, picture, w, h, stride as usize, fourcc);
+    }
+}
+
+/// Copy the presented frame to the CPU and show it, without blocking the
+/// GTK thread on the GPU: if the copy is still in flight, poll its fence
+/// from a timeout and push the texture once it's signaled. A newer frame
+/// supersedes a pending one, which is then dropped.
+fn push_readback(
+    renderer: &Rc<RefCell<Option<VkRenderer>>>,
+    picture: &Picture,
+    w: u32,
+    h: u32,
+    stride: usize,
+    fourcc: u32,
+) {
+    let frame = {
+        let opt = renderer.borrow();
+        let Some(ref r) = *opt else { return };
+        if r.readback_ready() {
+            if let Some(pixels) = r.read_pixels() {
+                push_memory_texture(picture, w, h, stride, fourcc, &pixels);
+            }
+            return;
+        }
+        r.frame_count()
+    };
+
+    let (renderer, picture) = (renderer.clone(), picture.clone());
+    glib::timeout_add_local(READBACK_POLL, move || {
+        let opt = renderer.borrow();
+        let Some(ref r) = *opt else {
+            return glib::ControlFlow::Break;
         };
-        let stride_bytes = stride as usize;
-        if let Some(pixels) = pixels {
-            push_memory_texture(picture, w, h, stride_bytes, fourcc, &pixels);
+        // Resizes reallocate the targets and restart the count, so the
+        // size has to match too.
+        let superseded = r.frame_count() != frame
+            || (r.render_target_width(), r.render_target_height()) != (w, h);
+        if superseded {
+            return glib::ControlFlow::Break;
         }
-    }
+        if !r.readback_ready() {
+            return glib::ControlFlow::Continue;
+        }
+        if let Some(pixels) = r.read_pixels() {
+            push_memory_texture(&picture, w, h, stride, fourcc, &pixels);
+        }
+        glib::ControlFlow::Break
+    });
 }
 
 fn try_push_dmabuf(
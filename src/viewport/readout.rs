@@ -0,0 +1,212 @@
+//! Pixel value readout: the source pixel under the pointer, with its 8-bit
+//! RGBA value, shown over the image while enabled.
+
+use futures::channel::oneshot;
+use glam::Vec2;
+use gtk4::glib;
+use gtk4::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use super::camera::Camera;
+use super::{DecodedImage, decode_image};
+
+/// One source pixel, in display (sRGB, 8-bit) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Pixel {
+    pub x: u32,
+    pub y: u32,
+    pub rgba: [u8; 4],
+}
+
+impl fmt::Display for Pixel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b, a] = self.rgba;
+        write!(
+            f,
+            "{}, {}   R {r:3} G {g:3} B {b:3} A {a:3}   #{r:02X}{g:02X}{b:02X}{a:02X}",
+            self.x, self.y
+        )
+    }
+}
+
+/// The pixel of `decoded` containing the image-space point `at`, or `None`
+/// off the image. Linear 16-bit and float pixels are tone mapped and
+/// encoded to sRGB, as the shader displays them.
+pub(super) fn sample(decoded: &DecodedImage, at: Vec2) -> Option<Pixel> {
+    let (width, height) = decoded.dimensions();
+    if at.x < 0.0 || at.y < 0.0 || at.x >= width as f32 || at.y >= height as f32 {
+        return None;
+    }
+    let (x, y) = (at.x as u32, at.y as u32);
+    let i = (y as usize * width as usize + x as usize) * 4;
+    let rgba = match decoded {
+        DecodedImage::Rgba8 { rgba, .. } => rgba.get(i..i + 4)?.try_into().ok()?,
+        DecodedImage::Rgba16 { data, .. } => {
+            let px = data.get(i..i + 4)?;
+            let encode = |v: u16| {
+                let mapped = crate::color::aces_tonemap(v as f32 / 65535.0);
+                (crate::color::linear_to_srgb(mapped) * 255.0).round() as u8
+            };
+            let alpha = (px[3] as f32 / 65535.0 * 255.0).round() as u8;
+            [encode(px[0]), encode(px[1]), encode(px[2]), alpha]
+        }
+        DecodedImage::RgbaF32 { data, .. } => {
+            crate::color::linear_f32_to_srgb_8(data.get(i..i + 4)?)
+                .try_into()
+                .ok()?
+        }
+    };
+    Some(Pixel { x, y, rgba })
+}
+
+/// Overlay label plus the decoded pixels of the image on screen. Textures
+/// live on the GPU, so the pixels are decoded again, and only kept while
+/// the readout is on.
+pub(super) struct PixelReadout {
+    pub label: gtk4::Label,
+    enabled: Cell<bool>,
+    /// Image on screen as `(source, page)`.
+    shown: RefCell<Option<(PathBuf, usize)>>,
+    pixels: RefCell<Option<DecodedImage>>,
+}
+
+impl PixelReadout {
+    pub fn new() -> Rc<Self> {
+        let label = gtk4::Label::builder()
+            .halign(gtk4::Align::Start)
+            .valign(gtk4::Align::End)
+            .can_target(false)
+            .visible(false)
+            .css_classes(["pixel-readout"])
+            .build();
+        Rc::new(Self {
+            label,
+            enabled: Cell::new(false),
+            shown: RefCell::new(None),
+            pixels: RefCell::new(None),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    pub fn set_enabled(self: &Rc<Self>, enabled: bool) {
+        self.enabled.set(enabled);
+        if enabled {
+            self.load();
+        } else {
+            self.pixels.replace(None);
+            self.hide();
+        }
+    }
+
+    /// Track the image now on screen.
+    pub fn set_image(self: &Rc<Self>, path: &Path, page: usize) {
+        self.shown.replace(Some((path.to_path_buf(), page)));
+        self.pixels.replace(None);
+        self.hide();
+        if self.enabled.get() {
+            self.load();
+        }
+    }
+
+    /// Show the pixel under widget point `(x, y)`, or hide the label when
+    /// that is off the image or the pixels aren't decoded yet.
+    pub fn update(&self, camera: &Camera, x: f64, y: f64) {
+        if !self.enabled.get() {
+            return;
+        }
+        let vw = camera.viewport_width as f32;
+        let vh = camera.viewport_height as f32;
+        let ndc = Vec2::new(x as f32 / vw * 2.0 - 1.0, 1.0 - y as f32 / vh * 2.0);
+        let pixel = self.pixels.borrow().as_ref().and_then(|decoded| {
+            let at = camera.ndc_to_image(ndc)?;
+            sample(decoded, at)
+        });
+        match pixel {
+            Some(pixel) => {
+                self.label.set_label(&pixel.to_string());
+                self.label.set_visible(true);
+            }
+            None => self.hide(),
+        }
+    }
+
+    pub fn hide(&self) {
+        self.label.set_visible(false);
+    }
+
+    fn load(self: &Rc<Self>) {
+        if self.pixels.borrow().is_some() {
+            return;
+        }
+        let Some((path, page)) = self.shown.borrow().clone() else {
+            return;
+        };
+        let (tx, rx) = oneshot::channel();
+        let path_load = path.clone();
+        rayon::spawn(move || {
+            let _ = tx.send(decode_image(&path_load, page).ok());
+        });
+        let this = Rc::clone(self);
+        glib::spawn_future_local(async move {
+            let Ok(Some(decoded)) = rx.await else { return };
+            let still_shown = this.shown.borrow().as_ref() == Some(&(path, page));
+            if still_shown && this.enabled.get() {
+                this.pixels.replace(Some(decoded));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::ColorInfo;
+
+    #[test]
+    fn samples_the_pixel_containing_a_point() {
+        let decoded = DecodedImage::Rgba8 {
+            rgba: vec![
+                1, 2, 3, 255, 4, 5, 6, 255, //
+                7, 8, 9, 255, 10, 11, 12, 128,
+            ],
+            width: 2,
+            height: 2,
+            color: ColorInfo::default(),
+        };
+        let pixel = sample(&decoded, Vec2::new(1.9, 1.2)).unwrap();
+        assert_eq!(
+            pixel,
+            Pixel {
+                x: 1,
+                y: 1,
+                rgba: [10, 11, 12, 128],
+            }
+        );
+        assert_eq!(
+            pixel.to_string(),
+            "1, 1   R  10 G  11 B  12 A 128   #0A0B0C80"
+        );
+        assert!(sample(&decoded, Vec2::new(2.0, 0.0)).is_none());
+        assert!(sample(&decoded, Vec2::new(0.5, -0.1)).is_none());
+    }
+
+    #[test]
+    fn linear_pixels_read_as_displayed() {
+        let decoded = DecodedImage::Rgba16 {
+            data: vec![0, 65535, 65535 / 5, 65535],
+            width: 1,
+            height: 1,
+            color: ColorInfo::default(),
+        };
+        let pixel = sample(&decoded, Vec2::new(0.5, 0.5)).unwrap();
+        // Tone mapped like the shader: linear 1.0 stays below white and
+        // 0.2 maps to about 0.3 before encoding.
+        assert_eq!(pixel.rgba, [0, 232, 149, 255]);
+    }
+}
@@ -5,8 +5,51 @@ struct Uniforms {
     pan: vec2<f32>,
     tone_map_enabled: f32,
     hdr_output_enabled: f32,
+    // Screen-space mirroring after rotation: -1.0 flips an axis, 1.0 keeps it.
+    flip: vec2<f32>,
+    // Checker cell size in framebuffer pixels behind transparent areas;
+    // 0.0 fills them with `background` instead.
+    checker_size: f32,
+    // Tone adjustments in -1.0..1.0; 0.0 leaves the image unchanged.
+    brightness: f32,
+    background: vec4<f32>,
+    contrast: f32,
+    grayscale: f32,
+    invert: f32,
+    compare_tone_map: f32,
+    // Per-draw placement for multi-image layouts, selected by instance index
+    // (instance 0 uses the camera transform above). Each rect is
+    // (center_x, center_y, half_width, half_height) in NDC.
+    quads: array<vec4<f32>, 16>,
+    // Fit scale of the compare image, drawn through the camera as
+    // COMPARE_INSTANCE into its own half of the framebuffer.
+    compare_scale: vec2<f32>,
+    // Navigating between images: the outgoing image, drawn first as
+    // TRANSITION_INSTANCE with its own fit scale and tone mapping, then
+    // the active one over it at `fade` opacity. The offsets (NDC) slide
+    // each sideways.
+    transition_tone_map: f32,
+    fade: f32,
+    transition_scale: vec2<f32>,
+    transition_offset: vec2<f32>,
+    active_offset: vec2<f32>,
+    // Straighten angle (radians, clockwise) and the viewport's width over
+    // height, so the turn happens in square pixels rather than NDC.
+    straighten: f32,
+    aspect: f32,
+    // Exposure in stops, scaling linear light, and the gamma the encoded
+    // values are raised to the reciprocal of; 0 and 1 leave them alone.
+    exposure: f32,
+    gamma: f32,
+    // 1.0 adds ordered dither before the 8-bit target quantizes, breaking
+    // up banding in smooth gradients.
+    dither: f32,
+    _pad: f32,
 }
 
+const COMPARE_INSTANCE: u32 = 17u;
+const TRANSITION_INSTANCE: u32 = 18u;
+
 @group(0) @binding(0) var<uniform> u: Uniforms;
 @group(0) @binding(1) var t_diffuse: texture_2d<f32>;
 @group(0) @binding(2) var s_diffuse: sampler;
@@ -14,6 +57,8 @@ struct Uniforms {
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) tone_map: f32,
+    @location(2) @interpolate(flat) opacity: f32,
 }
 
 fn rotate2d(angle: f32) -> mat2x2<f32> {
@@ -34,8 +79,38 @@ fn aces_tonemap(x: vec3<f32>) -> vec3<f32> {
     return clamp((x * (a * x + b)) / (x * (c * x + d) + e), vec3<f32>(0.0), vec3<f32>(1.0));
 }
 
+// sRGB transfer function: linear light to the encoded values an 8-bit
+// texture and the presented frame hold.
+fn linear_to_srgb(x: vec3<f32>) -> vec3<f32> {
+    let c = clamp(x, vec3<f32>(0.0), vec3<f32>(1.0));
+    let lo = c * 12.92;
+    let hi = 1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(hi, lo, c <= vec3<f32>(0.0031308));
+}
+
+// Inverse of `linear_to_srgb`.
+fn srgb_to_linear(x: vec3<f32>) -> vec3<f32> {
+    let lo = x / 12.92;
+    let hi = pow((x + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(hi, lo, x <= vec3<f32>(0.04045));
+}
+
+fn camera_transform(corner: vec2<f32>, scale: vec2<f32>) -> vec2<f32> {
+    var p = corner * scale;
+    p = rotate2d(u.rotation) * p;
+    p = p * u.flip;
+    var q = vec2<f32>(p.x * u.aspect, p.y);
+    q = rotate2d(u.straighten) * q;
+    p = vec2<f32>(q.x / u.aspect, q.y);
+    p = p * u.zoom;
+    return p + u.pan;
+}
+
 @vertex
-fn vs_main(@builtin(vertex_index) idx: u32) -> VertexOutput {
+fn vs_main(
+    @builtin(vertex_index) idx: u32,
+    @builtin(instance_index) instance: u32,
+) -> VertexOutput {
     var pos = array<vec2<f32>, 6>(
         vec2<f32>(-1.0,  1.0),
         vec2<f32>(-1.0, -1.0),
@@ -56,32 +131,99 @@ fn vs_main(@builtin(vertex_index) idx: u32) -> VertexOutput {
 
     var out: VertexOutput;
     var p = pos[idx];
+    out.tone_map = u.tone_map_enabled;
+    out.opacity = 1.0;
 
-    p = p * u.scale;
-    p = rotate2d(u.rotation) * p;
-    p = p * u.zoom;
-    p = p + u.pan;
+    if (instance == TRANSITION_INSTANCE) {
+        p = camera_transform(p, u.transition_scale) + u.transition_offset;
+        out.tone_map = u.transition_tone_map;
+    } else if (instance == COMPARE_INSTANCE) {
+        p = camera_transform(p, u.compare_scale);
+        out.tone_map = u.compare_tone_map;
+    } else if (instance > 0u) {
+        let quad = u.quads[instance - 1u];
+        p = p * quad.zw + quad.xy;
+    } else {
+        p = camera_transform(p, u.scale) + u.active_offset;
+        out.opacity = u.fade;
+    }
 
     out.position = vec4<f32>(p, 0.0, 1.0);
     out.uv = uvs[idx];
     return out;
 }
 
+fn backdrop(frag_pos: vec2<f32>) -> vec3<f32> {
+    if (u.checker_size <= 0.0) {
+        return u.background.rgb;
+    }
+    // Anchored to the framebuffer, so cells stay the same size at any zoom.
+    let cell = vec2<i32>(floor(frag_pos / u.checker_size));
+    if (((cell.x + cell.y) & 1) == 0) {
+        return vec3<f32>(0.6);
+    }
+    return vec3<f32>(0.4);
+}
+
+// A threshold from a 4x4 Bayer matrix, centered on zero and one 8-bit step
+// wide, so rounding to the target spreads error in a fixed fine pattern.
+fn ordered_dither(frag_pos: vec2<f32>) -> f32 {
+    var bayer = array<f32, 16>(
+        0.0, 8.0, 2.0, 10.0,
+        12.0, 4.0, 14.0, 6.0,
+        3.0, 11.0, 1.0, 9.0,
+        15.0, 7.0, 13.0, 5.0,
+    );
+    let p = vec2<u32>(frag_pos) % vec2<u32>(4u);
+    return ((bayer[p.y * 4u + p.x] + 0.5) / 16.0 - 0.5) / 255.0;
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     var color = textureSample(t_diffuse, s_diffuse, in.uv);
 
-    if (u.tone_map_enabled > 0.5) {
-        color = vec4<f32>(aces_tonemap(color.rgb), color.a);
+    // The working space is sRGB-encoded, matching 8-bit textures and the
+    // UNORM render target GTK reads as sRGB. Linear 16-bit (RAW) textures
+    // are tone mapped and then encoded to join it.
+    // Exposure scales linear light, so linear textures take it before tone
+    // mapping and encoded ones are decoded for it.
+    let gain = exp2(u.exposure);
+    if (in.tone_map > 0.5) {
+        color = vec4<f32>(linear_to_srgb(aces_tonemap(color.rgb * gain)), color.a);
+    } else if (u.exposure != 0.0) {
+        color = vec4<f32>(linear_to_srgb(srgb_to_linear(color.rgb) * gain), color.a);
     }
+    if (u.gamma != 1.0) {
+        color = vec4<f32>(pow(color.rgb, vec3<f32>(1.0 / u.gamma)), color.a);
+    }
+
+    // Contrast pivots around mid-gray; brightness shifts by up to half
+    // the range.
+    var adjusted = (color.rgb - 0.5) * (1.0 + u.contrast) + 0.5 + u.brightness * 0.5;
+    if (u.grayscale > 0.5) {
+        // Rec. 709 luminance.
+        adjusted = vec3<f32>(dot(adjusted, vec3<f32>(0.2126, 0.7152, 0.0722)));
+    }
+    if (u.invert > 0.5) {
+        adjusted = vec3<f32>(1.0) - clamp(adjusted, vec3<f32>(0.0), vec3<f32>(1.0));
+    }
+    color = vec4<f32>(adjusted, color.a);
+
+    color = vec4<f32>(mix(backdrop(in.position.xy), color.rgb, color.a), 1.0);
 
     // HDR output path placeholder:
     // once the export surface is negotiated to HDR-capable formats and
     // GdkColorState/HDR compositing is wired in, this branch can emit
     // scene-referred values instead of SDR-clamped output.
+    // Premultiplied, for blending over a transition's outgoing image.
     if (u.hdr_output_enabled > 0.5) {
-        return color;
+        return vec4<f32>(color.rgb * in.opacity, in.opacity);
     }
 
-    return vec4<f32>(clamp(color.rgb, vec3<f32>(0.0), vec3<f32>(1.0)), color.a);
+    var rgb = color.rgb;
+    if (u.dither > 0.5) {
+        rgb = rgb + vec3<f32>(ordered_dither(in.position.xy));
+    }
+    let clamped = clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0));
+    return vec4<f32>(clamped * in.opacity, in.opacity);
 }
@@ -0,0 +1,176 @@
+//! What the status bar reports about the image on screen: its source pixel
+//! format, colour space, and how long it took to show.
+
+use image::{ColorType, ImageDecoder};
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use super::DecodedImage;
+use crate::color::DynamicRange;
+use crate::raw;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadStats {
+    /// Source pixel layout, e.g. "8-bit RGB" or "16-bit linear RAW".
+    pub format: String,
+    pub has_alpha: bool,
+    pub color_space: String,
+    /// Scene dynamic range of float (HDR) images, in stops.
+    pub dynamic_range_stops: Option<f32>,
+    /// Decode plus upload; `None` when shown from the texture cache.
+    pub load_time: Option<Duration>,
+}
+
+impl LoadStats {
+    /// Describe page `page` of `path`, decoded into `decoded`.
+    pub(super) fn new(path: &Path, page: usize, decoded: &DecodedImage) -> Self {
+        let (format, has_alpha, color) = match decoded {
+            DecodedImage::Rgba16 { color, .. } => ("16-bit linear RAW".to_string(), false, color),
+            DecodedImage::Rgba8 { color, .. } if raw::is_raw(path) => {
+                ("8-bit RGB (RAW preview)".to_string(), false, color)
+            }
+            DecodedImage::RgbaF32 { data, color, .. } => {
+                let probed = (page == 0).then(|| source_color_type(path)).flatten();
+                match probed {
+                    Some(ct) => (describe_color_type(ct), ct.has_alpha(), color),
+                    None => (
+                        "32-bit float RGBA".to_string(),
+                        data.chunks_exact(4).any(|px| px[3] < 1.0),
+                        color,
+                    ),
+                }
+            }
+            DecodedImage::Rgba8 { rgba, color, .. } => {
+                let probed = (page == 0).then(|| source_color_type(path)).flatten();
+                match probed {
+                    Some(ct) => (describe_color_type(ct), ct.has_alpha(), color),
+                    // Pixels were expanded to RGBA on decode; look for any
+                    // that aren't opaque.
+                    None => (
+                        "8-bit RGBA".to_string(),
+                        rgba.chunks_exact(4).any(|px| px[3] < 255),
+                        color,
+                    ),
+                }
+            }
+        };
+        let mut color_space = match (&color.icc_profile, decoded) {
+            (_, DecodedImage::Rgba16 { .. }) => "Linear (camera)".to_string(),
+            (_, DecodedImage::RgbaF32 { .. }) => "Linear (scene)".to_string(),
+            (Some(icc), _) => crate::color::profile_description(icc)
+                .unwrap_or_else(|| "Embedded (unnamed)".into()),
+            (None, _) => "sRGB (untagged)".to_string(),
+        };
+        if color.dynamic_range == DynamicRange::Hdr {
+            color_space.push_str(", HDR");
+        }
+        let dynamic_range_stops = match decoded {
+            DecodedImage::RgbaF32 { data, .. } => crate::color::dynamic_range_stops(data),
+            _ => None,
+        };
+        Self {
+            format,
+            has_alpha,
+            color_space,
+            dynamic_range_stops,
+            load_time: None,
+        }
+    }
+
+    /// Describe a GIF of `frames` frames, with `has_alpha` if any pixel of
+    /// them is see-through.
+    pub(super) fn animation(frames: usize, has_alpha: bool) -> Self {
+        let format = if frames > 1 {
+            format!("8-bit indexed, {frames} frames")
+        } else {
+            "8-bit indexed".to_string()
+        };
+        Self {
+            format,
+            has_alpha,
+            color_space: "sRGB (untagged)".to_string(),
+            dynamic_range_stops: None,
+            load_time: None,
+        }
+    }
+}
+
+impl fmt::Display for LoadStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alpha = if self.has_alpha { "Alpha" } else { "No alpha" };
+        write!(
+            f,
+            "{}  ·  {alpha}  ·  {}  ·  ",
+            self.format, self.color_space
+        )?;
+        match self.load_time {
+            None => write!(f, "Cached"),
+            Some(t) if t < Duration::from_secs(1) => write!(f, "{} ms", t.as_millis()),
+            Some(t) => write!(f, "{:.2} s", t.as_secs_f64()),
+        }
+    }
+}
+
+/// Pixel layout stored in the file, read from its header alone.
+fn source_color_type(path: &Path) -> Option<ColorType> {
+    let reader = image::ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?;
+    Some(reader.into_decoder().ok()?.color_type())
+}
+
+/// "8-bit RGB", "16-bit Grayscale + alpha", "32-bit float RGBA" and the like.
+fn describe_color_type(ct: ColorType) -> String {
+    let channels = ct.channel_count();
+    let bits = ct.bits_per_pixel() / channels.max(1) as u16;
+    let float = if matches!(ct, ColorType::Rgb32F | ColorType::Rgba32F) {
+        " float"
+    } else {
+        ""
+    };
+    let layout = match channels {
+        1 => "Grayscale",
+        2 => "Grayscale + alpha",
+        3 => "RGB",
+        _ => "RGBA",
+    };
+    format!("{bits}-bit{float} {layout}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_color_types_by_depth_and_layout() {
+        assert_eq!(describe_color_type(ColorType::Rgb8), "8-bit RGB");
+        assert_eq!(describe_color_type(ColorType::L8), "8-bit Grayscale");
+        assert_eq!(
+            describe_color_type(ColorType::La16),
+            "16-bit Grayscale + alpha"
+        );
+        assert_eq!(describe_color_type(ColorType::Rgba32F), "32-bit float RGBA");
+        assert!(ColorType::La8.has_alpha() && !ColorType::L16.has_alpha());
+    }
+
+    #[test]
+    fn summarises_on_one_line() {
+        let mut stats = LoadStats {
+            format: "8-bit RGB".into(),
+            has_alpha: false,
+            color_space: "sRGB (untagged)".into(),
+            dynamic_range_stops: None,
+            load_time: Some(Duration::from_micros(42_700)),
+        };
+        assert_eq!(
+            stats.to_string(),
+            "8-bit RGB  ·  No alpha  ·  sRGB (untagged)  ·  42 ms"
+        );
+        stats.load_time = Some(Duration::from_millis(1250));
+        assert!(stats.to_string().ends_with("1.25 s"));
+        stats.load_time = None;
+        assert!(stats.to_string().ends_with("Cached"));
+    }
+}
@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+
+/// Height/width ratio assumed for an image until its real size is known.
+pub const DEFAULT_ASPECT: f32 = 1.4;
+
+/// Layout for continuous vertical reading (webtoon/comic mode).
+///
+/// Every image is drawn at the full viewport width and stacked top to bottom
+/// in file order. `scroll` is the distance in pixels from the top of the
+/// strip to the top of the viewport.
+pub struct StripLayout {
+    pub paths: Vec<PathBuf>,
+    aspects: Vec<f32>,
+    pub scroll: f32,
+}
+
+/// An image that intersects the (margin-extended) viewport.
+pub struct StripItem {
+    pub index: usize,
+    pub path: PathBuf,
+    /// `[center_x, center_y, half_width, half_height]` in NDC (y up).
+    pub rect: [f32; 4],
+}
+
+impl StripLayout {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let aspects = vec![DEFAULT_ASPECT; paths.len()];
+        Self {
+            paths,
+            aspects,
+            scroll: 0.0,
+        }
+    }
+
+    /// Record the real size of an image. If it sits above the viewport the
+    /// scroll offset is shifted by the height change so the visible content
+    /// doesn't jump.
+    pub fn set_aspect(&mut self, index: usize, width: u32, height: u32, view_width: f32) {
+        if width == 0 || height == 0 || index >= self.aspects.len() {
+            return;
+        }
+        let new_aspect = height as f32 / width as f32;
+        let old_aspect = self.aspects[index];
+        if index_top(&self.aspects, index, view_width) < self.scroll {
+            self.scroll += (new_aspect - old_aspect) * view_width;
+        }
+        self.aspects[index] = new_aspect;
+    }
+
+    pub fn total_height(&self, view_width: f32) -> f32 {
+        self.aspects.iter().map(|a| a * view_width).sum()
+    }
+
+    pub fn clamp(&mut self, view_width: f32, view_height: f32) {
+        let max = (self.total_height(view_width) - view_height).max(0.0);
+        self.scroll = self.scroll.clamp(0.0, max);
+    }
+
+    pub fn scroll_by(&mut self, delta: f32, view_width: f32, view_height: f32) {
+        self.scroll += delta;
+        self.clamp(view_width, view_height);
+    }
+
+    pub fn scroll_to(&mut self, index: usize, view_width: f32, view_height: f32) {
+        self.scroll = index_top(&self.aspects, index.min(self.aspects.len()), view_width);
+        self.clamp(view_width, view_height);
+    }
+
+    /// Index of the image under the vertical center of the viewport.
+    pub fn index_at_center(&self, view_width: f32, view_height: f32) -> usize {
+        let center = self.scroll + view_height / 2.0;
+        let mut top = 0.0;
+        for (i, aspect) in self.aspects.iter().enumerate() {
+            let bottom = top + aspect * view_width;
+            if center < bottom {
+                return i;
+            }
+            top = bottom;
+        }
+        self.aspects.len().saturating_sub(1)
+    }
+
+    /// Images intersecting the viewport grown by `margin` pixels above and
+    /// below, with their placement in NDC.
+    pub fn visible(&self, view_width: f32, view_height: f32, margin: f32) -> Vec<StripItem> {
+        let mut items = Vec::new();
+        if view_width <= 0.0 || view_height <= 0.0 {
+            return items;
+        }
+        let lo = self.scroll - margin;
+        let hi = self.scroll + view_height + margin;
+
+        let mut top = 0.0;
+        for (i, aspect) in self.aspects.iter().enumerate() {
+            let h = aspect * view_width;
+            let bottom = top + h;
+            if bottom > lo && top < hi {
+                let rel_top = top - self.scroll;
+                items.push(StripItem {
+                    index: i,
+                    path: self.paths[i].clone(),
+                    rect: [
+                        0.0,
+                        1.0 - (rel_top + h / 2.0) / view_height * 2.0,
+                        1.0,
+                        h / view_height,
+                    ],
+                });
+            }
+            if top >= hi {
+                break;
+            }
+            top = bottom;
+        }
+        items
+    }
+}
+
+fn index_top(aspects: &[f32], index: usize, view_width: f32) -> f32 {
+    aspects[..index].iter().map(|a| a * view_width).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(n: usize) -> StripLayout {
+        StripLayout::new((0..n).map(|i| PathBuf::from(format!("{i}.png"))).collect())
+    }
+
+    #[test]
+    fn scroll_is_clamped_to_strip_extent() {
+        let mut strip = layout(3);
+        for i in 0..3 {
+            strip.set_aspect(i, 100, 200, 500.0);
+        }
+        // Three 500×1000 images in a 500×800 viewport.
+        strip.scroll_by(10_000.0, 500.0, 800.0);
+        assert_eq!(strip.scroll, 3000.0 - 800.0);
+        strip.scroll_by(-10_000.0, 500.0, 800.0);
+        assert_eq!(strip.scroll, 0.0);
+    }
+
+    #[test]
+    fn visible_returns_intersecting_items_with_top_aligned_rects() {
+        let mut strip = layout(4);
+        for i in 0..4 {
+            strip.set_aspect(i, 100, 100, 400.0);
+        }
+        strip.scroll_to(1, 400.0, 400.0);
+
+        let items = strip.visible(400.0, 400.0, 0.0);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].index, 1);
+        assert_eq!(items[0].rect, [0.0, 0.0, 1.0, 1.0]);
+
+        let items = strip.visible(400.0, 400.0, 1.0);
+        let indices: Vec<usize> = items.iter().map(|i| i.index).collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn aspect_change_above_viewport_keeps_content_anchored() {
+        let mut strip = layout(3);
+        strip.scroll_to(2, 100.0, 100.0);
+        let before = strip.scroll;
+
+        strip.set_aspect(0, 100, 300, 100.0);
+        assert!((strip.scroll - (before + (3.0 - DEFAULT_ASPECT) * 100.0)).abs() < 0.001);
+        assert_eq!(strip.index_at_center(100.0, 100.0), 2);
+    }
+}
@@ -177,7 +177,7 @@ impl ComputeInfra {
         wgsl_source: &str,
     ) -> IrisResult<vk::Pipeline> {
         unsafe {
-            let shader_module = shader::compile_wgsl(&context.device, wgsl_source);
+            let shader_module = shader::compile_wgsl(&context.device, wgsl_source)?;
             let entry_point = std::ffi::CString::new("cs_main").unwrap();
 
             let stage = vk::PipelineShaderStageCreateInfo::default()
@@ -5,6 +5,67 @@ use std::sync::Arc;
 use crate::error::{IrisError, IrisResult};
 use crate::vk_check;
 
+/// Which GPU to render on, from the `IRIS_GPU` environment variable:
+/// `discrete` (the default), `integrated` to save power on laptops, or part
+/// of a device name as listed in the log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GpuPreference {
+    Discrete,
+    Integrated,
+    Named(String),
+}
+
+impl GpuPreference {
+    pub fn from_env() -> Self {
+        std::env::var("IRIS_GPU")
+            .map(|v| Self::parse(&v))
+            .unwrap_or(Self::Discrete)
+    }
+
+    fn parse(value: &str) -> Self {
+        let value = value.trim();
+        match value.to_lowercase().as_str() {
+            "" | "discrete" | "high-performance" => Self::Discrete,
+            "integrated" | "low-power" => Self::Integrated,
+            _ => Self::Named(value.to_string()),
+        }
+    }
+}
+
+/// Index into `devices` (type and name of each) to render on. A named
+/// device that isn't present falls back to the default order rather than
+/// failing: the preferred kind of GPU, then the other kind, then anything.
+fn pick_device(devices: &[(vk::PhysicalDeviceType, String)], pref: &GpuPreference) -> usize {
+    let of_type = |ty| devices.iter().position(|(t, _)| *t == ty);
+    let named = match pref {
+        GpuPreference::Named(wanted) => {
+            let wanted = wanted.to_lowercase();
+            let found = devices
+                .iter()
+                .position(|(_, name)| name.to_lowercase().contains(&wanted));
+            if found.is_none() {
+                eprintln!("[Iris] IRIS_GPU={wanted:?} matches no device; using the default");
+            }
+            found
+        }
+        _ => None,
+    };
+    let (first, second) = match pref {
+        GpuPreference::Integrated => (
+            vk::PhysicalDeviceType::INTEGRATED_GPU,
+            vk::PhysicalDeviceType::DISCRETE_GPU,
+        ),
+        _ => (
+            vk::PhysicalDeviceType::DISCRETE_GPU,
+            vk::PhysicalDeviceType::INTEGRATED_GPU,
+        ),
+    };
+    named
+        .or_else(|| of_type(first))
+        .or_else(|| of_type(second))
+        .unwrap_or(0)
+}
+
 pub struct VkContext {
     pub entry: Entry,
     pub instance: Instance,
@@ -15,9 +76,38 @@ pub struct VkContext {
     pub memory_properties: vk::PhysicalDeviceMemoryProperties,
     pub command_pool: vk::CommandPool,
     pub device_limits: vk::PhysicalDeviceLimits,
+    /// Whether `samplerAnisotropy` was enabled on the device.
+    pub sampler_anisotropy: bool,
+    /// Name the driver reports for the chosen GPU.
+    pub device_name: String,
+    pub device_type: vk::PhysicalDeviceType,
+    /// Vulkan version the driver supports.
+    pub api_version: u32,
+}
+
+/// "Discrete GPU", "Software (CPU)" and the like.
+fn device_type_name(ty: vk::PhysicalDeviceType) -> &'static str {
+    match ty {
+        vk::PhysicalDeviceType::DISCRETE_GPU => "Discrete GPU",
+        vk::PhysicalDeviceType::INTEGRATED_GPU => "Integrated GPU",
+        vk::PhysicalDeviceType::VIRTUAL_GPU => "Virtual GPU",
+        vk::PhysicalDeviceType::CPU => "Software (CPU)",
+        _ => "Other device",
+    }
 }
 
 impl VkContext {
+    /// Kind of device and API version, e.g. "Discrete GPU, Vulkan 1.3.280".
+    pub fn device_summary(&self) -> String {
+        format!(
+            "{}, Vulkan {}.{}.{}",
+            device_type_name(self.device_type),
+            vk::api_version_major(self.api_version),
+            vk::api_version_minor(self.api_version),
+            vk::api_version_patch(self.api_version),
+        )
+    }
+
     pub fn new() -> IrisResult<Arc<Self>> {
         unsafe {
             let entry = Entry::load()
@@ -53,24 +143,24 @@ impl VkContext {
                 ));
             }
 
-            let physical_device = physical_devices
+            let devices: Vec<(vk::PhysicalDeviceType, String)> = physical_devices
                 .iter()
-                .copied()
-                .find(|&p| {
-                    instance.get_physical_device_properties(p).device_type
-                        == vk::PhysicalDeviceType::DISCRETE_GPU
-                })
-                .or_else(|| {
-                    physical_devices.iter().copied().find(|&p| {
-                        instance.get_physical_device_properties(p).device_type
-                            == vk::PhysicalDeviceType::INTEGRATED_GPU
-                    })
+                .map(|&p| {
+                    let props = instance.get_physical_device_properties(p);
+                    let name = std::ffi::CStr::from_ptr(props.device_name.as_ptr())
+                        .to_string_lossy()
+                        .into_owned();
+                    (props.device_type, name)
                 })
-                .unwrap_or(physical_devices[0]);
+                .collect();
+            for (ty, name) in &devices {
+                println!("[Iris] Vulkan device: {name} ({ty:?})");
+            }
+            let chosen = pick_device(&devices, &GpuPreference::from_env());
+            let physical_device = physical_devices[chosen];
 
             let props = instance.get_physical_device_properties(physical_device);
-            let name = std::ffi::CStr::from_ptr(props.device_name.as_ptr()).to_string_lossy();
-            println!("[Iris] Vulkan GPU: {name}");
+            println!("[Iris] Vulkan GPU: {}", devices[chosen].1);
 
             let queue_family_index = instance
                 .get_physical_device_queue_family_properties(physical_device)
@@ -93,9 +183,17 @@ impl VkContext {
                 .queue_family_index(queue_family_index)
                 .queue_priorities(&priorities);
 
+            let sampler_anisotropy = instance
+                .get_physical_device_features(physical_device)
+                .sampler_anisotropy
+                == vk::TRUE;
+            let enabled_features =
+                vk::PhysicalDeviceFeatures::default().sampler_anisotropy(sampler_anisotropy);
+
             let device_create_info = vk::DeviceCreateInfo::default()
                 .queue_create_infos(std::slice::from_ref(&queue_info))
-                .enabled_extension_names(&device_extensions);
+                .enabled_extension_names(&device_extensions)
+                .enabled_features(&enabled_features);
 
             let device = vk_check!(
                 instance.create_device(physical_device, &device_create_info, None),
@@ -125,6 +223,10 @@ impl VkContext {
                 memory_properties,
                 command_pool,
                 device_limits: props.limits,
+                sampler_anisotropy,
+                device_name: devices[chosen].1.clone(),
+                device_type: props.device_type,
+                api_version: props.api_version,
             }))
         }
     }
@@ -225,3 +327,65 @@ impl Drop for VkContext {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn laptop() -> Vec<(vk::PhysicalDeviceType, String)> {
+        vec![
+            (vk::PhysicalDeviceType::CPU, "llvmpipe".into()),
+            (
+                vk::PhysicalDeviceType::INTEGRATED_GPU,
+                "Intel Iris Xe".into(),
+            ),
+            (
+                vk::PhysicalDeviceType::DISCRETE_GPU,
+                "NVIDIA RTX 3050".into(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn parses_preferences() {
+        assert_eq!(GpuPreference::parse(""), GpuPreference::Discrete);
+        assert_eq!(
+            GpuPreference::parse(" Integrated "),
+            GpuPreference::Integrated
+        );
+        assert_eq!(
+            GpuPreference::parse("NVIDIA"),
+            GpuPreference::Named("NVIDIA".into())
+        );
+    }
+
+    #[test]
+    fn names_device_types() {
+        assert_eq!(
+            device_type_name(vk::PhysicalDeviceType::INTEGRATED_GPU),
+            "Integrated GPU"
+        );
+        assert_eq!(
+            device_type_name(vk::PhysicalDeviceType::CPU),
+            "Software (CPU)"
+        );
+    }
+
+    #[test]
+    fn picks_devices_by_preference() {
+        let devices = laptop();
+        assert_eq!(pick_device(&devices, &GpuPreference::Discrete), 2);
+        assert_eq!(pick_device(&devices, &GpuPreference::Integrated), 1);
+        assert_eq!(
+            pick_device(&devices, &GpuPreference::Named("llvm".into())),
+            0
+        );
+        // Unknown names fall back instead of failing.
+        assert_eq!(
+            pick_device(&devices, &GpuPreference::Named("radeon".into())),
+            2
+        );
+        // Only a software device: use it rather than nothing.
+        assert_eq!(pick_device(&devices[..1], &GpuPreference::Integrated), 0);
+    }
+}
@@ -521,9 +521,29 @@ impl DmabufImage {
         }
     }
 
-    /// Map the HOST_VISIBLE export image and copy pixels to a `Vec<u8>`.
+    /// Whether the last blit into the export image has finished, without
+    /// waiting for it.
+    pub fn blit_finished(&self) -> bool {
+        unsafe {
+            self.context
+                .device
+                .get_fence_status(self.blit_fence)
+                .unwrap_or(true)
+        }
+    }
+
+    /// Map the HOST_VISIBLE export image and copy pixels to a `Vec<u8>`,
+    /// once the last blit into it has finished.
     pub fn read_pixels(&self) -> IrisResult<Vec<u8>> {
         unsafe {
+            vk_check!(
+                self.context.device.wait_for_fences(
+                    std::slice::from_ref(&self.blit_fence),
+                    true,
+                    u64::MAX
+                ),
+                "vkWaitForFences(read_pixels)"
+            )?;
             let ptr = vk_check!(
                 self.context.device.map_memory(
                     self.export_memory,
@@ -6,12 +6,25 @@ use std::sync::Arc;
 use crate::error::{IrisError, IrisResult};
 use crate::vk_check;
 
+/// Samples per pixel when antialiasing is on.
+pub const MSAA_SAMPLES: vk::SampleCountFlags = vk::SampleCountFlags::TYPE_4;
+
+/// A render pass and the image pipeline built against it.
+pub struct ImagePass {
+    pub render_pass: vk::RenderPass,
+    pub pipeline: vk::Pipeline,
+}
+
 pub struct VkPipeline {
     pub context: Arc<VkContext>,
     pub render_pass: vk::RenderPass,
     pub descriptor_set_layout: vk::DescriptorSetLayout,
     pub pipeline_layout: vk::PipelineLayout,
     pub pipeline: vk::Pipeline,
+    /// Draws into an `MSAA_SAMPLES` attachment and resolves into the same
+    /// single-sample target as `render_pass`. None when the GPU can't
+    /// render that many samples.
+    pub msaa: Option<ImagePass>,
 }
 
 impl VkPipeline {
@@ -63,36 +76,12 @@ impl VkPipeline {
             )?;
 
             // 3. Render Pass — uses the negotiated format, not hardcoded
-            let color_attachment = vk::AttachmentDescription::default()
-                .format(color_format)
-                .samples(vk::SampleCountFlags::TYPE_1)
-                .load_op(vk::AttachmentLoadOp::CLEAR)
-                .store_op(vk::AttachmentStoreOp::STORE)
-                .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
-                .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
-                .initial_layout(vk::ImageLayout::UNDEFINED)
-                .final_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL);
-
-            let color_attachment_ref = vk::AttachmentReference::default()
-                .attachment(0)
-                .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
-
-            let subpass = vk::SubpassDescription::default()
-                .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
-                .color_attachments(std::slice::from_ref(&color_attachment_ref));
-
-            let render_pass_info = vk::RenderPassCreateInfo::default()
-                .attachments(std::slice::from_ref(&color_attachment))
-                .subpasses(std::slice::from_ref(&subpass));
-
-            let render_pass = vk_check!(
-                context.device.create_render_pass(&render_pass_info, None),
-                "vkCreateRenderPass"
-            )?;
+            let render_pass =
+                create_render_pass(&context.device, color_format, vk::SampleCountFlags::TYPE_1)?;
 
             // 4. Compile Shaders
             let shader_src = include_str!("../shaders/image.wgsl");
-            let shader_module = shader::compile_wgsl(&context.device, shader_src);
+            let shader_module = shader::compile_wgsl(&context.device, shader_src)?;
 
             let entry_point_vs = std::ffi::CString::new("vs_main").unwrap();
             let entry_point_fs = std::ffi::CString::new("fs_main").unwrap();
@@ -108,67 +97,35 @@ impl VkPipeline {
                     .name(&entry_point_fs),
             ];
 
-            // 5. Fixed-Function State
-            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
-
-            let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
-                .topology(vk::PrimitiveTopology::TRIANGLE_LIST);
-
-            let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
-            let dynamic_state =
-                vk::PipelineDynamicStateCreateInfo::default().dynamic_states(&dynamic_states);
-
-            let viewport_state = vk::PipelineViewportStateCreateInfo::default()
-                .viewport_count(1)
-                .scissor_count(1);
-
-            let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
-                .polygon_mode(vk::PolygonMode::FILL)
-                .cull_mode(vk::CullModeFlags::NONE)
-                .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
-                .line_width(1.0);
-
-            let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
-                .rasterization_samples(vk::SampleCountFlags::TYPE_1);
-
-            let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
-                .color_write_mask(vk::ColorComponentFlags::RGBA)
-                .blend_enable(true)
-                .src_color_blend_factor(vk::BlendFactor::ONE)
-                .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
-                .color_blend_op(vk::BlendOp::ADD)
-                .src_alpha_blend_factor(vk::BlendFactor::ONE)
-                .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
-                .alpha_blend_op(vk::BlendOp::ADD);
-
-            let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
-                .attachments(std::slice::from_ref(&color_blend_attachment));
-
-            // 6. Bake the Pipeline
-            let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
-                .stages(&shader_stages)
-                .vertex_input_state(&vertex_input_info)
-                .input_assembly_state(&input_assembly)
-                .viewport_state(&viewport_state)
-                .rasterization_state(&rasterizer)
-                .multisample_state(&multisampling)
-                .color_blend_state(&color_blending)
-                .dynamic_state(&dynamic_state)
-                .layout(pipeline_layout)
-                .render_pass(render_pass)
-                .subpass(0);
-
-            let pipeline = context
-                .device
-                .create_graphics_pipelines(
-                    vk::PipelineCache::null(),
-                    std::slice::from_ref(&pipeline_info),
-                    None,
-                )
-                .map_err(|(_pipelines, code)| IrisError::Vk {
-                    call: "vkCreateGraphicsPipelines",
-                    code,
-                })?[0];
+            // 5. Bake the Pipelines
+            let pipeline = create_graphics_pipeline(
+                &context.device,
+                &shader_stages,
+                pipeline_layout,
+                render_pass,
+                vk::SampleCountFlags::TYPE_1,
+            )?;
+
+            let msaa_supported = context
+                .device_limits
+                .framebuffer_color_sample_counts
+                .contains(MSAA_SAMPLES);
+            let msaa = if msaa_supported {
+                let render_pass = create_render_pass(&context.device, color_format, MSAA_SAMPLES)?;
+                let pipeline = create_graphics_pipeline(
+                    &context.device,
+                    &shader_stages,
+                    pipeline_layout,
+                    render_pass,
+                    MSAA_SAMPLES,
+                )?;
+                Some(ImagePass {
+                    render_pass,
+                    pipeline,
+                })
+            } else {
+                None
+            };
 
             // Clean up shader module (driver retains its own compiled form)
             context.device.destroy_shader_module(shader_module, None);
@@ -179,6 +136,7 @@ impl VkPipeline {
                 descriptor_set_layout,
                 pipeline_layout,
                 pipeline,
+                msaa,
             })
         }
     }
@@ -188,6 +146,12 @@ impl Drop for VkPipeline {
     fn drop(&mut self) {
         unsafe {
             self.context.device.destroy_pipeline(self.pipeline, None);
+            if let Some(msaa) = &self.msaa {
+                self.context.device.destroy_pipeline(msaa.pipeline, None);
+                self.context
+                    .device
+                    .destroy_render_pass(msaa.render_pass, None);
+            }
             self.context
                 .device
                 .destroy_pipeline_layout(self.pipeline_layout, None);
@@ -200,3 +164,126 @@ impl Drop for VkPipeline {
         }
     }
 }
+
+/// The image render pass. Multisampled, it draws into attachment 0 and
+/// resolves into attachment 1, the single-sample target read back and
+/// exported; otherwise it draws into that target directly.
+unsafe fn create_render_pass(
+    device: &ash::Device,
+    color_format: vk::Format,
+    samples: vk::SampleCountFlags,
+) -> IrisResult<vk::RenderPass> {
+    let target = vk::AttachmentDescription::default()
+        .format(color_format)
+        .samples(vk::SampleCountFlags::TYPE_1)
+        .load_op(vk::AttachmentLoadOp::CLEAR)
+        .store_op(vk::AttachmentStoreOp::STORE)
+        .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+        .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .final_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL);
+
+    let color_ref = vk::AttachmentReference::default()
+        .attachment(0)
+        .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+    let resolve_ref = vk::AttachmentReference::default()
+        .attachment(1)
+        .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+    let multisampled = samples != vk::SampleCountFlags::TYPE_1;
+    let attachments = if multisampled {
+        // The samples only live until they're resolved.
+        let samples_attachment = target
+            .samples(samples)
+            .store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .final_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+        let resolved = target.load_op(vk::AttachmentLoadOp::DONT_CARE);
+        vec![samples_attachment, resolved]
+    } else {
+        vec![target]
+    };
+
+    let mut subpass = vk::SubpassDescription::default()
+        .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+        .color_attachments(std::slice::from_ref(&color_ref));
+    if multisampled {
+        subpass = subpass.resolve_attachments(std::slice::from_ref(&resolve_ref));
+    }
+
+    let render_pass_info = vk::RenderPassCreateInfo::default()
+        .attachments(&attachments)
+        .subpasses(std::slice::from_ref(&subpass));
+
+    vk_check!(
+        device.create_render_pass(&render_pass_info, None),
+        "vkCreateRenderPass"
+    )
+}
+
+unsafe fn create_graphics_pipeline(
+    device: &ash::Device,
+    shader_stages: &[vk::PipelineShaderStageCreateInfo],
+    pipeline_layout: vk::PipelineLayout,
+    render_pass: vk::RenderPass,
+    samples: vk::SampleCountFlags,
+) -> IrisResult<vk::Pipeline> {
+    let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+
+    let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+        .topology(vk::PrimitiveTopology::TRIANGLE_LIST);
+
+    let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+    let dynamic_state =
+        vk::PipelineDynamicStateCreateInfo::default().dynamic_states(&dynamic_states);
+
+    let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+        .viewport_count(1)
+        .scissor_count(1);
+
+    let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+        .polygon_mode(vk::PolygonMode::FILL)
+        .cull_mode(vk::CullModeFlags::NONE)
+        .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+        .line_width(1.0);
+
+    let multisampling =
+        vk::PipelineMultisampleStateCreateInfo::default().rasterization_samples(samples);
+
+    let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+        .color_write_mask(vk::ColorComponentFlags::RGBA)
+        .blend_enable(true)
+        .src_color_blend_factor(vk::BlendFactor::ONE)
+        .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+        .color_blend_op(vk::BlendOp::ADD)
+        .src_alpha_blend_factor(vk::BlendFactor::ONE)
+        .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
+        .alpha_blend_op(vk::BlendOp::ADD);
+
+    let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+        .attachments(std::slice::from_ref(&color_blend_attachment));
+
+    let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+        .stages(shader_stages)
+        .vertex_input_state(&vertex_input_info)
+        .input_assembly_state(&input_assembly)
+        .viewport_state(&viewport_state)
+        .rasterization_state(&rasterizer)
+        .multisample_state(&multisampling)
+        .color_blend_state(&color_blending)
+        .dynamic_state(&dynamic_state)
+        .layout(pipeline_layout)
+        .render_pass(render_pass)
+        .subpass(0);
+
+    let pipelines = device
+        .create_graphics_pipelines(
+            vk::PipelineCache::null(),
+            std::slice::from_ref(&pipeline_info),
+            None,
+        )
+        .map_err(|(_pipelines, code)| IrisError::Vk {
+            call: "vkCreateGraphicsPipelines",
+            code,
+        })?;
+    Ok(pipelines[0])
+}
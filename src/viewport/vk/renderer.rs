@@ -1,5 +1,6 @@
 use ash::vk;
 use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -8,7 +9,7 @@ use std::sync::Arc;
 use super::compute::{ComputeInfra, ComputeParams, ProcessingPass};
 use super::context::VkContext;
 use super::dmabuf::DmabufImage;
-use super::pipeline::VkPipeline;
+use super::pipeline::{MSAA_SAMPLES, VkPipeline};
 use crate::color::DynamicRange;
 use crate::error::{IrisError, IrisResult};
 use crate::viewport::camera::Camera;
@@ -23,6 +24,222 @@ struct Uniforms {
     pan: [f32; 2],
     tone_map_enabled: f32,
     hdr_output_enabled: f32,
+    flip: [f32; 2],
+    checker_size: f32,
+    brightness: f32,
+    background: [f32; 4],
+    contrast: f32,
+    grayscale: f32,
+    invert: f32,
+    compare_tone_map: f32,
+    quads: [QuadRect; MAX_QUADS],
+    compare_scale: [f32; 2],
+    transition_tone_map: f32,
+    fade: f32,
+    transition_scale: [f32; 2],
+    transition_offset: [f32; 2],
+    active_offset: [f32; 2],
+    straighten: f32,
+    aspect: f32,
+    exposure: f32,
+    gamma: f32,
+    dither: f32,
+    _pad: f32,
+}
+
+/// What shows through the transparent parts of an image.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlphaBackground {
+    #[default]
+    Checkerboard,
+    Black,
+    White,
+    /// The viewport's own background color.
+    Theme,
+}
+
+impl AlphaBackground {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Checkerboard => Self::Black,
+            Self::Black => Self::White,
+            Self::White => Self::Theme,
+            Self::Theme => Self::Checkerboard,
+        }
+    }
+
+    /// Checker cell size in pixels (0 for a solid fill) and the fill color.
+    fn uniform(self) -> (f32, [f32; 4]) {
+        match self {
+            Self::Checkerboard => (CHECKER_SIZE_PX, [0.0; 4]),
+            Self::Black => (0.0, [0.0, 0.0, 0.0, 1.0]),
+            Self::White => (0.0, [1.0; 4]),
+            Self::Theme => (0.0, CLEAR_COLOR),
+        }
+    }
+}
+
+/// Non-destructive tone adjustments, applied in the fragment shader.
+/// Brightness and contrast range over -1.0..=1.0 with 0.0 leaving the image
+/// untouched; grayscale and invert apply on top of them. Exposure and gamma
+/// come first: `exposure` is in stops (0.0 neutral) and `gamma` brightens
+/// the midtones above 1.0 and darkens them below (1.0 neutral).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Adjustments {
+    pub brightness: f32,
+    pub contrast: f32,
+    pub grayscale: bool,
+    pub invert: bool,
+    pub exposure: f32,
+    pub gamma: f32,
+}
+
+impl Default for Adjustments {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 0.0,
+            grayscale: false,
+            invert: false,
+            exposure: 0.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+/// Exposure and gamma slider limits.
+pub const EXPOSURE_RANGE_STOPS: f32 = 4.0;
+pub const GAMMA_RANGE: (f32, f32) = (0.25, 4.0);
+
+const CHECKER_SIZE_PX: f32 = 8.0;
+const CLEAR_COLOR: [f32; 4] = [0.051, 0.051, 0.051, 1.0];
+
+/// Decoded images kept on the GPU unless configured otherwise.
+pub const DEFAULT_CACHE_CAPACITY: usize = 8;
+
+/// Upper bound on images drawn in one pass by `render_strip`.
+pub const MAX_QUADS: usize = 16;
+
+/// Placement of one image in a multi-image pass:
+/// `[center_x, center_y, half_width, half_height]` in NDC.
+pub type QuadRect = [f32; 4];
+
+/// Instance index that draws the compare image through the camera, with
+/// its own fit scale. Indices 1..=MAX_QUADS select strip quads.
+const COMPARE_INSTANCE: u32 = MAX_QUADS as u32 + 1;
+
+/// Instance index that draws the outgoing image of a transition.
+const TRANSITION_INSTANCE: u32 = COMPARE_INSTANCE + 1;
+
+/// Default position of the compare divider, as a fraction of the width.
+pub const DEFAULT_COMPARE_SPLIT: f32 = 0.5;
+
+/// Which draws `record_and_submit` issues for its descriptor sets.
+#[derive(Clone, Copy)]
+enum DrawLayout {
+    /// One set through the camera.
+    Camera,
+    /// Each set with the quad at the same index.
+    Quads,
+    /// Active and compare set left and right of the given column.
+    Split(u32),
+    /// The compare set alone, through the camera.
+    CompareOnly,
+    /// The outgoing set, then the active one over it.
+    Transition,
+}
+
+/// DRM fourcc of `R8G8B8A8_UNORM`, bytes R, G, B, A in memory.
+const DRM_FORMAT_ABGR8888: u32 = 0x34324241;
+/// DRM fourcc of `B8G8R8A8_UNORM`, bytes B, G, R, A in memory.
+const DRM_FORMAT_ARGB8888: u32 = 0x34325241;
+/// The X variants of the above, whose fourth byte is padding.
+const DRM_FORMAT_XBGR8888: u32 = 0x34324258;
+const DRM_FORMAT_XRGB8888: u32 = 0x34325258;
+
+/// Width of the line drawn between the two halves of a compare frame.
+const DIVIDER_WIDTH_PX: u32 = 2;
+const DIVIDER_COLOR: [f32; 4] = [0.8, 0.8, 0.8, 1.0];
+
+/// How a compare image is shown next to the active one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompareLayout {
+    /// Both images, split by a draggable divider.
+    #[default]
+    SideBySide,
+    /// Only the active image, until a peek swaps in the compare image.
+    Toggle,
+}
+
+/// How navigating from one image to the next is animated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageTransition {
+    /// The new image replaces the old at once.
+    #[default]
+    None,
+    Crossfade,
+    /// The new image pushes the old out, in the direction of navigation.
+    Slide,
+}
+
+impl ImageTransition {
+    pub const ALL: [Self; 3] = [Self::None, Self::Crossfade, Self::Slide];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Crossfade => "Crossfade",
+            Self::Slide => "Slide",
+        }
+    }
+
+    /// Opacity of the incoming image and the horizontal NDC offsets of the
+    /// outgoing and incoming ones, `progress` (0..=1) into the transition.
+    /// A slide with no direction crossfades instead.
+    fn frame(self, progress: f32, direction: i32) -> (f32, f32, f32) {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Self::Slide if direction != 0 => {
+                // Ease out, so the new image settles into place.
+                let eased = 1.0 - (1.0 - t) * (1.0 - t);
+                let d = direction.signum() as f32;
+                (1.0, -d * 2.0 * eased, d * 2.0 * (1.0 - eased))
+            }
+            Self::None => (1.0, 0.0, 0.0),
+            _ => (t, 0.0, 0.0),
+        }
+    }
+}
+
+/// The image being navigated away from, while the active one animates in.
+struct Transition {
+    from: PathBuf,
+    direction: i32,
+    progress: f32,
+}
+
+/// Per-frame state of a transition draw.
+struct FadeFrame {
+    /// Fit scale of the outgoing image.
+    scale: [f32; 2],
+    tone_map: bool,
+    /// Opacity of the active image drawn over it.
+    opacity: f32,
+    from_offset: f32,
+    active_offset: f32,
+}
+
+/// Per-frame state of a compare draw.
+struct SplitFrame {
+    /// Framebuffer column where the right half starts; `None` while
+    /// peeking, when only the compare image is drawn, across the frame.
+    divider: Option<u32>,
+    /// Fit scales of the active and compare image within their areas.
+    scale: [f32; 2],
+    compare_scale: [f32; 2],
+    compare_tone_map: bool,
 }
 
 struct CachedTexture {
@@ -30,6 +247,8 @@ struct CachedTexture {
     image_view: vk::ImageView,
     memory: vk::DeviceMemory,
     descriptor_set: vk::DescriptorSet,
+    /// Size of the source image. The texture itself may be smaller if it
+    /// was downscaled to fit the GPU's dimension limit.
     dims: (u32, u32),
     memory_bytes: u64,
     dynamic_range: DynamicRange,
@@ -90,6 +309,100 @@ unsafe fn mip_barrier(
     );
 }
 
+/// Multisampled color attachment the image is drawn into before being
+/// resolved into a render target.
+struct MsaaImage {
+    image: vk::Image,
+    image_view: vk::ImageView,
+    memory: vk::DeviceMemory,
+}
+
+impl MsaaImage {
+    unsafe fn new(
+        context: &VkContext,
+        width: u32,
+        height: u32,
+        vk_format: vk::Format,
+    ) -> IrisResult<Self> {
+        let image = vk_check!(
+            context.device.create_image(
+                &vk::ImageCreateInfo::default()
+                    .image_type(vk::ImageType::TYPE_2D)
+                    .format(vk_format)
+                    .extent(vk::Extent3D {
+                        width,
+                        height,
+                        depth: 1,
+                    })
+                    .mip_levels(1)
+                    .array_layers(1)
+                    .samples(MSAA_SAMPLES)
+                    .tiling(vk::ImageTiling::OPTIMAL)
+                    .usage(
+                        vk::ImageUsageFlags::COLOR_ATTACHMENT
+                            | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+                    )
+                    .initial_layout(vk::ImageLayout::UNDEFINED),
+                None,
+            ),
+            "vkCreateImage(msaa)"
+        )?;
+
+        let req = context.device.get_image_memory_requirements(image);
+        // Tilers can keep transient samples in on-chip memory.
+        let mem_idx = context
+            .find_memory_type_index(&req, vk::MemoryPropertyFlags::LAZILY_ALLOCATED)
+            .or_else(|| context.find_memory_type_index(&req, vk::MemoryPropertyFlags::DEVICE_LOCAL))
+            .ok_or(IrisError::NoMemoryType("msaa image"))?;
+
+        let memory = vk_check!(
+            context.device.allocate_memory(
+                &vk::MemoryAllocateInfo::default()
+                    .allocation_size(req.size)
+                    .memory_type_index(mem_idx),
+                None,
+            ),
+            "vkAllocateMemory(msaa)"
+        )?;
+
+        vk_check!(
+            context.device.bind_image_memory(image, memory, 0),
+            "vkBindImageMemory(msaa)"
+        )?;
+
+        let image_view = vk_check!(
+            context.device.create_image_view(
+                &vk::ImageViewCreateInfo::default()
+                    .image(image)
+                    .view_type(vk::ImageViewType::TYPE_2D)
+                    .format(vk_format)
+                    .subresource_range(
+                        vk::ImageSubresourceRange::default()
+                            .aspect_mask(vk::ImageAspectFlags::COLOR)
+                            .base_mip_level(0)
+                            .level_count(1)
+                            .base_array_layer(0)
+                            .layer_count(1),
+                    ),
+                None,
+            ),
+            "vkCreateImageView(msaa)"
+        )?;
+
+        Ok(Self {
+            image,
+            image_view,
+            memory,
+        })
+    }
+
+    unsafe fn destroy(&self, device: &ash::Device) {
+        device.destroy_image_view(self.image_view, None);
+        device.destroy_image(self.image, None);
+        device.free_memory(self.memory, None);
+    }
+}
+
 struct ProcessingImage {
     image: vk::Image,
     image_view: vk::ImageView,
@@ -217,10 +530,29 @@ pub struct VkRenderer {
     cache_order: Vec<PathBuf>,
     cache_memory_used: u64,
     cache_memory_budget: u64,
+    /// Maximum number of cached images, independent of the VRAM budget.
+    cache_capacity: usize,
 
     active_path: Option<PathBuf>,
+    /// Image shown in compare mode, how it is laid out, the divider's
+    /// position as a fraction of the width, and whether it is being peeked.
+    compare_path: Option<PathBuf>,
+    compare_layout: CompareLayout,
+    compare_split: f32,
+    compare_peek: bool,
+    transition_style: ImageTransition,
+    /// Off when the desktop asks for reduced motion; changes of image are
+    /// then instant whatever `transition_style` says.
+    animations_enabled: bool,
+    /// Dither the output before it's quantized to 8 bits. Off by default
+    /// so frames stay pixel-exact.
+    dither: bool,
+    transition: Option<Transition>,
 
     render_targets: [DmabufImage; 2],
+    /// Multisampled attachments resolved into `render_targets` while
+    /// antialiasing is on.
+    msaa_targets: Option<[MsaaImage; 2]>,
     framebuffers: [vk::Framebuffer; 2],
     command_buffers: [vk::CommandBuffer; 2],
     fences: [vk::Fence; 2],
@@ -228,11 +560,26 @@ pub struct VkRenderer {
 
     framebuffer_width: u32,
     framebuffer_height: u32,
+    /// Device pixels per logical pixel. The framebuffer is sized in device
+    /// pixels while the camera viewport stays logical.
+    scale_factor: u32,
 
     pub dirty: bool,
+    /// Set while the window is being resized interactively: frames keep
+    /// drawing into the current targets, stretched to fit, and they're
+    /// reallocated only once the size settles.
+    pub resize_held: bool,
+    /// Camera the current single-image frame was drawn with. While it
+    /// matches and nothing else is dirty, the frame on screen is current.
+    last_camera: Option<Camera>,
     pub image_dims: (f32, f32),
     pub tone_map_enabled: bool,
+    pub alpha_background: AlphaBackground,
+    pub adjustments: Adjustments,
     last_sync_fd: Option<std::os::fd::RawFd>,
+    /// Set once GTK fails to import a frame as a DMA-BUF; every later frame
+    /// goes straight to CPU readback instead of exporting and failing again.
+    dmabuf_rejected: bool,
 
     pub vk_format: vk::Format,
     pub format_fourcc: u32,
@@ -245,6 +592,18 @@ pub struct VkRenderer {
 }
 
 impl VkRenderer {
+    /// A renderer with no display attached, drawing into RGBA8 targets that
+    /// `render_to_image` reads back.
+    pub fn headless(width: u32, height: u32) -> IrisResult<Self> {
+        Self::new(
+            VkContext::new()?,
+            width,
+            height,
+            vk::Format::R8G8B8A8_UNORM,
+            DRM_FORMAT_ABGR8888,
+        )
+    }
+
     pub fn new(
         context: Arc<VkContext>,
         width: u32,
@@ -284,6 +643,9 @@ impl VkRenderer {
                 "vkCreateDescriptorPool"
             )?;
 
+            // Trilinear across the mip chain, plus anisotropy where the GPU
+            // offers it, so zoomed-out high-frequency detail doesn't alias.
+            let max_anisotropy = context.device_limits.max_sampler_anisotropy.min(16.0);
             let sampler = vk_check!(
                 context.device.create_sampler(
                     &vk::SamplerCreateInfo::default()
@@ -293,6 +655,8 @@ impl VkRenderer {
                         .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
                         .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
                         .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+                        .anisotropy_enable(context.sampler_anisotropy)
+                        .max_anisotropy(max_anisotropy.max(1.0))
                         .min_lod(0.0)
                         .max_lod(16.0)
                         .unnormalized_coordinates(false),
@@ -367,14 +731,14 @@ impl VkRenderer {
             let fb0 = create_framebuffer(
                 &context.device,
                 pipeline.render_pass,
-                rt0.render_image_view,
+                &[rt0.render_image_view],
                 width,
                 height,
             )?;
             let fb1 = create_framebuffer(
                 &context.device,
                 pipeline.render_pass,
-                rt1.render_image_view,
+                &[rt1.render_image_view],
                 width,
                 height,
             )?;
@@ -449,18 +813,34 @@ impl VkRenderer {
                 cache_order: Vec::new(),
                 cache_memory_used: 0,
                 cache_memory_budget,
+                cache_capacity: DEFAULT_CACHE_CAPACITY,
                 active_path: None,
+                compare_path: None,
+                compare_layout: CompareLayout::default(),
+                compare_split: DEFAULT_COMPARE_SPLIT,
+                compare_peek: false,
+                transition_style: ImageTransition::default(),
+                animations_enabled: true,
+                dither: false,
+                transition: None,
                 render_targets: [rt0, rt1],
+                msaa_targets: None,
                 framebuffers: [fb0, fb1],
                 command_buffers: [cb0, cb1],
                 fences: [fence0, fence1],
                 frame_index: 0,
                 framebuffer_width: width,
                 framebuffer_height: height,
+                scale_factor: 1,
                 dirty: true,
+                resize_held: false,
+                last_camera: None,
                 image_dims: (1.0, 1.0),
                 tone_map_enabled: false,
+                alpha_background: AlphaBackground::default(),
+                adjustments: Adjustments::default(),
                 last_sync_fd: None,
+                dmabuf_rejected: false,
                 vk_format,
                 format_fourcc,
                 compute,
@@ -498,6 +878,14 @@ impl VkRenderer {
                 .device
                 .wait_for_fences(&self.fences, true, u64::MAX);
 
+            if self.msaa_targets.is_some() {
+                self.destroy_msaa_targets();
+                match self.create_msaa_targets(width, height) {
+                    Ok(targets) => self.msaa_targets = Some(targets),
+                    Err(e) => eprintln!("[Iris] resize MSAA targets failed: {e}"),
+                }
+            }
+
             for i in 0..2 {
                 self.context
                     .device
@@ -517,13 +905,7 @@ impl VkRenderer {
                     }
                 }
 
-                match create_framebuffer(
-                    &self.context.device,
-                    self.pipeline.render_pass,
-                    self.render_targets[i].render_image_view,
-                    width,
-                    height,
-                ) {
+                match self.create_slot_framebuffer(i, width, height) {
                     Ok(fb) => self.framebuffers[i] = fb,
                     Err(e) => {
                         eprintln!("[Iris] resize framebuffer failed: {e}");
@@ -566,6 +948,100 @@ impl VkRenderer {
         }
     }
 
+    /// Whether the GPU can draw multisampled; without it antialiasing is
+    /// never turned on.
+    pub fn antialiasing_supported(&self) -> bool {
+        self.pipeline.msaa.is_some()
+    }
+
+    /// Draw with `MSAA_SAMPLES` samples per pixel, smoothing the edges of a
+    /// rotated or zoomed-out image against the background.
+    pub fn set_antialiasing(&mut self, enabled: bool) {
+        let enabled = enabled && self.antialiasing_supported();
+        if enabled == self.msaa_targets.is_some() {
+            return;
+        }
+
+        unsafe {
+            let _ = self
+                .context
+                .device
+                .wait_for_fences(&self.fences, true, u64::MAX);
+
+            self.destroy_msaa_targets();
+            if enabled {
+                match self.create_msaa_targets(self.framebuffer_width, self.framebuffer_height) {
+                    Ok(targets) => self.msaa_targets = Some(targets),
+                    Err(e) => eprintln!("[Iris] MSAA targets failed: {e}"),
+                }
+            }
+
+            for i in 0..2 {
+                match self.create_slot_framebuffer(
+                    i,
+                    self.framebuffer_width,
+                    self.framebuffer_height,
+                ) {
+                    Ok(fb) => {
+                        self.context
+                            .device
+                            .destroy_framebuffer(self.framebuffers[i], None);
+                        self.framebuffers[i] = fb;
+                    }
+                    Err(e) => eprintln!("[Iris] MSAA framebuffer failed: {e}"),
+                }
+            }
+        }
+        self.dirty = true;
+    }
+
+    unsafe fn create_msaa_targets(&self, width: u32, height: u32) -> IrisResult<[MsaaImage; 2]> {
+        let a = MsaaImage::new(&self.context, width, height, self.vk_format)?;
+        match MsaaImage::new(&self.context, width, height, self.vk_format) {
+            Ok(b) => Ok([a, b]),
+            Err(e) => {
+                a.destroy(&self.context.device);
+                Err(e)
+            }
+        }
+    }
+
+    unsafe fn destroy_msaa_targets(&mut self) {
+        if let Some(targets) = self.msaa_targets.take() {
+            for target in &targets {
+                target.destroy(&self.context.device);
+            }
+        }
+    }
+
+    /// Render pass and pipeline for the current antialiasing setting.
+    fn image_pass(&self) -> (vk::RenderPass, vk::Pipeline) {
+        match (&self.msaa_targets, &self.pipeline.msaa) {
+            (Some(_), Some(msaa)) => (msaa.render_pass, msaa.pipeline),
+            _ => (self.pipeline.render_pass, self.pipeline.pipeline),
+        }
+    }
+
+    unsafe fn create_slot_framebuffer(
+        &self,
+        slot: usize,
+        width: u32,
+        height: u32,
+    ) -> IrisResult<vk::Framebuffer> {
+        let target = self.render_targets[slot].render_image_view;
+        let (render_pass, _) = self.image_pass();
+        match &self.msaa_targets {
+            Some(msaa) => create_framebuffer(
+                &self.context.device,
+                render_pass,
+                &[msaa[slot].image_view, target],
+                width,
+                height,
+            ),
+            None => create_framebuffer(&self.context.device, render_pass, &[target], width, height),
+        }
+    }
+
     pub fn upload_and_activate(&mut self, path: &Path, rgba: &[u8], w: u32, h: u32) -> (u32, u32) {
         self.upload_texture(path, rgba, w, h);
         self.tone_map_enabled = false;
@@ -594,6 +1070,25 @@ impl VkRenderer {
         self.upload_texture_16bit(path, rgba16, w, h);
     }
 
+    /// Upload scene-linear float pixels, e.g. OpenEXR, as a 16-bit float
+    /// texture that is tone mapped like RAW.
+    pub fn upload_and_activate_float(
+        &mut self,
+        path: &Path,
+        rgba: &[f32],
+        w: u32,
+        h: u32,
+    ) -> (u32, u32) {
+        self.upload_texture_float(path, rgba, w, h);
+        self.tone_map_enabled = true;
+        self.activate(path);
+        (w, h)
+    }
+
+    pub fn cache_only_float(&mut self, path: &Path, rgba: &[f32], w: u32, h: u32) {
+        self.upload_texture_float(path, rgba, w, h);
+    }
+
     pub fn activate_cached(&mut self, path: &Path) -> Option<(f32, f32)> {
         if self.cache.contains_key(path) {
             self.activate(path);
@@ -605,6 +1100,59 @@ impl VkRenderer {
         }
     }
 
+    /// Mark a cached image as recently used without activating it.
+    pub fn touch(&mut self, path: &Path) {
+        if self.cache.contains_key(path) {
+            self.cache_order.retain(|p| p != path);
+            self.cache_order.insert(0, path.to_owned());
+        }
+    }
+
+    /// Takes effect on the next upload.
+    pub fn set_cache_capacity(&mut self, capacity: usize) {
+        self.cache_capacity = capacity.max(1);
+    }
+
+    /// Name of the GPU this renders on.
+    pub fn device_name(&self) -> &str {
+        &self.context.device_name
+    }
+
+    /// Kind of GPU and Vulkan version, to go with `device_name`.
+    pub fn device_summary(&self) -> String {
+        self.context.device_summary()
+    }
+
+    /// Drop `path` from the texture cache, unless it is the active image.
+    pub fn evict(&mut self, path: &Path) {
+        if self.active_path.as_deref() == Some(path) {
+            return;
+        }
+        let Some(texture) = self.cache.remove(path) else {
+            return;
+        };
+        // Either frame in flight may still sample it.
+        unsafe {
+            let _ = self
+                .context
+                .device
+                .wait_for_fences(&self.fences, true, u64::MAX);
+            texture.destroy(&self.context.device, self.descriptor_pool);
+        }
+        self.cache_memory_used = self.cache_memory_used.saturating_sub(texture.memory_bytes);
+        self.cache_order.retain(|p| p != path);
+    }
+
+    /// Drop `path` from the texture cache even while it is on screen, e.g.
+    /// after its file was rewritten. The last frame stays up until another
+    /// image is activated.
+    pub fn forget(&mut self, path: &Path) {
+        if self.active_path.as_deref() == Some(path) {
+            self.active_path = None;
+        }
+        self.evict(path);
+    }
+
     pub fn is_cached(&self, path: &Path) -> bool {
         self.cache.contains_key(path)
     }
@@ -649,34 +1197,258 @@ impl VkRenderer {
         self.active_passes.contains(&pass)
     }
 
-    pub fn render(&mut self, camera: &Camera) {
-        if !self.dirty {
-            return;
+    /// Draw the active image if the camera moved or the renderer is dirty.
+    /// Returns whether a new frame was submitted.
+    pub fn render(&mut self, camera: &Camera) -> bool {
+        if !self.dirty && self.last_camera.as_ref() == Some(camera) {
+            return false;
         }
 
         let active_path = match &self.active_path {
             Some(p) => p.clone(),
-            None => return,
+            None => return false,
         };
 
         // Don't render the blank placeholder during resize — it causes
         // a fence stall because the compositor may still hold the previous
         // 1×1 DMA-BUF from the initial blank render.
         if active_path == PathBuf::from("__blank__") && self.framebuffer_width > 1 {
-            return;
+            return false;
         }
 
         let descriptor_set = match self.cache.get(&active_path) {
             Some(c) => c.descriptor_set,
-            None => return,
+            None => return false,
+        };
+
+        match self.split_frame(camera) {
+            Some((compare_set, split)) if split.divider.is_some() => {
+                self.submit_frame(
+                    camera,
+                    &[descriptor_set, compare_set],
+                    &[],
+                    Some(split),
+                    None,
+                );
+            }
+            Some((compare_set, split)) => {
+                self.submit_frame(camera, &[compare_set], &[], Some(split), None);
+            }
+            None => match self.fade_frame(camera) {
+                Some((from_set, fade)) => {
+                    self.submit_frame(camera, &[from_set, descriptor_set], &[], None, Some(fade));
+                }
+                None => self.submit_frame(camera, &[descriptor_set], &[], None, None),
+            },
+        }
+        true
+    }
+
+    pub fn set_transition_style(&mut self, style: ImageTransition) {
+        self.transition_style = style;
+        if style == ImageTransition::None {
+            self.transition = None;
+        }
+    }
+
+    pub fn set_animations_enabled(&mut self, enabled: bool) {
+        self.animations_enabled = enabled;
+        if !enabled {
+            self.transition = None;
+        }
+    }
+
+    pub fn set_dither(&mut self, enabled: bool) {
+        if enabled != self.dither {
+            self.dither = enabled;
+            self.dirty = true;
+        }
+    }
+
+    /// Animate from the active image to the next one activated, moving
+    /// in `direction` (+1 forward, -1 back, 0 a jump). Returns whether a
+    /// transition will play; the caller drives it with
+    /// `set_transition_progress`.
+    pub fn begin_transition(&mut self, direction: i32) -> bool {
+        self.transition = None;
+        if self.transition_style == ImageTransition::None || !self.animations_enabled {
+            return false;
+        }
+        let Some(from) = self.active_path.clone() else {
+            return false;
+        };
+        if from == Path::new("__blank__") || !self.cache.contains_key(&from) {
+            return false;
+        }
+        self.transition = Some(Transition {
+            from,
+            direction,
+            progress: 0.0,
+        });
+        true
+    }
+
+    /// Advance the transition to `progress` (0..=1), ending it at 1.
+    /// Returns whether it is still running.
+    pub fn set_transition_progress(&mut self, progress: f32) -> bool {
+        let Some(transition) = self.transition.as_mut() else {
+            return false;
+        };
+        self.dirty = true;
+        if progress >= 1.0 {
+            self.transition = None;
+            return false;
+        }
+        transition.progress = progress;
+        true
+    }
+
+    /// Descriptor set and placement of the outgoing image, mid-transition.
+    fn fade_frame(&self, camera: &Camera) -> Option<(vk::DescriptorSet, FadeFrame)> {
+        let transition = self.transition.as_ref()?;
+        if self.active_path.as_ref() == Some(&transition.from) {
+            return None;
+        }
+        let from = self.cache.get(&transition.from)?;
+        let (opacity, from_offset, active_offset) = self
+            .transition_style
+            .frame(transition.progress, transition.direction);
+        let fade = FadeFrame {
+            scale: camera.fit_scale(from.dims.0 as f32, from.dims.1 as f32),
+            tone_map: matches!(from.dynamic_range, DynamicRange::Hdr),
+            opacity,
+            from_offset,
+            active_offset,
         };
+        Some((from.descriptor_set, fade))
+    }
+
+    /// Compare the active image with `path` (a cached image), both
+    /// following the same camera. `None` leaves compare mode.
+    pub fn set_compare(&mut self, path: Option<&Path>, layout: CompareLayout) {
+        self.compare_path = path.map(Path::to_owned);
+        self.compare_layout = layout;
+        self.compare_split = DEFAULT_COMPARE_SPLIT;
+        self.compare_peek = false;
+        self.dirty = true;
+    }
+
+    /// Divider position as a fraction of the width, while comparing side
+    /// by side.
+    pub fn compare_split(&self) -> Option<f32> {
+        let side_by_side = self.compare_layout == CompareLayout::SideBySide;
+        self.compare_path
+            .as_ref()
+            .filter(|_| side_by_side)
+            .map(|_| self.compare_split)
+    }
+
+    /// Show only the compare image, in place of the active one, while
+    /// `peek` holds.
+    pub fn set_compare_peek(&mut self, peek: bool) {
+        if self.compare_peek != peek {
+            self.compare_peek = peek;
+            self.dirty = true;
+        }
+    }
+
+    pub fn set_compare_split(&mut self, split: f32) {
+        self.compare_split = split.clamp(0.1, 0.9);
+        self.dirty = true;
+    }
+
+    /// Descriptor set and layout of the compare image, if it is shown.
+    fn split_frame(&mut self, camera: &Camera) -> Option<(vk::DescriptorSet, SplitFrame)> {
+        if !self.compare_peek && self.compare_layout == CompareLayout::Toggle {
+            return None;
+        }
+        let path = self.compare_path.clone()?;
+        let compare = self.cache.get(&path)?;
+        let (descriptor_set, dims, hdr) = (
+            compare.descriptor_set,
+            compare.dims,
+            matches!(compare.dynamic_range, DynamicRange::Hdr),
+        );
+        // Keep the compare image ahead of the LRU like the active one.
+        self.touch(&path);
+
+        if self.compare_peek {
+            let split = SplitFrame {
+                divider: None,
+                scale: camera.fit_scale(self.image_dims.0, self.image_dims.1),
+                compare_scale: camera.fit_scale(dims.0 as f32, dims.1 as f32),
+                compare_tone_map: hdr,
+            };
+            return Some((descriptor_set, split));
+        }
+
+        let width = self.framebuffer_width;
+        let divider = ((width as f32 * self.compare_split).round() as u32)
+            .clamp(1, width.saturating_sub(1).max(1));
+        // Each half fits its image to its own aspect ratio; zoom, pan and
+        // rotation are shared. The camera viewport is in logical pixels.
+        let left = Camera {
+            viewport_width: (divider / self.scale_factor).max(1),
+            ..*camera
+        };
+        let right = Camera {
+            viewport_width: (width.saturating_sub(divider) / self.scale_factor).max(1),
+            ..*camera
+        };
+        let split = SplitFrame {
+            divider: Some(divider),
+            scale: left.fit_scale(self.image_dims.0, self.image_dims.1),
+            compare_scale: right.fit_scale(dims.0 as f32, dims.1 as f32),
+            compare_tone_map: hdr,
+        };
+        Some((descriptor_set, split))
+    }
+
+    /// Render several cached images in one pass, each placed by its own
+    /// quad instead of the camera transform. Paths that aren't cached are
+    /// skipped; the rest are marked recently used so the LRU keeps them.
+    pub fn render_strip(&mut self, camera: &Camera, items: &[(PathBuf, QuadRect)]) {
+        let mut sets = Vec::with_capacity(items.len().min(MAX_QUADS));
+        let mut quads = Vec::with_capacity(items.len().min(MAX_QUADS));
+        for (path, rect) in items.iter().take(MAX_QUADS) {
+            if let Some(c) = self.cache.get(path) {
+                sets.push(c.descriptor_set);
+                quads.push(*rect);
+                self.cache_order.retain(|p| p != path);
+                self.cache_order.insert(0, path.clone());
+            }
+        }
+
+        self.submit_frame(camera, &sets, &quads, None, None);
+    }
 
+    /// Record, submit and export one frame. With `quads` empty the single
+    /// descriptor set is drawn through the camera, or laid out by `split`
+    /// or `fade`; otherwise each set is drawn with the quad at the same
+    /// index.
+    fn submit_frame(
+        &mut self,
+        camera: &Camera,
+        descriptor_sets: &[vk::DescriptorSet],
+        quads: &[QuadRect],
+        split: Option<SplitFrame>,
+        fade: Option<FadeFrame>,
+    ) {
         let cur = self.frame_index % 2;
 
         let result: IrisResult<()> = (|| unsafe {
             self.wait_fence(cur)?;
-            self.write_uniforms(camera);
-            self.record_and_submit(descriptor_set, cur)?;
+            self.write_uniforms(camera, quads, split.as_ref(), fade.as_ref());
+            let layout = match split {
+                Some(SplitFrame {
+                    divider: Some(d), ..
+                }) => DrawLayout::Split(d),
+                Some(_) => DrawLayout::CompareOnly,
+                None if fade.is_some() => DrawLayout::Transition,
+                None if quads.is_empty() => DrawLayout::Camera,
+                None => DrawLayout::Quads,
+            };
+            self.record_and_submit(descriptor_sets, layout, cur)?;
 
             if !self.active_passes.is_empty() {
                 self.run_compute_passes(cur)?;
@@ -695,6 +1467,7 @@ impl VkRenderer {
 
         self.frame_index = self.frame_index.wrapping_add(1);
         self.dirty = false;
+        self.last_camera = quads.is_empty().then_some(*camera);
     }
 
     pub fn take_sync_fd(&mut self) -> Option<std::os::fd::RawFd> {
@@ -702,11 +1475,19 @@ impl VkRenderer {
     }
 
     pub fn export_fd_for_gtk(&self) -> Option<std::os::fd::RawFd> {
+        if self.dmabuf_rejected {
+            return None;
+        }
         self.render_targets[self.presented_slot()]
             .export_fd_for_gtk()
             .ok()
     }
 
+    /// Stop offering frames as DMA-BUFs for the rest of the session.
+    pub fn reject_dmabuf(&mut self) {
+        self.dmabuf_rejected = true;
+    }
+
     pub fn render_target_stride(&self) -> u32 {
         self.render_targets[self.presented_slot()].stride
     }
@@ -723,12 +1504,57 @@ impl VkRenderer {
         self.framebuffer_height
     }
 
+    /// Set the display's scale factor, so that on-screen decorations such
+    /// as the checkerboard and the compare divider keep their logical size.
+    pub fn set_scale_factor(&mut self, scale: u32) {
+        let scale = scale.max(1);
+        if scale != self.scale_factor {
+            self.scale_factor = scale;
+            self.dirty = true;
+        }
+    }
+
+    /// Frames submitted so far; a change means a newer frame superseded
+    /// the one being read back.
+    pub fn frame_count(&self) -> usize {
+        self.frame_index
+    }
+
+    /// Whether `read_pixels` can return the presented frame without
+    /// blocking on the GPU.
+    pub fn readback_ready(&self) -> bool {
+        self.render_targets[self.presented_slot()].blit_finished()
+    }
+
     pub fn read_pixels(&self) -> Option<Vec<u8>> {
         self.render_targets[self.presented_slot()]
             .read_pixels()
             .ok()
     }
 
+    /// Draw `camera`'s view of the active image and read the frame back
+    /// instead of presenting it, for exports and tests without a window.
+    pub fn render_to_image(&mut self, camera: &Camera) -> IrisResult<image::RgbaImage> {
+        self.dirty = true;
+        if !self.render(camera) {
+            return Err(IrisError::Other("Nothing to render".into()));
+        }
+        // No compositor waits on this frame.
+        if let Some(fd) = self.take_sync_fd() {
+            unsafe { libc::close(fd) };
+        }
+        let target = &self.render_targets[self.presented_slot()];
+        let pixels = target.read_pixels()?;
+        frame_to_rgba(
+            &pixels,
+            self.framebuffer_width,
+            self.framebuffer_height,
+            target.stride,
+            target.format_fourcc,
+        )
+        .ok_or_else(|| IrisError::Other("Frame read back was truncated".into()))
+    }
+
     fn presented_slot(&self) -> usize {
         self.frame_index.wrapping_sub(1) % 2
     }
@@ -763,8 +1589,22 @@ impl VkRenderer {
         Ok(())
     }
 
-    unsafe fn write_uniforms(&self, camera: &Camera) {
-        let scale = camera.fit_scale(self.image_dims.0, self.image_dims.1);
+    unsafe fn write_uniforms(
+        &self,
+        camera: &Camera,
+        quads: &[QuadRect],
+        split: Option<&SplitFrame>,
+        fade: Option<&FadeFrame>,
+    ) {
+        let scale = match split {
+            Some(s) => s.scale,
+            None => camera.fit_scale(self.image_dims.0, self.image_dims.1),
+        };
+        let mut quad_array = [[0.0; 4]; MAX_QUADS];
+        for (dst, src) in quad_array.iter_mut().zip(quads) {
+            *dst = *src;
+        }
+        let (checker_size, background) = self.alpha_background.uniform();
         let uniforms = Uniforms {
             scale,
             rotation: camera.rotation,
@@ -772,6 +1612,38 @@ impl VkRenderer {
             pan: [camera.position.x, camera.position.y],
             tone_map_enabled: if self.tone_map_enabled { 1.0 } else { 0.0 },
             hdr_output_enabled: 0.0,
+            flip: [
+                if camera.flip_h { -1.0 } else { 1.0 },
+                if camera.flip_v { -1.0 } else { 1.0 },
+            ],
+            checker_size: checker_size * self.scale_factor as f32,
+            brightness: self.adjustments.brightness,
+            background,
+            contrast: self.adjustments.contrast,
+            grayscale: if self.adjustments.grayscale { 1.0 } else { 0.0 },
+            invert: if self.adjustments.invert { 1.0 } else { 0.0 },
+            compare_tone_map: if split.is_some_and(|s| s.compare_tone_map) {
+                1.0
+            } else {
+                0.0
+            },
+            quads: quad_array,
+            compare_scale: split.map_or([1.0, 1.0], |s| s.compare_scale),
+            transition_tone_map: if fade.is_some_and(|f| f.tone_map) {
+                1.0
+            } else {
+                0.0
+            },
+            fade: fade.map_or(1.0, |f| f.opacity),
+            transition_scale: fade.map_or([1.0, 1.0], |f| f.scale),
+            transition_offset: [fade.map_or(0.0, |f| f.from_offset), 0.0],
+            active_offset: [fade.map_or(0.0, |f| f.active_offset), 0.0],
+            straighten: camera.straighten,
+            aspect: camera.viewport_width as f32 / camera.viewport_height.max(1) as f32,
+            exposure: self.adjustments.exposure,
+            gamma: self.adjustments.gamma.max(GAMMA_RANGE.0),
+            dither: if self.dither { 1.0 } else { 0.0 },
+            _pad: 0.0,
         };
         std::ptr::copy_nonoverlapping(
             &uniforms as *const Uniforms as *const u8,
@@ -782,7 +1654,8 @@ impl VkRenderer {
 
     unsafe fn record_and_submit(
         &self,
-        descriptor_set: vk::DescriptorSet,
+        descriptor_sets: &[vk::DescriptorSet],
+        layout: DrawLayout,
         slot: usize,
     ) -> IrisResult<()> {
         let cmd = self.command_buffers[slot];
@@ -801,12 +1674,13 @@ impl VkRenderer {
 
         let clear_values = [vk::ClearValue {
             color: vk::ClearColorValue {
-                float32: [0.051, 0.051, 0.051, 1.0],
+                float32: CLEAR_COLOR,
             },
         }];
 
+        let (render_pass, pipeline) = self.image_pass();
         let render_pass_begin = vk::RenderPassBeginInfo::default()
-            .render_pass(self.pipeline.render_pass)
+            .render_pass(render_pass)
             .framebuffer(self.framebuffers[slot])
             .render_area(vk::Rect2D {
                 offset: vk::Offset2D { x: 0, y: 0 },
@@ -823,45 +1697,98 @@ impl VkRenderer {
             vk::SubpassContents::INLINE,
         );
 
-        self.context.device.cmd_bind_pipeline(
-            cmd,
-            vk::PipelineBindPoint::GRAPHICS,
-            self.pipeline.pipeline,
-        );
-
-        let viewport = vk::Viewport {
-            x: 0.0,
-            y: 0.0,
-            width: self.framebuffer_width as f32,
-            height: self.framebuffer_height as f32,
-            min_depth: 0.0,
-            max_depth: 1.0,
-        };
-        let scissor = vk::Rect2D {
-            offset: vk::Offset2D { x: 0, y: 0 },
-            extent: vk::Extent2D {
-                width: self.framebuffer_width,
-                height: self.framebuffer_height,
-            },
-        };
-
-        self.context
-            .device
-            .cmd_set_viewport(cmd, 0, std::slice::from_ref(&viewport));
         self.context
             .device
-            .cmd_set_scissor(cmd, 0, std::slice::from_ref(&scissor));
+            .cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, pipeline);
+
+        let set_area = |x: u32, width: u32| {
+            let viewport = vk::Viewport {
+                x: x as f32,
+                y: 0.0,
+                width: width as f32,
+                height: self.framebuffer_height as f32,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            };
+            let scissor = vk::Rect2D {
+                offset: vk::Offset2D { x: x as i32, y: 0 },
+                extent: vk::Extent2D {
+                    width,
+                    height: self.framebuffer_height,
+                },
+            };
+            self.context
+                .device
+                .cmd_set_viewport(cmd, 0, std::slice::from_ref(&viewport));
+            self.context
+                .device
+                .cmd_set_scissor(cmd, 0, std::slice::from_ref(&scissor));
+        };
 
-        self.context.device.cmd_bind_descriptor_sets(
-            cmd,
-            vk::PipelineBindPoint::GRAPHICS,
-            self.pipeline.pipeline_layout,
-            0,
-            std::slice::from_ref(&descriptor_set),
-            &[],
-        );
+        set_area(0, self.framebuffer_width);
 
-        self.context.device.cmd_draw(cmd, 6, 1, 0, 0);
+        for (i, descriptor_set) in descriptor_sets.iter().enumerate() {
+            self.context.device.cmd_bind_descriptor_sets(
+                cmd,
+                vk::PipelineBindPoint::GRAPHICS,
+                self.pipeline.pipeline_layout,
+                0,
+                std::slice::from_ref(descriptor_set),
+                &[],
+            );
+
+            // Instance 0 selects the camera transform; instance i + 1 selects
+            // quad i. A compare frame draws each half into its own viewport;
+            // a transition draws the outgoing image, then the active one.
+            let first_instance = match layout {
+                DrawLayout::Camera => 0,
+                DrawLayout::Quads => i as u32 + 1,
+                DrawLayout::Split(d) if i == 0 => {
+                    set_area(0, d);
+                    0
+                }
+                DrawLayout::Split(d) => {
+                    set_area(d, self.framebuffer_width - d);
+                    COMPARE_INSTANCE
+                }
+                DrawLayout::CompareOnly => COMPARE_INSTANCE,
+                DrawLayout::Transition if i == 0 => TRANSITION_INSTANCE,
+                DrawLayout::Transition => 0,
+            };
+            self.context.device.cmd_draw(cmd, 6, 1, 0, first_instance);
+        }
+
+        if let DrawLayout::Split(d) = layout {
+            let divider_width = DIVIDER_WIDTH_PX * self.scale_factor;
+            let attachment = vk::ClearAttachment {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                color_attachment: 0,
+                clear_value: vk::ClearValue {
+                    color: vk::ClearColorValue {
+                        float32: DIVIDER_COLOR,
+                    },
+                },
+            };
+            let line = vk::ClearRect {
+                rect: vk::Rect2D {
+                    offset: vk::Offset2D {
+                        x: d.saturating_sub(divider_width / 2) as i32,
+                        y: 0,
+                    },
+                    extent: vk::Extent2D {
+                        width: divider_width.min(self.framebuffer_width),
+                        height: self.framebuffer_height,
+                    },
+                },
+                base_array_layer: 0,
+                layer_count: 1,
+            };
+            self.context.device.cmd_clear_attachments(
+                cmd,
+                std::slice::from_ref(&attachment),
+                std::slice::from_ref(&line),
+            );
+        }
         self.context.device.cmd_end_render_pass(cmd);
 
         self.context
@@ -1149,6 +2076,34 @@ impl VkRenderer {
         Ok(())
     }
 
+    /// Evict least-recently-used textures until one more of `incoming` bytes
+    /// fits both the VRAM budget and the entry cap. The blank placeholder and
+    /// the images on screen, active and compared, are never evicted.
+    fn make_room(&mut self, incoming: u64) {
+        let blank = Path::new("__blank__");
+        loop {
+            let entries = self.cache_order.iter().filter(|p| *p != blank).count();
+            let over_budget = self.cache_memory_used + incoming > self.cache_memory_budget;
+            if !over_budget && entries < self.cache_capacity {
+                break;
+            }
+            let on_screen = [self.active_path.as_deref(), self.compare_path.as_deref()];
+            let Some(pos) = self
+                .cache_order
+                .iter()
+                .rposition(|p| p != blank && !on_screen.contains(&Some(p.as_path())))
+            else {
+                break;
+            };
+            let oldest = self.cache_order.remove(pos);
+            if let Some(evicted) = self.cache.remove(&oldest) {
+                unsafe { evicted.destroy(&self.context.device, self.descriptor_pool) };
+                self.cache_memory_used =
+                    self.cache_memory_used.saturating_sub(evicted.memory_bytes);
+            }
+        }
+    }
+
     fn upload_texture(&mut self, path: &Path, rgba: &[u8], w: u32, h: u32) {
         if let Some(old) = self.cache.remove(path) {
             unsafe { old.destroy(&self.context.device, self.descriptor_pool) };
@@ -1156,6 +2111,7 @@ impl VkRenderer {
             self.cache_order.retain(|p| p != path);
         }
 
+        let source_dims = (w, h);
         let max_dim = self.context.device_limits.max_image_dimension2_d;
         let (w, h, owned): (u32, u32, Cow<[u8]>) = if w > max_dim || h > max_dim {
             let scale = max_dim as f32 / w.max(h) as f32;
@@ -1178,19 +2134,7 @@ impl VkRenderer {
         let rgba: &[u8] = &owned;
 
         let mem = (w as u64) * (h as u64) * 4;
-
-        while self.cache_memory_used + mem > self.cache_memory_budget {
-            let oldest = match self.cache_order.last().cloned() {
-                Some(p) if p != PathBuf::from("__blank__") => p,
-                _ => break,
-            };
-            if let Some(evicted) = self.cache.remove(&oldest) {
-                unsafe { evicted.destroy(&self.context.device, self.descriptor_pool) };
-                self.cache_memory_used =
-                    self.cache_memory_used.saturating_sub(evicted.memory_bytes);
-                self.cache_order.pop();
-            }
-        }
+        self.make_room(mem);
 
         unsafe {
             match upload_rgba_texture(
@@ -1204,7 +2148,8 @@ impl VkRenderer {
                 h,
                 self.vk_format,
             ) {
-                Ok(cached) => {
+                Ok(mut cached) => {
+                    cached.dims = source_dims;
                     self.cache_memory_used += mem;
                     self.cache_order.insert(0, path.to_owned());
                     self.cache.insert(path.to_owned(), cached);
@@ -1221,6 +2166,7 @@ impl VkRenderer {
             self.cache_order.retain(|p| p != path);
         }
 
+        let source_dims = (w, h);
         let max_dim = self.context.device_limits.max_image_dimension2_d;
         let (w, h, owned): (u32, u32, Cow<[u16]>) = if w > max_dim || h > max_dim {
             let scale = max_dim as f32 / w.max(h) as f32;
@@ -1247,19 +2193,69 @@ impl VkRenderer {
         let rgba16: &[u16] = &owned;
 
         let mem = (w as u64) * (h as u64) * 8;
+        self.make_room(mem);
 
-        while self.cache_memory_used + mem > self.cache_memory_budget {
-            let oldest = match self.cache_order.last().cloned() {
-                Some(p) if p != PathBuf::from("__blank__") => p,
-                _ => break,
-            };
-            if let Some(evicted) = self.cache.remove(&oldest) {
-                unsafe { evicted.destroy(&self.context.device, self.descriptor_pool) };
-                self.cache_memory_used =
-                    self.cache_memory_used.saturating_sub(evicted.memory_bytes);
-                self.cache_order.pop();
+        unsafe {
+            match upload_rgba16_texture(
+                &self.context,
+                self.descriptor_pool,
+                self.pipeline.descriptor_set_layout,
+                self.uniform_buffer,
+                self.sampler,
+                rgba16,
+                w,
+                h,
+                vk::Format::R16G16B16A16_UNORM,
+            ) {
+                Ok(mut cached) => {
+                    cached.dims = source_dims;
+                    self.cache_memory_used += mem;
+                    self.cache_order.insert(0, path.to_owned());
+                    self.cache.insert(path.to_owned(), cached);
+                }
+                Err(e) => eprintln!("[Iris] upload_texture_16bit failed: {e}"),
             }
         }
+    }
+
+    /// Like `upload_texture_16bit`, halving float pixels to 16-bit floats,
+    /// which keep highlights far past 1.0 at half the memory of 32-bit.
+    fn upload_texture_float(&mut self, path: &Path, rgba: &[f32], w: u32, h: u32) {
+        if let Some(old) = self.cache.remove(path) {
+            unsafe { old.destroy(&self.context.device, self.descriptor_pool) };
+            self.cache_memory_used = self.cache_memory_used.saturating_sub(old.memory_bytes);
+            self.cache_order.retain(|p| p != path);
+        }
+
+        let source_dims = (w, h);
+        let max_dim = self.context.device_limits.max_image_dimension2_d;
+        let (w, h, owned): (u32, u32, Cow<[f32]>) = if w > max_dim || h > max_dim {
+            let scale = max_dim as f32 / w.max(h) as f32;
+            let new_w = ((w as f32 * scale) as u32).max(1);
+            let new_h = ((h as f32 * scale) as u32).max(1);
+            eprintln!(
+                "[Iris] HDR {}×{} exceeds GPU limit (max {}); downscaling to {}×{}",
+                w, h, max_dim, new_w, new_h
+            );
+            let Some(src) = image::Rgba32FImage::from_raw(w, h, rgba.to_vec()) else {
+                eprintln!("[Iris] HDR downscale failed: buffer length mismatch");
+                return;
+            };
+            // Triangle rather than Lanczos: its weights are never negative,
+            // so bright highlights can't ring into negative light.
+            let dst =
+                image::imageops::resize(&src, new_w, new_h, image::imageops::FilterType::Triangle);
+            (new_w, new_h, Cow::Owned(dst.into_raw()))
+        } else {
+            (w, h, Cow::Borrowed(rgba))
+        };
+        let half: Vec<u16> = owned
+            .iter()
+            .map(|&v| crate::color::f32_to_f16_bits(v))
+            .collect();
+
+        let mem = (w as u64) * (h as u64) * 8;
+        self.make_room(mem);
 
         unsafe {
             match upload_rgba16_texture(
@@ -1268,16 +2264,18 @@ impl VkRenderer {
                 self.pipeline.descriptor_set_layout,
                 self.uniform_buffer,
                 self.sampler,
-                rgba16,
+                &half,
                 w,
                 h,
+                vk::Format::R16G16B16A16_SFLOAT,
             ) {
-                Ok(cached) => {
+                Ok(mut cached) => {
+                    cached.dims = source_dims;
                     self.cache_memory_used += mem;
                     self.cache_order.insert(0, path.to_owned());
                     self.cache.insert(path.to_owned(), cached);
                 }
-                Err(e) => eprintln!("[Iris] upload_texture_16bit failed: {e}"),
+                Err(e) => eprintln!("[Iris] upload_texture_float failed: {e}"),
             }
         }
     }
@@ -1301,6 +2299,7 @@ impl Drop for VkRenderer {
             if let Some(ref pi) = self.processing_b {
                 pi.destroy(&self.context.device);
             }
+            self.destroy_msaa_targets();
 
             for i in 0..2 {
                 self.context.device.destroy_fence(self.fences[i], None);
@@ -1332,7 +2331,7 @@ impl Drop for VkRenderer {
 unsafe fn create_framebuffer(
     device: &ash::Device,
     render_pass: vk::RenderPass,
-    image_view: vk::ImageView,
+    attachments: &[vk::ImageView],
     width: u32,
     height: u32,
 ) -> IrisResult<vk::Framebuffer> {
@@ -1340,7 +2339,7 @@ unsafe fn create_framebuffer(
         device.create_framebuffer(
             &vk::FramebufferCreateInfo::default()
                 .render_pass(render_pass)
-                .attachments(std::slice::from_ref(&image_view))
+                .attachments(attachments)
                 .width(width)
                 .height(height)
                 .layers(1),
@@ -1658,6 +2657,8 @@ unsafe fn upload_rgba_texture(
     })
 }
 
+/// Upload 16 bits per channel of linear light: `R16G16B16A16_UNORM` for
+/// RAW, or `R16G16B16A16_SFLOAT` half floats for HDR formats.
 unsafe fn upload_rgba16_texture(
     context: &VkContext,
     descriptor_pool: vk::DescriptorPool,
@@ -1667,6 +2668,7 @@ unsafe fn upload_rgba16_texture(
     rgba16: &[u16],
     w: u32,
     h: u32,
+    vk_format: vk::Format,
 ) -> IrisResult<CachedTexture> {
     let mip_levels = compute_mip_levels(w, h);
     let data_size = (w as u64) * (h as u64) * 8;
@@ -1732,7 +2734,7 @@ unsafe fn upload_rgba16_texture(
         .create_image(
             &vk::ImageCreateInfo::default()
                 .image_type(vk::ImageType::TYPE_2D)
-                .format(vk::Format::R16G16B16A16_UNORM)
+                .format(vk_format)
                 .extent(vk::Extent3D {
                     width: w,
                     height: h,
@@ -1930,7 +2932,7 @@ unsafe fn upload_rgba16_texture(
             &vk::ImageViewCreateInfo::default()
                 .image(image)
                 .view_type(vk::ImageViewType::TYPE_2D)
-                .format(vk::Format::R16G16B16A16_UNORM)
+                .format(vk_format)
                 .subresource_range(
                     vk::ImageSubresourceRange::default()
                         .aspect_mask(vk::ImageAspectFlags::COLOR)
@@ -2037,3 +3039,103 @@ unsafe fn allocate_descriptor_set(
 
     Ok(descriptor_set)
 }
+
+/// Unpack a read-back frame of `stride`-byte rows into tightly packed RGBA,
+/// swapping channels for the BGR formats and making padding bytes opaque.
+/// `None` if `pixels` is too short for the frame.
+fn frame_to_rgba(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    stride: u32,
+    fourcc: u32,
+) -> Option<image::RgbaImage> {
+    let row_bytes = width as usize * 4;
+    let stride = stride as usize;
+    if stride < row_bytes || pixels.len() < stride * height.saturating_sub(1) as usize + row_bytes {
+        return None;
+    }
+    let bgr = matches!(fourcc, DRM_FORMAT_ARGB8888 | DRM_FORMAT_XRGB8888);
+    let opaque = matches!(fourcc, DRM_FORMAT_XBGR8888 | DRM_FORMAT_XRGB8888);
+    let mut out = Vec::with_capacity(row_bytes * height as usize);
+    for row in pixels.chunks(stride).take(height as usize) {
+        for px in row[..row_bytes].chunks_exact(4) {
+            let (r, b) = if bgr { (px[2], px[0]) } else { (px[0], px[2]) };
+            out.extend_from_slice(&[r, px[1], b, if opaque { 255 } else { px[3] }]);
+        }
+    }
+    image::RgbaImage::from_raw(width, height, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpacks_read_back_frames() {
+        // 1x2 frame with 8-byte rows: BGRA pixels, then padding.
+        let pixels = [10, 20, 30, 40, 0, 0, 0, 0, 50, 60, 70, 80];
+        let img = frame_to_rgba(&pixels, 1, 2, 8, DRM_FORMAT_ARGB8888).unwrap();
+        assert_eq!(img.get_pixel(0, 0).0, [30, 20, 10, 40]);
+        assert_eq!(img.get_pixel(0, 1).0, [70, 60, 50, 80]);
+
+        let img = frame_to_rgba(&pixels, 1, 2, 8, DRM_FORMAT_XBGR8888).unwrap();
+        assert_eq!(img.get_pixel(0, 1).0, [50, 60, 70, 255]);
+
+        assert!(frame_to_rgba(&pixels[..8], 1, 2, 8, DRM_FORMAT_ABGR8888).is_none());
+    }
+
+    #[test]
+    fn solid_colors_read_back_as_displayed() {
+        let Ok(mut r) = VkRenderer::headless(8, 8) else {
+            eprintln!("skipping: no Vulkan device");
+            return;
+        };
+        let mut camera = Camera::new();
+        camera.set_viewport_size(8, 8);
+        camera.set_image_size(1, 1);
+        let close =
+            |got: [u8; 4], want: [u8; 4]| got.iter().zip(want).all(|(&g, w)| g.abs_diff(w) <= 1);
+
+        // 8-bit pixels are already sRGB-encoded and pass through.
+        r.upload_and_activate(Path::new("solid8"), &[200, 100, 50, 255], 1, 1);
+        let frame = r.render_to_image(&camera).unwrap();
+        let got = frame.get_pixel(4, 4).0;
+        assert!(close(got, [200, 100, 50, 255]), "{got:?}");
+
+        // Linear 16-bit pixels are tone mapped, then encoded to sRGB.
+        let linear = [0, 65535, 65535 / 5, 65535];
+        r.upload_and_activate_16bit(Path::new("solid16"), &linear, 1, 1);
+        let frame = r.render_to_image(&camera).unwrap();
+        let encode = |v: u16| {
+            let mapped = crate::color::aces_tonemap(v as f32 / 65535.0);
+            (crate::color::linear_to_srgb(mapped) * 255.0).round() as u8
+        };
+        let got = frame.get_pixel(4, 4).0;
+        let want = [encode(linear[0]), encode(linear[1]), encode(linear[2]), 255];
+        assert!(close(got, want), "{got:?} != {want:?}");
+    }
+
+    #[test]
+    fn keeps_the_compared_image_cached() {
+        let Ok(mut r) = VkRenderer::headless(4, 4) else {
+            eprintln!("skipping: no Vulkan device");
+            return;
+        };
+        r.set_cache_capacity(2);
+        let pixel = [255, 0, 0, 255];
+        let (a, b) = (Path::new("a.png"), Path::new("b.png"));
+        r.cache_only(b, &pixel, 1, 1);
+        r.upload_and_activate(a, &pixel, 1, 1);
+        r.set_compare(Some(b), CompareLayout::SideBySide);
+
+        // Prefetches fill the cache past its cap; neither image on screen
+        // may go.
+        for name in ["c.png", "d.png", "e.png"] {
+            r.cache_only(Path::new(name), &pixel, 1, 1);
+        }
+        assert!(r.is_cached(a));
+        assert!(r.is_cached(b));
+        assert!(r.split_frame(&Camera::new()).is_some());
+    }
+}
@@ -5,7 +5,26 @@ use naga::{
     valid::{Capabilities, ValidationFlags, Validator},
 };
 
-pub fn compile_wgsl(device: &ash::Device, source: &str) -> vk::ShaderModule {
+use crate::error::IrisResult;
+use crate::vk_check;
+
+/// Compile a bundled WGSL shader into a Vulkan shader module. The WGSL
+/// itself is checked by the tests below, so only the driver can fail here,
+/// and that is reported so the viewport can fall back to software.
+pub fn compile_wgsl(device: &ash::Device, source: &str) -> IrisResult<vk::ShaderModule> {
+    let words = wgsl_to_spirv(source);
+
+    // 4. Create Vulkan Shader Module
+    let create_info = vk::ShaderModuleCreateInfo::default().code(&words);
+    unsafe {
+        vk_check!(
+            device.create_shader_module(&create_info, None),
+            "vkCreateShaderModule"
+        )
+    }
+}
+
+fn wgsl_to_spirv(source: &str) -> Vec<u32> {
     // 1. Parse WGSL
     let module = wgsl::parse_str(source).expect("Failed to parse WGSL shader");
 
@@ -24,12 +43,22 @@ pub fn compile_wgsl(device: &ash::Device, source: &str) -> vk::ShaderModule {
     writer
         .write(&module, &info, None, &None, &mut words)
         .expect("Failed to write SPIR-V");
+    words
+}
 
-    // 4. Create Vulkan Shader Module
-    let create_info = vk::ShaderModuleCreateInfo::default().code(&words);
-    unsafe {
-        device
-            .create_shader_module(&create_info, None)
-            .expect("Failed to create Vulkan Shader Module")
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_shaders_compile() {
+        for source in [
+            include_str!("../shaders/image.wgsl"),
+            include_str!("../shaders/enhance.wgsl"),
+            include_str!("../shaders/sharpen.wgsl"),
+            include_str!("../shaders/denoise.wgsl"),
+        ] {
+            assert!(!wgsl_to_spirv(source).is_empty());
+        }
     }
 }